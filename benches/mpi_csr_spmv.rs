@@ -0,0 +1,120 @@
+//! Benchmark of the distributed CSR matrix-vector product.
+//!
+//! Run with `mpirun -n 4 cargo bench --bench mpi_csr_spmv --features mpi`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use mpi::collective::SystemOperation;
+use mpi::traits::*;
+use rlst::prelude::*;
+
+/// Number of grid points in each direction of the two-dimensional grid.
+const GRID_SIZE: usize = 1000;
+
+/// Number of timed matrix-vector products.
+const NSAMPLES: usize = 100;
+
+pub fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let rank = world.rank() as usize;
+    let size = world.size() as usize;
+    let ndofs = GRID_SIZE * GRID_SIZE;
+
+    let index_layout = DefaultMpiIndexLayout::new(ndofs, 1, &world);
+    let (first_row, last_row) = index_layout.local_range();
+
+    // Five point Laplacian on the grid. Each process assembles its own rows, so the
+    // off-diagonal block couples the first and last grid lines of neighbouring processes.
+
+    let mut rows = Vec::<usize>::new();
+    let mut cols = Vec::<usize>::new();
+    let mut data = Vec::<f64>::new();
+
+    for row in first_row..last_row {
+        let (i, j) = (row / GRID_SIZE, row % GRID_SIZE);
+        rows.push(row);
+        cols.push(row);
+        data.push(4.0);
+        let mut neighbours = Vec::with_capacity(4);
+        if i > 0 {
+            neighbours.push(row - GRID_SIZE);
+        }
+        if i + 1 < GRID_SIZE {
+            neighbours.push(row + GRID_SIZE);
+        }
+        if j > 0 {
+            neighbours.push(row - 1);
+        }
+        if j + 1 < GRID_SIZE {
+            neighbours.push(row + 1);
+        }
+        for col in neighbours {
+            rows.push(row);
+            cols.push(col);
+            data.push(-1.0);
+        }
+    }
+
+    let mat = MpiCsrMatrix::from_aij(
+        [ndofs, ndofs],
+        &rows,
+        &cols,
+        &data,
+        &index_layout,
+        &index_layout,
+        &world,
+    )
+    .unwrap();
+
+    let x = DistributedVector::<f64, _>::new(&index_layout);
+    let mut y = DistributedVector::<f64, _>::new(&index_layout);
+
+    for (index, global_index) in (first_row..last_row).enumerate() {
+        x.local_mut()[[index]] = global_index as f64;
+    }
+
+    let overlapped = time_products(&world, || {
+        mat.matmul(1.0, black_box(&x), 0.0, black_box(&mut y))
+    });
+    let blocking = time_products(&world, || {
+        mat.matmul_blocking(1.0, black_box(&x), 0.0, black_box(&mut y))
+    });
+
+    if rank == 0 {
+        println!(
+            "mpi_csr_spmv: {} dofs on {} processes, {} off-diagonal entries on rank 0",
+            ndofs,
+            size,
+            mat.off_diagonal_block().nelems()
+        );
+        for (name, (mean_time, max_time)) in [("overlapped", overlapped), ("blocking", blocking)] {
+            println!(
+                "{} time per product: mean {:.3e} s, max {:.3e} s",
+                name, mean_time, max_time
+            );
+        }
+    }
+}
+
+/// Return the mean and the maximum over all processes of the time per call of `product`.
+fn time_products<C: CommunicatorCollectives>(world: &C, mut product: impl FnMut()) -> (f64, f64) {
+    // Warm up before timing.
+    product();
+
+    world.barrier();
+    let start = Instant::now();
+    for _ in 0..NSAMPLES {
+        product();
+    }
+    let local_time = start.elapsed().as_secs_f64() / NSAMPLES as f64;
+
+    let mut max_time = 0.0;
+    let mut total_time = 0.0;
+    world.all_reduce_into(&local_time, &mut max_time, SystemOperation::max());
+    world.all_reduce_into(&local_time, &mut total_time, SystemOperation::sum());
+
+    (total_time / world.size() as f64, max_time)
+}
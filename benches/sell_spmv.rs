@@ -0,0 +1,57 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::prelude::*;
+use rlst::prelude::*;
+
+/// Number of rows of the benchmark matrices.
+const DIM: usize = 100_000;
+
+/// Sparse matrix with a random number of entries between 1 and 30 in each row.
+fn random_csr<T: RlstScalar>(rng: &mut StdRng) -> CsrMatrix<T>
+where
+    rand::distributions::Standard: Distribution<T>,
+{
+    let mut rows = Vec::new();
+    let mut cols = Vec::new();
+    let mut data = Vec::new();
+    for row in 0..DIM {
+        for _ in 0..rng.gen_range(1..=30) {
+            rows.push(row);
+            cols.push(rng.gen_range(0..DIM));
+            data.push(rng.gen::<T>());
+        }
+    }
+    CsrMatrix::from_aij([DIM, DIM], &rows, &cols, &data).unwrap()
+}
+
+macro_rules! impl_spmv_bench {
+    ($scalar:ty) => {
+        paste::paste! {
+
+            pub fn [<spmv_ $scalar>](c: &mut Criterion) {
+                let mut rng = StdRng::seed_from_u64(0);
+
+                let csr = random_csr::<$scalar>(&mut rng);
+                let sell = SellMatrix::from_csr(&csr, 256);
+
+                let x = (0..DIM).map(|_| rng.gen::<$scalar>()).collect::<Vec<_>>();
+                let mut y: Vec<$scalar> = vec![0.0; DIM];
+
+                c.bench_function(&format!("csr_spmv_{}", stringify!($scalar)), |b| {
+                    b.iter(|| csr.matmul(1.0, black_box(&x), 0.0, black_box(&mut y)))
+                });
+
+                c.bench_function(&format!("sell_spmv_{}", stringify!($scalar)), |b| {
+                    b.iter(|| sell.matmul(1.0, black_box(&x), 0.0, black_box(&mut y)))
+                });
+            }
+        }
+    };
+}
+
+impl_spmv_bench!(f32);
+impl_spmv_bench!(f64);
+
+criterion_group!(benches, spmv_f32, spmv_f64);
+criterion_main!(benches);
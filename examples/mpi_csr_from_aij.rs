@@ -0,0 +1,90 @@
+//! Assemble a distributed CSR matrix from triplets contributed by all ranks.
+//!
+//! Run with `mpirun -n 4 cargo run --example mpi_csr_from_aij --features mpi`.
+
+use approx::assert_relative_eq;
+
+use mpi::traits::*;
+
+use rlst::prelude::*;
+
+const ROOT: usize = 0;
+const NDIM: usize = 40;
+
+pub fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let rank = world.rank() as usize;
+    let size = world.size() as usize;
+
+    let index_layout = DefaultMpiIndexLayout::new(NDIM, 1, &world);
+
+    // Assemble the 1d finite element Laplacian. The elements are distributed round robin,
+    // so most of the contributions of a rank belong to rows owned by other ranks.
+
+    let mut rows = Vec::<usize>::new();
+    let mut cols = Vec::<usize>::new();
+    let mut data = Vec::<f64>::new();
+
+    for elem in (rank..NDIM - 1).step_by(size) {
+        for (i, j, value) in [(0, 0, 1.0), (0, 1, -1.0), (1, 0, -1.0), (1, 1, 1.0)] {
+            rows.push(elem + i);
+            cols.push(elem + j);
+            data.push(value);
+        }
+    }
+
+    let mat = MpiCsrMatrix::from_aij(
+        [NDIM, NDIM],
+        &rows,
+        &cols,
+        &data,
+        &index_layout,
+        &index_layout,
+        &world,
+    )
+    .unwrap();
+
+    // Every local row has at most three entries after summing up the duplicates.
+
+    let local_mat = mat.to_local_csr();
+    let (first_row, last_row) = index_layout.local_range();
+    for row in first_row..last_row {
+        let local_row = row - first_row;
+        let nnz = local_mat.indptr()[local_row + 1] - local_mat.indptr()[local_row];
+        let expected = if row == 0 || row == NDIM - 1 { 2 } else { 3 };
+        assert_eq!(nnz, expected);
+    }
+
+    // The local rows are split into the owned columns and the ghost columns.
+
+    assert_eq!(
+        mat.diagonal_block().nelems() + mat.off_diagonal_block().nelems(),
+        local_mat.nelems()
+    );
+
+    // Multiply with x_i = i. The result is zero apart from the two boundary rows.
+
+    let x = DistributedVector::<f64, _>::new(&index_layout);
+    let mut y = DistributedVector::<f64, _>::new(&index_layout);
+
+    for (index, global_index) in (first_row..last_row).enumerate() {
+        x.local_mut()[[index]] = global_index as f64;
+    }
+
+    mat.matmul(1.0, &x, 0.0, &mut y);
+
+    if rank == ROOT {
+        let mut arr = rlst_dynamic_array1!(f64, [NDIM]);
+        y.gather_to_rank_root(arr.view_mut());
+
+        assert_relative_eq!(arr[[0]], -1.0, epsilon = 1E-12);
+        assert_relative_eq!(arr[[NDIM - 1]], 1.0, epsilon = 1E-12);
+        for index in 1..NDIM - 1 {
+            assert_relative_eq!(arr[[index]], 0.0, epsilon = 1E-12);
+        }
+    } else {
+        y.gather_to_rank(ROOT);
+    }
+}
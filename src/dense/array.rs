@@ -8,6 +8,7 @@ use crate::dense::base_array::BaseArray;
 use crate::dense::data_container::SliceContainer;
 use crate::dense::data_container::SliceContainerMut;
 use crate::dense::data_container::VectorContainer;
+use crate::dense::layout::{layout_from_stride, MemoryLayout};
 use crate::dense::traits::{
     ChunkedAccess, DefaultIterator, DefaultIteratorMut, NumberOfElements, RandomAccessByRef,
     RandomAccessByValue, RandomAccessMut, RawAccess, RawAccessMut, ResizeInPlace, Shape, Stride,
@@ -17,14 +18,19 @@ use crate::dense::types::DataChunk;
 
 use super::types::RlstBase;
 
+pub mod contraction;
 pub mod empty_axis;
 pub mod iterators;
+pub mod kronecker;
 pub mod mult_into;
 pub mod operations;
 pub mod operators;
+#[cfg(feature = "rayon")]
+mod parallel;
 pub mod random;
 pub mod rank1_array;
 pub mod slice;
+pub mod sort;
 pub mod views;
 
 /// A basic dynamically allocated array.
@@ -77,6 +83,16 @@ impl<Item: RlstBase, const NDIM: usize> DynamicArray<Item, NDIM> {
         Self::new(BaseArray::new(VectorContainer::new(size), shape))
     }
 
+    /// Create a new heap allocated array from a given shape with row-major layout.
+    pub fn from_shape_row_major(shape: [usize; NDIM]) -> Self {
+        let size = shape.iter().product();
+        Self::new(BaseArray::new_with_layout(
+            VectorContainer::new(size),
+            shape,
+            MemoryLayout::RowMajor,
+        ))
+    }
+
     /// Create a new heap allocated array from a given shape.
     pub fn from_shape_with_stride(shape: [usize; NDIM], stride: [usize; NDIM]) -> Self {
         let size = shape.iter().product();
@@ -269,6 +285,21 @@ impl<
     }
 }
 
+impl<
+        Item: RlstBase,
+        ArrayImpl: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + Stride<NDIM>,
+        const NDIM: usize,
+    > Array<Item, ArrayImpl, NDIM>
+{
+    /// Return the memory layout of the array.
+    ///
+    /// Returns `None` if the array is not contiguous in either column-major or
+    /// row-major order, e.g. for subviews.
+    pub fn memory_layout(&self) -> Option<MemoryLayout> {
+        layout_from_stride(self.shape(), self.stride())
+    }
+}
+
 impl<
         Item: RlstBase,
         ArrayImpl: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + ResizeInPlace<NDIM>,
@@ -298,6 +329,17 @@ impl<'a, Item: RlstBase, const NDIM: usize> SliceArray<'a, Item, NDIM> {
         Array::new(BaseArray::new(SliceContainer::new(slice), shape))
     }
 
+    /// Create a new array from a slice with a given `shape`.
+    ///
+    /// The data in `slice` is assumed to be stored in row-major order.
+    pub fn from_shape_row_major(slice: &'a [Item], shape: [usize; NDIM]) -> Self {
+        Array::new(BaseArray::new_with_layout(
+            SliceContainer::new(slice),
+            shape,
+            MemoryLayout::RowMajor,
+        ))
+    }
+
     /// Create a new array from a slice with a given `shape` and `stride`.
     pub fn from_shape_with_stride(
         slice: &'a [Item],
@@ -320,6 +362,17 @@ impl<'a, Item: RlstBase, const NDIM: usize> SliceArrayMut<'a, Item, NDIM> {
         Array::new(BaseArray::new(SliceContainerMut::new(slice), shape))
     }
 
+    /// Create a new array from a slice with a given `shape`.
+    ///
+    /// The data in `slice` is assumed to be stored in row-major order.
+    pub fn from_shape_row_major(slice: &'a mut [Item], shape: [usize; NDIM]) -> Self {
+        Array::new(BaseArray::new_with_layout(
+            SliceContainerMut::new(slice),
+            shape,
+            MemoryLayout::RowMajor,
+        ))
+    }
+
     /// Create a new array from a slice with a given `shape` and `stride`.
     pub fn from_shape_with_stride(
         slice: &'a mut [Item],
@@ -0,0 +1,298 @@
+//! Tensor contractions of n-dimensional arrays.
+//!
+//! A contraction sums over pairs of axes of two arrays. Both operands are permuted with
+//! [permute_axes](crate::Array::permute_axes) such that the contracted axes form one
+//! dimension of a column-major matrix. The contraction is then evaluated as a single
+//! matrix product through BLAS.
+
+use std::collections::HashSet;
+
+use crate::dense::traits::MultInto;
+use crate::dense::types::{RlstError, RlstResult, RlstScalar, TransMode};
+use crate::threading::MaybeSync;
+
+use super::{
+    Array, DynamicArray, RawAccess, RawAccessMut, Shape, SliceArray, SliceArrayMut,
+    UnsafeRandomAccessByValue,
+};
+
+/// Return the axes of an array with `ndim` dimensions that are not in `axes`, in ascending order.
+fn free_axes(ndim: usize, axes: &[usize]) -> Vec<usize> {
+    (0..ndim).filter(|axis| !axes.contains(axis)).collect()
+}
+
+/// Check that `axes` are distinct and valid axes of an array with `ndim` dimensions.
+fn assert_valid_axes(ndim: usize, axes: &[usize]) {
+    for (index, &axis) in axes.iter().enumerate() {
+        assert!(
+            axis < ndim,
+            "Axis {} out of bounds. Array has {} axes.",
+            axis,
+            ndim
+        );
+        assert!(
+            !axes[..index].contains(&axis),
+            "Axis {} is contracted more than once.",
+            axis
+        );
+    }
+}
+
+/// Permute the axes of `arr` into the order `order` and return a column-major copy.
+fn permuted_copy<
+    Item: RlstScalar,
+    ArrayImpl: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + MaybeSync,
+    const NDIM: usize,
+>(
+    arr: Array<Item, ArrayImpl, NDIM>,
+    order: &[usize],
+) -> DynamicArray<Item, NDIM> {
+    let permutation: [usize; NDIM] = order.try_into().unwrap();
+    arr.permute_axes(permutation).to_contiguous()
+}
+
+/// Contract the axes `a_axes` of `a` with the axes `b_axes` of `b`.
+///
+/// The axis `a_axes[i]` is summed against the axis `b_axes[i]`. The result has the
+/// remaining axes of `a` followed by the remaining axes of `b`, each in their original
+/// order. Hence, `NC` must be equal to `NA + NB - 2 * a_axes.len()`. For two matrices
+/// `contract(a, &[1], b, &[0])` is the matrix product.
+///
+/// Both operands are copied into GEMM-compatible layouts. The result must have at least
+/// one axis. For a contraction over all axes use [inner](crate::Array::inner).
+pub fn contract<
+    Item: RlstScalar,
+    ArrayImplA: UnsafeRandomAccessByValue<NA, Item = Item> + Shape<NA> + MaybeSync,
+    ArrayImplB: UnsafeRandomAccessByValue<NB, Item = Item> + Shape<NB> + MaybeSync,
+    const NA: usize,
+    const NB: usize,
+    const NC: usize,
+>(
+    a: Array<Item, ArrayImplA, NA>,
+    a_axes: &[usize],
+    b: Array<Item, ArrayImplB, NB>,
+    b_axes: &[usize],
+) -> DynamicArray<Item, NC> {
+    assert_eq!(
+        a_axes.len(),
+        b_axes.len(),
+        "Number of contracted axes differs. {} != {}",
+        a_axes.len(),
+        b_axes.len()
+    );
+    assert_valid_axes(NA, a_axes);
+    assert_valid_axes(NB, b_axes);
+    assert_eq!(
+        NC + 2 * a_axes.len(),
+        NA + NB,
+        "Result of contraction has {} axes but {} were requested.",
+        NA + NB - 2 * a_axes.len(),
+        NC
+    );
+    assert!(NC > 0, "Contraction over all axes is not supported.");
+
+    let a_shape = a.shape();
+    let b_shape = b.shape();
+
+    for (&a_axis, &b_axis) in a_axes.iter().zip(b_axes) {
+        assert_eq!(
+            a_shape[a_axis], b_shape[b_axis],
+            "Contracted dimensions differ. {} != {}",
+            a_shape[a_axis], b_shape[b_axis]
+        );
+    }
+
+    let a_free = free_axes(NA, a_axes);
+    let b_free = free_axes(NB, b_axes);
+
+    let m: usize = a_free.iter().map(|&axis| a_shape[axis]).product();
+    let n: usize = b_free.iter().map(|&axis| b_shape[axis]).product();
+    let k: usize = a_axes.iter().map(|&axis| a_shape[axis]).product();
+
+    let mut shape = [0; NC];
+    for (dim, &axis) in shape.iter_mut().zip(a_free.iter()) {
+        *dim = a_shape[axis];
+    }
+    for (dim, &axis) in shape[a_free.len()..].iter_mut().zip(b_free.iter()) {
+        *dim = b_shape[axis];
+    }
+
+    let mut result = DynamicArray::<Item, NC>::from_shape(shape);
+    if m * n == 0 {
+        return result;
+    }
+    if k == 0 {
+        result.set_zero();
+        return result;
+    }
+
+    // The free axes of `a` become the rows and the contracted axes the columns of a
+    // column-major matrix. For `b` it is the other way round.
+    let a_order = a_free.iter().chain(a_axes).copied().collect::<Vec<_>>();
+    let b_order = b_axes
+        .iter()
+        .chain(b_free.iter())
+        .copied()
+        .collect::<Vec<_>>();
+
+    let a_mat = permuted_copy(a, &a_order);
+    let b_mat = permuted_copy(b, &b_order);
+
+    SliceArrayMut::from_shape(result.data_mut(), [m, n]).mult_into(
+        TransMode::NoTrans,
+        TransMode::NoTrans,
+        <Item as num::One>::one(),
+        SliceArray::from_shape(a_mat.data(), [m, k]),
+        SliceArray::from_shape(b_mat.data(), [k, n]),
+        <Item as num::Zero>::zero(),
+    );
+
+    result
+}
+
+/// Parse the labels of one operand of an einsum specification.
+fn parse_labels(labels: &str, ndim: usize) -> RlstResult<Vec<char>> {
+    let labels = labels.trim().chars().collect::<Vec<_>>();
+    if labels.len() != ndim {
+        return Err(RlstError::GeneralError(format!(
+            "Einsum labels '{}' do not match an array with {} axes.",
+            labels.iter().collect::<String>(),
+            ndim
+        )));
+    }
+    if let Some(label) = labels.iter().find(|&&label| !label.is_ascii_alphabetic()) {
+        return Err(RlstError::GeneralError(format!(
+            "Invalid einsum label '{}'.",
+            label
+        )));
+    }
+    if labels.iter().collect::<HashSet<_>>().len() != labels.len() {
+        return Err(RlstError::NotImplemented(
+            "einsum with repeated labels in one operand".to_string(),
+        ));
+    }
+    Ok(labels)
+}
+
+/// Evaluate a contraction of two arrays given in Einstein summation notation.
+///
+/// The specification has the form `"ijk,kl->ijl"`. Labels are ASCII letters. Labels
+/// that appear in both operands and not in the output are summed over. Every other
+/// label must appear exactly once in the output, which may list the remaining axes in
+/// any order. If the output `->...` is omitted, the remaining labels are sorted
+/// alphabetically.
+///
+/// Traces, batch axes (labels in both operands and the output) and sums over axes of a
+/// single operand are not supported and return an error.
+pub fn einsum<
+    Item: RlstScalar,
+    ArrayImplA: UnsafeRandomAccessByValue<NA, Item = Item> + Shape<NA> + MaybeSync,
+    ArrayImplB: UnsafeRandomAccessByValue<NB, Item = Item> + Shape<NB> + MaybeSync,
+    const NA: usize,
+    const NB: usize,
+    const NC: usize,
+>(
+    spec: &str,
+    a: Array<Item, ArrayImplA, NA>,
+    b: Array<Item, ArrayImplB, NB>,
+) -> RlstResult<DynamicArray<Item, NC>> {
+    let (inputs, output) = match spec.split_once("->") {
+        Some((inputs, output)) => (inputs, Some(output.trim())),
+        None => (spec, None),
+    };
+
+    let Some((a_labels, b_labels)) = inputs.split_once(',') else {
+        return Err(RlstError::GeneralError(format!(
+            "Einsum specification '{}' must have two operands.",
+            spec
+        )));
+    };
+    let a_labels = parse_labels(a_labels, NA)?;
+    let b_labels = parse_labels(b_labels, NB)?;
+
+    let mut a_axes = Vec::new();
+    let mut b_axes = Vec::new();
+    let mut free_labels = Vec::new();
+
+    for (a_axis, label) in a_labels.iter().enumerate() {
+        match b_labels.iter().position(|other| other == label) {
+            Some(b_axis) => {
+                a_axes.push(a_axis);
+                b_axes.push(b_axis);
+            }
+            None => free_labels.push(*label),
+        }
+    }
+    free_labels.extend(b_labels.iter().filter(|label| !a_labels.contains(label)));
+
+    let output_labels = match output {
+        Some(output) => output.chars().collect::<Vec<_>>(),
+        None => {
+            let mut labels = free_labels.clone();
+            labels.sort();
+            labels
+        }
+    };
+
+    for label in &output_labels {
+        if a_labels.contains(label) && b_labels.contains(label) {
+            return Err(RlstError::NotImplemented(format!(
+                "einsum with batch label '{}'",
+                label
+            )));
+        }
+        if !free_labels.contains(label) {
+            return Err(RlstError::GeneralError(format!(
+                "Output label '{}' does not appear in the operands.",
+                label
+            )));
+        }
+    }
+    if output_labels.iter().collect::<HashSet<_>>().len() != output_labels.len() {
+        return Err(RlstError::GeneralError(format!(
+            "Repeated output label in '{}'.",
+            spec
+        )));
+    }
+    if let Some(label) = free_labels
+        .iter()
+        .find(|label| !output_labels.contains(label))
+    {
+        return Err(RlstError::NotImplemented(format!(
+            "einsum summing over label '{}' of a single operand",
+            label
+        )));
+    }
+    if output_labels.len() != NC {
+        return Err(RlstError::GeneralError(format!(
+            "Output of '{}' has rank {} but the result array has rank {}.",
+            spec,
+            output_labels.len(),
+            NC
+        )));
+    }
+    if NC == 0 {
+        return Err(RlstError::NotImplemented(
+            "einsum with scalar output".to_string(),
+        ));
+    }
+
+    let result = contract::<_, _, _, NA, NB, NC>(a, &a_axes, b, &b_axes);
+
+    let permutation: [usize; NC] = output_labels
+        .iter()
+        .map(|label| free_labels.iter().position(|other| other == label).unwrap())
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+
+    if permutation
+        .iter()
+        .enumerate()
+        .all(|(index, &axis)| index == axis)
+    {
+        Ok(result)
+    } else {
+        Ok(result.permute_axes(permutation).to_contiguous())
+    }
+}
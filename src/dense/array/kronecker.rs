@@ -0,0 +1,516 @@
+//! Lazy Kronecker and Khatri-Rao products of two-dimensional arrays.
+//!
+//! The products are never formed explicitly. Elements are computed on access and
+//! matrix-vector products use the identity `(B ⊗ A) vec(X) = vec(A X B^T)`, where `vec`
+//! stacks the columns of a matrix.
+
+use crate::dense::layout::convert_1d_nd_from_shape;
+use crate::dense::traits::MultInto;
+use crate::dense::types::{DataChunk, RlstScalar, TransMode};
+
+use super::{
+    empty_chunk, Array, ChunkedAccess, DefaultIterator, DefaultIteratorMut, DynamicArray,
+    RawAccess, RawAccessMut, Shape, Stride, UnsafeRandomAccessByValue, UnsafeRandomAccessMut,
+};
+
+/// Kronecker product `A ⊗ B` of two matrices.
+///
+/// For `A` of shape `[m, n]` and `B` of shape `[p, q]` the product has shape `[m p, n q]`
+/// with `(A ⊗ B)[[i p + k, j q + l]] = A[[i, j]] B[[k, l]]`.
+///
+/// Use the function [kron] instead.
+pub struct KronArray<
+    Item: RlstScalar,
+    ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+> {
+    a: Array<Item, ArrayImplA, 2>,
+    b: Array<Item, ArrayImplB, 2>,
+}
+
+/// Khatri-Rao (column-wise Kronecker) product of two matrices.
+///
+/// For `A` of shape `[m, n]` and `B` of shape `[p, n]` the product has shape `[m p, n]`
+/// and its `j`th column is `A[:, j] ⊗ B[:, j]`.
+///
+/// Use the function [khatri_rao] instead.
+pub struct KhatriRaoArray<
+    Item: RlstScalar,
+    ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+> {
+    a: Array<Item, ArrayImplA, 2>,
+    b: Array<Item, ArrayImplB, 2>,
+}
+
+impl<
+        Item: RlstScalar,
+        ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    > KronArray<Item, ArrayImplA, ArrayImplB>
+{
+    /// Create a new Kronecker product `a ⊗ b`.
+    pub fn new(a: Array<Item, ArrayImplA, 2>, b: Array<Item, ArrayImplB, 2>) -> Self {
+        Self { a, b }
+    }
+}
+
+impl<
+        Item: RlstScalar,
+        ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    > KhatriRaoArray<Item, ArrayImplA, ArrayImplB>
+{
+    /// Create a new Khatri-Rao product of `a` and `b`.
+    pub fn new(a: Array<Item, ArrayImplA, 2>, b: Array<Item, ArrayImplB, 2>) -> Self {
+        assert_eq!(
+            a.shape()[1],
+            b.shape()[1],
+            "Khatri-Rao product requires the same number of columns. {} != {}",
+            a.shape()[1],
+            b.shape()[1]
+        );
+        Self { a, b }
+    }
+}
+
+/// Return the lazy Kronecker product `a ⊗ b`.
+pub fn kron<
+    Item: RlstScalar,
+    ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+>(
+    a: Array<Item, ArrayImplA, 2>,
+    b: Array<Item, ArrayImplB, 2>,
+) -> Array<Item, KronArray<Item, ArrayImplA, ArrayImplB>, 2> {
+    Array::new(KronArray::new(a, b))
+}
+
+/// Return the lazy Khatri-Rao product of `a` and `b`.
+///
+/// Both matrices must have the same number of columns.
+pub fn khatri_rao<
+    Item: RlstScalar,
+    ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+>(
+    a: Array<Item, ArrayImplA, 2>,
+    b: Array<Item, ArrayImplB, 2>,
+) -> Array<Item, KhatriRaoArray<Item, ArrayImplA, ArrayImplB>, 2> {
+    Array::new(KhatriRaoArray::new(a, b))
+}
+
+impl<
+        Item: RlstScalar,
+        ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    > UnsafeRandomAccessByValue<2> for KronArray<Item, ArrayImplA, ArrayImplB>
+{
+    type Item = Item;
+
+    #[inline]
+    unsafe fn get_value_unchecked(&self, multi_index: [usize; 2]) -> Self::Item {
+        let [p, q] = self.b.shape();
+        self.a
+            .get_value_unchecked([multi_index[0] / p, multi_index[1] / q])
+            * self
+                .b
+                .get_value_unchecked([multi_index[0] % p, multi_index[1] % q])
+    }
+}
+
+impl<
+        Item: RlstScalar,
+        ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    > UnsafeRandomAccessByValue<2> for KhatriRaoArray<Item, ArrayImplA, ArrayImplB>
+{
+    type Item = Item;
+
+    #[inline]
+    unsafe fn get_value_unchecked(&self, multi_index: [usize; 2]) -> Self::Item {
+        let p = self.b.shape()[0];
+        self.a
+            .get_value_unchecked([multi_index[0] / p, multi_index[1]])
+            * self
+                .b
+                .get_value_unchecked([multi_index[0] % p, multi_index[1]])
+    }
+}
+
+impl<
+        Item: RlstScalar,
+        ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    > Shape<2> for KronArray<Item, ArrayImplA, ArrayImplB>
+{
+    fn shape(&self) -> [usize; 2] {
+        let [m, n] = self.a.shape();
+        let [p, q] = self.b.shape();
+        [m * p, n * q]
+    }
+}
+
+impl<
+        Item: RlstScalar,
+        ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    > Shape<2> for KhatriRaoArray<Item, ArrayImplA, ArrayImplB>
+{
+    fn shape(&self) -> [usize; 2] {
+        [self.a.shape()[0] * self.b.shape()[0], self.a.shape()[1]]
+    }
+}
+
+impl<
+        Item: RlstScalar,
+        ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        const N: usize,
+    > ChunkedAccess<N> for KronArray<Item, ArrayImplA, ArrayImplB>
+{
+    type Item = Item;
+
+    fn get_chunk(&self, chunk_index: usize) -> Option<DataChunk<Self::Item, N>> {
+        let shape = self.shape();
+        if let Some(mut chunk) = empty_chunk(chunk_index, shape.iter().product()) {
+            for count in 0..chunk.valid_entries {
+                unsafe {
+                    chunk.data[count] = self.get_value_unchecked(convert_1d_nd_from_shape(
+                        chunk.start_index + count,
+                        shape,
+                    ))
+                }
+            }
+            Some(chunk)
+        } else {
+            None
+        }
+    }
+}
+
+impl<
+        Item: RlstScalar,
+        ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        const N: usize,
+    > ChunkedAccess<N> for KhatriRaoArray<Item, ArrayImplA, ArrayImplB>
+{
+    type Item = Item;
+
+    fn get_chunk(&self, chunk_index: usize) -> Option<DataChunk<Self::Item, N>> {
+        let shape = self.shape();
+        if let Some(mut chunk) = empty_chunk(chunk_index, shape.iter().product()) {
+            for count in 0..chunk.valid_entries {
+                unsafe {
+                    chunk.data[count] = self.get_value_unchecked(convert_1d_nd_from_shape(
+                        chunk.start_index + count,
+                        shape,
+                    ))
+                }
+            }
+            Some(chunk)
+        } else {
+            None
+        }
+    }
+}
+
+/// Update `y = alpha vec(mat) + beta y`.
+fn update_from_vec<
+    Item: RlstScalar,
+    ArrayImplY: UnsafeRandomAccessByValue<1, Item = Item> + UnsafeRandomAccessMut<1, Item = Item> + Shape<1>,
+>(
+    alpha: Item,
+    mat: &DynamicArray<Item, 2>,
+    beta: Item,
+    y: &mut Array<Item, ArrayImplY, 1>,
+) {
+    for (out, &value) in y.iter_mut().zip(mat.data().iter()) {
+        *out = alpha * value + beta * *out;
+    }
+}
+
+impl<
+        Item: RlstScalar,
+        ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2> + Stride<2> + RawAccess<Item = Item>,
+        ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2> + Stride<2> + RawAccess<Item = Item>,
+    > Array<Item, KronArray<Item, ArrayImplA, ArrayImplB>, 2>
+{
+    /// Compute `y = alpha (A ⊗ B) x + beta y` without forming the Kronecker product.
+    ///
+    /// With `X` the `[q, n]` matrix whose columns are consecutive blocks of `x` this
+    /// evaluates `vec(B X A^T)`, which needs `O(pqn + pnm)` or `O(qnm + pqm)` operations
+    /// instead of `O(mnpq)`.
+    pub fn matvec<
+        ArrayImplX: UnsafeRandomAccessByValue<1, Item = Item> + Shape<1>,
+        ArrayImplY: UnsafeRandomAccessByValue<1, Item = Item>
+            + UnsafeRandomAccessMut<1, Item = Item>
+            + Shape<1>,
+    >(
+        &self,
+        alpha: Item,
+        x: Array<Item, ArrayImplX, 1>,
+        beta: Item,
+        y: &mut Array<Item, ArrayImplY, 1>,
+    ) {
+        let [m, n] = self.0.a.shape();
+        let [p, q] = self.0.b.shape();
+
+        assert_eq!(
+            x.shape()[0],
+            n * q,
+            "Wrong dimension of x. {} != {}",
+            x.shape()[0],
+            n * q
+        );
+        assert_eq!(
+            y.shape()[0],
+            m * p,
+            "Wrong dimension of y. {} != {}",
+            y.shape()[0],
+            m * p
+        );
+
+        let one = <Item as num::One>::one();
+        let zero = <Item as num::Zero>::zero();
+
+        let mut x_mat = DynamicArray::<Item, 2>::from_shape([q, n]);
+        for (out, value) in x_mat.data_mut().iter_mut().zip(x.iter()) {
+            *out = value;
+        }
+
+        let mut y_mat = DynamicArray::<Item, 2>::from_shape([p, m]);
+
+        // Evaluate the product in the order that needs fewer operations.
+        if p * n * (q + m) <= q * m * (n + p) {
+            let mut tmp = DynamicArray::<Item, 2>::from_shape([p, n]);
+            tmp.view_mut().mult_into(
+                TransMode::NoTrans,
+                TransMode::NoTrans,
+                one,
+                self.0.b.view(),
+                x_mat.view(),
+                zero,
+            );
+            y_mat.view_mut().mult_into(
+                TransMode::NoTrans,
+                TransMode::Trans,
+                one,
+                tmp.view(),
+                self.0.a.view(),
+                zero,
+            );
+        } else {
+            let mut tmp = DynamicArray::<Item, 2>::from_shape([q, m]);
+            tmp.view_mut().mult_into(
+                TransMode::NoTrans,
+                TransMode::Trans,
+                one,
+                x_mat.view(),
+                self.0.a.view(),
+                zero,
+            );
+            y_mat.view_mut().mult_into(
+                TransMode::NoTrans,
+                TransMode::NoTrans,
+                one,
+                self.0.b.view(),
+                tmp.view(),
+                zero,
+            );
+        }
+
+        update_from_vec(alpha, &y_mat, beta, y);
+    }
+}
+
+impl<
+        Item: RlstScalar,
+        ArrayImplA: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2> + Stride<2> + RawAccess<Item = Item>,
+        ArrayImplB: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+    > Array<Item, KhatriRaoArray<Item, ArrayImplA, ArrayImplB>, 2>
+{
+    /// Compute `y = alpha (A ⊙ B) x + beta y` without forming the Khatri-Rao product.
+    ///
+    /// This evaluates `vec(B diag(x) A^T)`, which needs `O(pnm)` operations.
+    pub fn matvec<
+        ArrayImplX: UnsafeRandomAccessByValue<1, Item = Item> + Shape<1>,
+        ArrayImplY: UnsafeRandomAccessByValue<1, Item = Item>
+            + UnsafeRandomAccessMut<1, Item = Item>
+            + Shape<1>,
+    >(
+        &self,
+        alpha: Item,
+        x: Array<Item, ArrayImplX, 1>,
+        beta: Item,
+        y: &mut Array<Item, ArrayImplY, 1>,
+    ) {
+        let [m, n] = self.0.a.shape();
+        let p = self.0.b.shape()[0];
+
+        assert_eq!(
+            x.shape()[0],
+            n,
+            "Wrong dimension of x. {} != {}",
+            x.shape()[0],
+            n
+        );
+        assert_eq!(
+            y.shape()[0],
+            m * p,
+            "Wrong dimension of y. {} != {}",
+            y.shape()[0],
+            m * p
+        );
+
+        let mut scaled_b = DynamicArray::<Item, 2>::from_shape([p, n]);
+        for (col, x_value) in x.iter().enumerate() {
+            for row in 0..p {
+                scaled_b[[row, col]] =
+                    unsafe { self.0.b.get_value_unchecked([row, col]) } * x_value;
+            }
+        }
+
+        let mut y_mat = DynamicArray::<Item, 2>::from_shape([p, m]);
+        y_mat.view_mut().mult_into(
+            TransMode::NoTrans,
+            TransMode::Trans,
+            <Item as num::One>::one(),
+            scaled_b.view(),
+            self.0.a.view(),
+            <Item as num::Zero>::zero(),
+        );
+
+        update_from_vec(alpha, &y_mat, beta, y);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use rand::SeedableRng;
+
+    use super::{khatri_rao, kron};
+    use crate::{assert_array_relative_eq, prelude::*};
+
+    macro_rules! impl_kronecker_tests {
+        ($scalar:ty, $eps:expr) => {
+            paste::paste! {
+                #[test]
+                fn [<test_kron_ $scalar>]() {
+                    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+                    let (m, n, p, q) = (4, 3, 2, 5);
+
+                    let mut a = rlst_dynamic_array2!($scalar, [m, n]);
+                    let mut b = rlst_dynamic_array2!($scalar, [p, q]);
+                    a.fill_from_equally_distributed(&mut rng);
+                    b.fill_from_equally_distributed(&mut rng);
+
+                    let mut expected = rlst_dynamic_array2!($scalar, [m * p, n * q]);
+                    for (i, j, k, l) in itertools::iproduct!(0..m, 0..n, 0..p, 0..q) {
+                        expected[[i * p + k, j * q + l]] = a[[i, j]] * b[[k, l]];
+                    }
+
+                    let prod = kron(a.view(), b.view());
+                    assert_eq!(prod.shape(), [m * p, n * q]);
+
+                    let mut chunked = rlst_dynamic_array2!($scalar, [m * p, n * q]);
+                    chunked.fill_from_chunked::<_, 7>(prod.view());
+                    assert_array_relative_eq!(prod, expected, $eps);
+                    assert_array_relative_eq!(chunked, expected, $eps);
+
+                    let mut x = rlst_dynamic_array1!($scalar, [n * q]);
+                    let mut y = rlst_dynamic_array1!($scalar, [m * p]);
+                    x.fill_from_equally_distributed(&mut rng);
+                    y.fill_from_equally_distributed(&mut rng);
+
+                    let alpha = <$scalar as RlstScalar>::from_real(2.0);
+                    let beta = <$scalar as RlstScalar>::from_real(0.5);
+                    let mut y_expected = rlst_dynamic_array1!($scalar, [m * p]);
+                    y_expected.fill_from(y.view());
+                    y_expected.view_mut().mult_into(
+                        TransMode::NoTrans,
+                        TransMode::NoTrans,
+                        alpha,
+                        expected.view(),
+                        x.view(),
+                        beta,
+                    );
+
+                    prod.matvec(alpha, x.view(), beta, &mut y);
+                    assert_array_relative_eq!(y, y_expected, $eps);
+
+                    // A wide first factor evaluates the product in the other order.
+                    let mut c = rlst_dynamic_array2!($scalar, [2, 6]);
+                    c.fill_from_equally_distributed(&mut rng);
+                    let prod = kron(c.view(), a.view());
+                    let mut x = rlst_dynamic_array1!($scalar, [6 * n]);
+                    x.fill_from_equally_distributed(&mut rng);
+                    let mut y = rlst_dynamic_array1!($scalar, [2 * m]);
+                    let mut y_expected = rlst_dynamic_array1!($scalar, [2 * m]);
+                    y_expected.view_mut().mult_into(
+                        TransMode::NoTrans,
+                        TransMode::NoTrans,
+                        alpha,
+                        prod.to_contiguous().view(),
+                        x.view(),
+                        <$scalar as num::Zero>::zero(),
+                    );
+                    prod.matvec(alpha, x.view(), <$scalar as num::Zero>::zero(), &mut y);
+                    assert_array_relative_eq!(y, y_expected, $eps);
+                }
+
+                #[test]
+                fn [<test_khatri_rao_ $scalar>]() {
+                    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+                    let (m, n, p) = (4, 3, 5);
+
+                    let mut a = rlst_dynamic_array2!($scalar, [m, n]);
+                    let mut b = rlst_dynamic_array2!($scalar, [p, n]);
+                    a.fill_from_equally_distributed(&mut rng);
+                    b.fill_from_equally_distributed(&mut rng);
+
+                    let mut expected = rlst_dynamic_array2!($scalar, [m * p, n]);
+                    for (i, j, k) in itertools::iproduct!(0..m, 0..n, 0..p) {
+                        expected[[i * p + k, j]] = a[[i, j]] * b[[k, j]];
+                    }
+
+                    let prod = khatri_rao(a.view(), b.view());
+                    assert_eq!(prod.shape(), [m * p, n]);
+
+                    let mut chunked = rlst_dynamic_array2!($scalar, [m * p, n]);
+                    chunked.fill_from_chunked::<_, 7>(prod.view());
+                    assert_array_relative_eq!(prod, expected, $eps);
+                    assert_array_relative_eq!(chunked, expected, $eps);
+
+                    let mut x = rlst_dynamic_array1!($scalar, [n]);
+                    let mut y = rlst_dynamic_array1!($scalar, [m * p]);
+                    x.fill_from_equally_distributed(&mut rng);
+                    y.fill_from_equally_distributed(&mut rng);
+
+                    let alpha = <$scalar as RlstScalar>::from_real(2.0);
+                    let beta = <$scalar as RlstScalar>::from_real(0.5);
+                    let mut y_expected = rlst_dynamic_array1!($scalar, [m * p]);
+                    y_expected.fill_from(y.view());
+                    y_expected.view_mut().mult_into(
+                        TransMode::NoTrans,
+                        TransMode::NoTrans,
+                        alpha,
+                        expected.view(),
+                        x.view(),
+                        beta,
+                    );
+
+                    prod.matvec(alpha, x.view(), beta, &mut y);
+                    assert_array_relative_eq!(y, y_expected, $eps);
+                }
+            }
+        };
+    }
+
+    impl_kronecker_tests!(f32, 1E-5);
+    impl_kronecker_tests!(f64, 1E-12);
+    impl_kronecker_tests!(c32, 1E-5);
+    impl_kronecker_tests!(c64, 1E-12);
+}
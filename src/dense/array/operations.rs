@@ -15,6 +15,12 @@ use super::{
 use crate::dense::traits::ResizeInPlace;
 
 use crate::dense::types::RlstScalar;
+use crate::threading::MaybeSync;
+
+#[cfg(feature = "rayon")]
+use super::parallel;
+#[cfg(feature = "rayon")]
+use crate::threading::use_parallel;
 
 impl<Item: RlstBase, ArrayImpl: UnsafeRandomAccessByValue<1, Item = Item> + Shape<1>>
     Array<Item, ArrayImpl, 1>
@@ -86,12 +92,22 @@ impl<
     }
 
     /// Fill an array with values from another array.
-    pub fn fill_from<ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM>>(
+    pub fn fill_from<
+        ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + MaybeSync,
+    >(
         &mut self,
         other: Array<Item, ArrayImplOther, NDIM>,
     ) {
         assert_eq!(self.shape(), other.shape());
 
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            parallel::update(self.iter_mut(), &other, |item, other_item| {
+                *item = other_item
+            });
+            return;
+        }
+
         for (item, other_item) in self.iter_mut().zip(other.iter()) {
             *item = other_item;
         }
@@ -99,7 +115,7 @@ impl<
 
     /// Fill an array from another array and resize if necessary.
     pub fn fill_from_resize<
-        ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM>,
+        ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + MaybeSync,
     >(
         &mut self,
         other: Array<Item, ArrayImplOther, NDIM>,
@@ -115,7 +131,10 @@ impl<
 
     /// Fill an array with values from an other arrays using chunks of size `N`.
     pub fn fill_from_chunked<
-        Other: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + ChunkedAccess<N, Item = Item>,
+        Other: UnsafeRandomAccessByValue<NDIM, Item = Item>
+            + Shape<NDIM>
+            + ChunkedAccess<N, Item = Item>
+            + MaybeSync,
         const N: usize,
     >(
         &mut self,
@@ -123,6 +142,14 @@ impl<
     ) {
         assert_eq!(self.shape(), other.shape());
 
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            parallel::update_chunked(self.iter_mut(), &other, |item, other_item| {
+                *item = other_item
+            });
+            return;
+        }
+
         let mut chunk_index = 0;
 
         while let Some(chunk) = other.get_chunk(chunk_index) {
@@ -142,7 +169,10 @@ impl<
 
     /// Fill an array with values from an other arrays using chunks of size `N`. Resize if necessary.
     pub fn fill_from_chunked_resize<
-        Other: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + ChunkedAccess<N, Item = Item>,
+        Other: UnsafeRandomAccessByValue<NDIM, Item = Item>
+            + Shape<NDIM>
+            + ChunkedAccess<N, Item = Item>
+            + MaybeSync,
         const N: usize,
     >(
         &mut self,
@@ -197,10 +227,21 @@ impl<
     }
 
     /// Sum other array into array.
-    pub fn sum_into<ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM>>(
+    pub fn sum_into<
+        ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + MaybeSync,
+    >(
         &mut self,
         other: Array<Item, ArrayImplOther, NDIM>,
     ) {
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            assert_eq!(self.shape(), other.shape());
+            parallel::update(self.iter_mut(), &other, |item, other_item| {
+                *item += other_item
+            });
+            return;
+        }
+
         for (item, other_item) in self.iter_mut().zip(other.iter()) {
             *item += other_item;
         }
@@ -208,11 +249,20 @@ impl<
 
     /// Componentwise multiply other array into array.
     pub fn cmp_mult_into<
-        ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM>,
+        ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + MaybeSync,
     >(
         &mut self,
         other: Array<Item, ArrayImplOther, NDIM>,
     ) {
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            assert_eq!(self.shape(), other.shape());
+            parallel::update(self.iter_mut(), &other, |item, other_item| {
+                *item *= other_item
+            });
+            return;
+        }
+
         for (item, other_item) in self.iter_mut().zip(other.iter()) {
             *item *= other_item;
         }
@@ -220,7 +270,10 @@ impl<
 
     /// Chunked summation into array.
     pub fn sum_into_chunked<
-        ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + ChunkedAccess<N, Item = Item>,
+        ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item>
+            + Shape<NDIM>
+            + ChunkedAccess<N, Item = Item>
+            + MaybeSync,
         const N: usize,
     >(
         &mut self,
@@ -230,6 +283,14 @@ impl<
     {
         assert_eq!(self.shape(), other.shape());
 
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            parallel::update_chunked(self.iter_mut(), &other, |item, other_item| {
+                *item += other_item
+            });
+            return;
+        }
+
         let mut chunk_index = 0;
 
         while let (Some(mut my_chunk), Some(chunk)) =
@@ -256,7 +317,10 @@ impl<
 
     /// Chunked componentwise multiplication into array.
     pub fn cmp_mult_into_chunked<
-        ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + ChunkedAccess<N, Item = Item>,
+        ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item>
+            + Shape<NDIM>
+            + ChunkedAccess<N, Item = Item>
+            + MaybeSync,
         const N: usize,
     >(
         &mut self,
@@ -266,6 +330,14 @@ impl<
     {
         assert_eq!(self.shape(), other.shape());
 
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            parallel::update_chunked(self.iter_mut(), &other, |item, other_item| {
+                *item *= other_item
+            });
+            return;
+        }
+
         let mut chunk_index = 0;
 
         while let (Some(mut my_chunk), Some(chunk)) =
@@ -307,7 +379,19 @@ impl<
     }
 
     /// Return the sum of the elements.
-    pub fn sum(self) -> Item {
+    pub fn sum(self) -> Item
+    where
+        ArrayImpl: MaybeSync,
+    {
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            return self.par_map_reduce(
+                <Item as Zero>::zero(),
+                |elem| elem,
+                |acc, elem| acc + elem,
+            );
+        }
+
         self.iter().sum()
     }
 }
@@ -320,16 +404,30 @@ where
     /// Compute the inner product between two vectors.
     ///
     /// The inner product takes the complex conjugate of the `other` argument.
-    pub fn inner<ArrayImplOther: UnsafeRandomAccessByValue<1, Item = Item> + Shape<1>>(
+    pub fn inner<ArrayImplOther: UnsafeRandomAccessByValue<1, Item = Item> + Shape<1> + MaybeSync>(
         &self,
         other: Array<Item, ArrayImplOther, 1>,
-    ) -> Item {
+    ) -> Item
+    where
+        ArrayImpl: MaybeSync,
+    {
         assert_eq!(
             self.number_of_elements(),
             other.number_of_elements(),
             "Arrays must have the same length"
         );
 
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            return self.par_map_reduce_indices(
+                <Item as Zero>::zero(),
+                |index| unsafe {
+                    self.get_value_unchecked(index) * other.get_value_unchecked(index).conj()
+                },
+                |acc, elem| acc + elem,
+            );
+        }
+
         self.iter()
             .zip(other.iter())
             .fold(<Item as Zero>::zero(), |acc, (elem1, elem_other)| {
@@ -338,7 +436,19 @@ where
     }
 
     /// Compute the maximum (or inf) norm of a vector.
-    pub fn norm_inf(self) -> <Item as RlstScalar>::Real {
+    pub fn norm_inf(self) -> <Item as RlstScalar>::Real
+    where
+        ArrayImpl: MaybeSync,
+    {
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            return self.par_map_reduce(
+                <<Item as RlstScalar>::Real as Zero>::zero(),
+                |elem| elem.abs(),
+                <<Item as RlstScalar>::Real as num::Float>::max,
+            );
+        }
+
         self.iter()
             .map(|elem| <Item as RlstScalar>::abs(elem))
             .reduce(<<Item as RlstScalar>::Real as num::Float>::max)
@@ -346,7 +456,19 @@ where
     }
 
     /// Compute the 1-norm of a vector.
-    pub fn norm_1(self) -> <Item as RlstScalar>::Real {
+    pub fn norm_1(self) -> <Item as RlstScalar>::Real
+    where
+        ArrayImpl: MaybeSync,
+    {
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            return self.par_map_reduce(
+                <<Item as RlstScalar>::Real as Zero>::zero(),
+                |elem| elem.abs(),
+                |acc, elem| acc + elem,
+            );
+        }
+
         self.iter()
             .map(|elem| <Item as RlstScalar>::abs(elem))
             .fold(<<Item as RlstScalar>::Real as Zero>::zero(), |acc, elem| {
@@ -355,7 +477,19 @@ where
     }
 
     /// Compute the 2-norm of a vector.
-    pub fn norm_2(self) -> <Item as RlstScalar>::Real {
+    pub fn norm_2(self) -> <Item as RlstScalar>::Real
+    where
+        ArrayImpl: MaybeSync,
+    {
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            return RlstScalar::sqrt(self.par_map_reduce(
+                <<Item as RlstScalar>::Real as Zero>::zero(),
+                |elem| elem.square(),
+                |acc, elem| acc + elem,
+            ));
+        }
+
         RlstScalar::sqrt(
             self.iter()
                 .map(|elem| <Item as RlstScalar>::abs(elem))
@@ -424,7 +558,19 @@ impl<Item: RlstScalar, ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item> + Sh
     Array<Item, ArrayImpl, 2>
 {
     /// Compute the Frobenius-norm of a matrix.
-    pub fn norm_fro(self) -> Item::Real {
+    pub fn norm_fro(self) -> Item::Real
+    where
+        ArrayImpl: MaybeSync,
+    {
+        #[cfg(feature = "rayon")]
+        if use_parallel(self.number_of_elements()) {
+            return RlstScalar::sqrt(self.par_map_reduce(
+                <Item::Real as Zero>::zero(),
+                |elem| elem.square(),
+                |acc, elem| acc + elem,
+            ));
+        }
+
         RlstScalar::sqrt(
             self.iter()
                 .map(|elem| <Item as RlstScalar>::abs(elem))
@@ -436,7 +582,10 @@ impl<Item: RlstScalar, ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item> + Sh
     }
 
     /// Compute the inf-norm of a matrix.
-    pub fn norm_inf(self) -> Item::Real {
+    pub fn norm_inf(self) -> Item::Real
+    where
+        ArrayImpl: MaybeSync,
+    {
         self.row_iter()
             .map(|row| row.norm_1())
             .reduce(<<Item as RlstScalar>::Real as num::Float>::max)
@@ -444,7 +593,10 @@ impl<Item: RlstScalar, ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item> + Sh
     }
 
     /// Compute the 1-norm of a matrix.
-    pub fn norm_1(self) -> Item::Real {
+    pub fn norm_1(self) -> Item::Real
+    where
+        ArrayImpl: MaybeSync,
+    {
         self.col_iter()
             .map(|row| row.norm_1())
             .reduce(<<Item as RlstScalar>::Real as num::Float>::max)
@@ -0,0 +1,117 @@
+//! Parallel kernels of array operations.
+//!
+//! With feature `rayon`, `fill_from`, `sum_into`, `cmp_mult_into`, their `_chunked` variants
+//! and the reductions `sum`, `inner`, `norm_1`, `norm_2`, `norm_inf` and `norm_fro` distribute
+//! chunks of elements across the RLST thread pool for arrays with at least
+//! [get_parallel_threshold](crate::threading::get_parallel_threshold) elements.
+
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use rayon::slice::ParallelSliceMut;
+
+use crate::dense::layout::convert_1d_nd_from_shape;
+use crate::dense::types::RlstBase;
+use crate::threading::install;
+
+use super::{Array, ChunkedAccess, Shape, UnsafeRandomAccessByValue};
+
+/// Number of elements per chunk for methods without a chunk size.
+pub(crate) const PAR_CHUNK_SIZE: usize = 256;
+
+/// Call `op` with the index and the elements of each chunk of `chunk_size` elements.
+fn for_each_chunk<'a, Item: Send + 'a, Op: Fn(usize, &mut [&'a mut Item]) + Sync>(
+    elements: impl Iterator<Item = &'a mut Item>,
+    chunk_size: usize,
+    op: Op,
+) {
+    let mut elements = elements.collect::<Vec<_>>();
+    install(|| {
+        elements
+            .par_chunks_mut(chunk_size)
+            .enumerate()
+            .for_each(|(chunk_index, chunk)| op(chunk_index, chunk))
+    });
+}
+
+/// Apply `op` to each element and the corresponding element of `other`.
+pub(crate) fn update<
+    'a,
+    Item: RlstBase,
+    ArrayImplOther: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + Sync,
+    Op: Fn(&mut Item, Item) + Sync,
+    const NDIM: usize,
+>(
+    elements: impl Iterator<Item = &'a mut Item>,
+    other: &Array<Item, ArrayImplOther, NDIM>,
+    op: Op,
+) {
+    let shape = other.shape();
+    for_each_chunk(elements, PAR_CHUNK_SIZE, |chunk_index, chunk| {
+        for (index, item) in chunk.iter_mut().enumerate() {
+            let multi_index = convert_1d_nd_from_shape(chunk_index * PAR_CHUNK_SIZE + index, shape);
+            op(item, unsafe { other.get_value_unchecked(multi_index) });
+        }
+    });
+}
+
+/// Apply `op` to each element and the corresponding element of the chunks of `other`.
+pub(crate) fn update_chunked<
+    'a,
+    Item: RlstBase,
+    Other: ChunkedAccess<N, Item = Item> + Sync,
+    Op: Fn(&mut Item, Item) + Sync,
+    const N: usize,
+>(
+    elements: impl Iterator<Item = &'a mut Item>,
+    other: &Other,
+    op: Op,
+) {
+    for_each_chunk(elements, N, |chunk_index, chunk| {
+        let other_chunk = other.get_chunk(chunk_index).unwrap();
+        for (item, &other_item) in chunk.iter_mut().zip(other_chunk.data.iter()) {
+            op(item, other_item);
+        }
+    });
+}
+
+impl<
+        Item: RlstBase,
+        ArrayImpl: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM> + Sync,
+        const NDIM: usize,
+    > Array<Item, ArrayImpl, NDIM>
+{
+    /// Reduce the values `map(elem)` of the elements.
+    pub(crate) fn par_map_reduce<R: Clone + Send + Sync>(
+        &self,
+        identity: R,
+        map: impl Fn(Item) -> R + Sync,
+        reduce: impl Fn(R, R) -> R + Sync,
+    ) -> R {
+        self.par_map_reduce_indices(
+            identity,
+            |multi_index| map(unsafe { self.get_value_unchecked(multi_index) }),
+            reduce,
+        )
+    }
+
+    /// Reduce the values `map(multi_index)` of the multi-indices of the array.
+    pub(crate) fn par_map_reduce_indices<R: Clone + Send + Sync>(
+        &self,
+        identity: R,
+        map: impl Fn([usize; NDIM]) -> R + Sync,
+        reduce: impl Fn(R, R) -> R + Sync,
+    ) -> R {
+        let shape = self.shape();
+        let nelements = self.number_of_elements();
+        install(|| {
+            (0..nelements.div_ceil(PAR_CHUNK_SIZE))
+                .into_par_iter()
+                .map(|chunk_index| {
+                    (chunk_index * PAR_CHUNK_SIZE
+                        ..nelements.min((1 + chunk_index) * PAR_CHUNK_SIZE))
+                        .map(|index| map(convert_1d_nd_from_shape(index, shape)))
+                        .fold(identity.clone(), &reduce)
+                })
+                .reduce(|| identity.clone(), &reduce)
+        })
+    }
+}
@@ -0,0 +1,189 @@
+//! Sorting and searching of arrays.
+//!
+//! All sorting routines are stable and work in place on arrays, views and subviews.
+
+use std::cmp::Ordering;
+
+use crate::dense::types::RlstBase;
+
+use super::{
+    Array, DefaultIterator, DefaultIteratorMut, DynamicArray, RawAccessMut, Shape,
+    UnsafeRandomAccessByValue, UnsafeRandomAccessMut,
+};
+
+/// Types with a total order used for sorting.
+///
+/// Floating point types are ordered such that all `NaN` values compare equal
+/// to each other and greater than any other value. Hence, `NaN` values are
+/// sorted to the end.
+pub trait Sortable: RlstBase {
+    /// Compare two values.
+    fn sort_cmp(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_sortable_float {
+    ($scalar:ty) => {
+        impl Sortable for $scalar {
+            #[inline]
+            fn sort_cmp(&self, other: &Self) -> Ordering {
+                match (self.is_nan(), other.is_nan()) {
+                    (false, false) => self.partial_cmp(other).unwrap(),
+                    (false, true) => Ordering::Less,
+                    (true, false) => Ordering::Greater,
+                    (true, true) => Ordering::Equal,
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_sortable_int {
+    ($scalar:ty) => {
+        impl Sortable for $scalar {
+            #[inline]
+            fn sort_cmp(&self, other: &Self) -> Ordering {
+                self.cmp(other)
+            }
+        }
+    };
+}
+
+impl_sortable_float!(f32);
+impl_sortable_float!(f64);
+
+impl_sortable_int!(u8);
+impl_sortable_int!(u16);
+impl_sortable_int!(u32);
+impl_sortable_int!(u64);
+impl_sortable_int!(usize);
+impl_sortable_int!(i8);
+impl_sortable_int!(i16);
+impl_sortable_int!(i32);
+impl_sortable_int!(i64);
+impl_sortable_int!(isize);
+
+/// Side of the insertion point for [searchsorted](crate::Array::searchsorted).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchSide {
+    /// Return the first index at which the value can be inserted.
+    Left,
+    /// Return the last index at which the value can be inserted.
+    Right,
+}
+
+impl<Item: RlstBase, ArrayImpl: UnsafeRandomAccessByValue<1, Item = Item> + Shape<1>>
+    Array<Item, ArrayImpl, 1>
+{
+    /// Return the permutation that stably sorts the vector with the comparison function `compare`.
+    ///
+    /// If `perm = arr.argsort_by(compare)` then `arr[[perm[0]]], arr[[perm[1]]], ...` is sorted.
+    pub fn argsort_by<F: FnMut(&Item, &Item) -> Ordering>(&self, mut compare: F) -> Vec<usize> {
+        let values = self.iter().collect::<Vec<_>>();
+        let mut perm = (0..values.len()).collect::<Vec<_>>();
+        perm.sort_by(|&first, &second| compare(&values[first], &values[second]));
+        perm
+    }
+}
+
+impl<Item: Sortable, ArrayImpl: UnsafeRandomAccessByValue<1, Item = Item> + Shape<1>>
+    Array<Item, ArrayImpl, 1>
+{
+    /// Return the permutation that stably sorts the vector in ascending order.
+    ///
+    /// `NaN` values are placed at the end.
+    pub fn argsort(&self) -> Vec<usize> {
+        self.argsort_by(Sortable::sort_cmp)
+    }
+
+    /// Return the sorted unique values of the vector.
+    ///
+    /// All `NaN` values are collapsed into a single `NaN` at the end.
+    pub fn unique(&self) -> DynamicArray<Item, 1> {
+        let mut values = self.iter().collect::<Vec<_>>();
+        values.sort_by(Sortable::sort_cmp);
+        values.dedup_by(|first, second| first.sort_cmp(second) == Ordering::Equal);
+
+        let mut result = DynamicArray::<Item, 1>::from_shape([values.len()]);
+        if !values.is_empty() {
+            result.data_mut().copy_from_slice(&values);
+        }
+        result
+    }
+
+    /// Find the index at which `value` needs to be inserted to keep a sorted vector sorted.
+    ///
+    /// The vector must be sorted in ascending order. For [SearchSide::Left] the first
+    /// suitable index is returned, for [SearchSide::Right] the last one.
+    pub fn searchsorted(&self, value: Item, side: SearchSide) -> usize {
+        let mut low = 0;
+        let mut high = self.len();
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let elem = unsafe { self.get_value_unchecked([mid]) };
+            let go_right = match side {
+                SearchSide::Left => elem.sort_cmp(&value) == Ordering::Less,
+                SearchSide::Right => elem.sort_cmp(&value) != Ordering::Greater,
+            };
+            if go_right {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
+
+impl<
+        Item: RlstBase,
+        ArrayImpl: UnsafeRandomAccessByValue<1, Item = Item>
+            + Shape<1>
+            + UnsafeRandomAccessMut<1, Item = Item>,
+    > Array<Item, ArrayImpl, 1>
+{
+    /// Stably sort the vector in place with the comparison function `compare`.
+    pub fn sort_by<F: FnMut(&Item, &Item) -> Ordering>(&mut self, compare: F) {
+        let mut values = self.iter().collect::<Vec<_>>();
+        values.sort_by(compare);
+
+        for (item, value) in self.iter_mut().zip(values) {
+            *item = value;
+        }
+    }
+}
+
+impl<
+        Item: Sortable,
+        ArrayImpl: UnsafeRandomAccessByValue<1, Item = Item>
+            + Shape<1>
+            + UnsafeRandomAccessMut<1, Item = Item>,
+    > Array<Item, ArrayImpl, 1>
+{
+    /// Stably sort the vector in place in ascending order.
+    ///
+    /// `NaN` values are placed at the end.
+    pub fn sort(&mut self) {
+        self.sort_by(Sortable::sort_cmp)
+    }
+}
+
+impl<
+        Item: Sortable,
+        ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item>
+            + Shape<2>
+            + UnsafeRandomAccessMut<2, Item = Item>,
+    > Array<Item, ArrayImpl, 2>
+{
+    /// Sort a matrix in place along the given `axis` in ascending order.
+    ///
+    /// For `axis = 0` each column is sorted, for `axis = 1` each row is sorted.
+    pub fn sort_axis(&mut self, axis: usize) {
+        assert!(axis < 2, "Axis {} out of bounds. Array has 2 axes.", axis);
+        let other_axis = 1 - axis;
+
+        for index in 0..self.shape()[other_axis] {
+            self.view_mut().slice::<1>(other_axis, index).sort();
+        }
+    }
+}
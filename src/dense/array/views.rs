@@ -4,10 +4,12 @@
 //! original array. A subview is similar but restricts to a subpart of the original array.
 
 pub mod flattened;
+pub mod reshape;
 pub mod subview;
 pub mod view;
 
 pub use flattened::{ArrayFlatView, ArrayFlatViewMut};
+pub use reshape::ArrayReshape;
 pub use subview::ArraySubView;
 pub use view::{ArrayView, ArrayViewMut};
 
@@ -0,0 +1,185 @@
+//! Reshaped view onto an array
+
+use crate::dense::array::{empty_chunk, Array, DynamicArray, SliceArray};
+use crate::dense::layout::{convert_1d_nd_from_shape, reshape_stride};
+use crate::dense::traits::{
+    ChunkedAccess, DefaultIterator, RawAccess, RawAccessMut, Shape, Stride,
+    UnsafeRandomAccessByRef, UnsafeRandomAccessByValue,
+};
+use crate::dense::types::{DataChunk, RlstBase};
+use crate::threading::MaybeSync;
+
+/// A reshaped array.
+///
+/// If the stride of the original array is compatible with the new shape
+/// the reshaped array is a view onto the original data. Otherwise, the data
+/// is copied into a new column-major array.
+///
+/// Use the function [arr.reshape()](crate::Array::reshape) instead.
+pub enum ArrayReshape<'a, Item: RlstBase, const NDIM: usize> {
+    /// A view onto the data of the original array.
+    View(SliceArray<'a, Item, NDIM>),
+    /// A column-major copy of the original array.
+    Copy(DynamicArray<Item, NDIM>),
+}
+
+impl<'a, Item: RlstBase, const NDIM: usize> ArrayReshape<'a, Item, NDIM> {
+    /// Return true if the reshaped array is a view onto the original data.
+    pub fn is_view(&self) -> bool {
+        matches!(self, ArrayReshape::View(_))
+    }
+}
+
+impl<'a, Item: RlstBase, const NDIM: usize> Array<Item, ArrayReshape<'a, Item, NDIM>, NDIM> {
+    /// Return true if the reshaped array is a view onto the original data.
+    pub fn is_view(&self) -> bool {
+        self.0.is_view()
+    }
+}
+
+impl<'a, Item: RlstBase, const NDIM: usize> Shape<NDIM> for ArrayReshape<'a, Item, NDIM> {
+    fn shape(&self) -> [usize; NDIM] {
+        match self {
+            ArrayReshape::View(arr) => arr.shape(),
+            ArrayReshape::Copy(arr) => arr.shape(),
+        }
+    }
+}
+
+impl<'a, Item: RlstBase, const NDIM: usize> Stride<NDIM> for ArrayReshape<'a, Item, NDIM> {
+    fn stride(&self) -> [usize; NDIM] {
+        match self {
+            ArrayReshape::View(arr) => arr.stride(),
+            ArrayReshape::Copy(arr) => arr.stride(),
+        }
+    }
+}
+
+impl<'a, Item: RlstBase, const NDIM: usize> UnsafeRandomAccessByValue<NDIM>
+    for ArrayReshape<'a, Item, NDIM>
+{
+    type Item = Item;
+
+    #[inline]
+    unsafe fn get_value_unchecked(&self, multi_index: [usize; NDIM]) -> Self::Item {
+        match self {
+            ArrayReshape::View(arr) => arr.get_value_unchecked(multi_index),
+            ArrayReshape::Copy(arr) => arr.get_value_unchecked(multi_index),
+        }
+    }
+}
+
+impl<'a, Item: RlstBase, const NDIM: usize> UnsafeRandomAccessByRef<NDIM>
+    for ArrayReshape<'a, Item, NDIM>
+{
+    type Item = Item;
+
+    #[inline]
+    unsafe fn get_unchecked(&self, multi_index: [usize; NDIM]) -> &Self::Item {
+        match self {
+            ArrayReshape::View(arr) => arr.get_unchecked(multi_index),
+            ArrayReshape::Copy(arr) => arr.get_unchecked(multi_index),
+        }
+    }
+}
+
+impl<'a, Item: RlstBase, const NDIM: usize> RawAccess for ArrayReshape<'a, Item, NDIM> {
+    type Item = Item;
+
+    fn data(&self) -> &[Self::Item] {
+        match self {
+            ArrayReshape::View(arr) => arr.data(),
+            ArrayReshape::Copy(arr) => arr.data(),
+        }
+    }
+
+    fn buff_ptr(&self) -> *const Self::Item {
+        match self {
+            ArrayReshape::View(arr) => arr.buff_ptr(),
+            ArrayReshape::Copy(arr) => arr.buff_ptr(),
+        }
+    }
+
+    fn offset(&self) -> usize {
+        match self {
+            ArrayReshape::View(arr) => arr.offset(),
+            ArrayReshape::Copy(arr) => arr.offset(),
+        }
+    }
+}
+
+impl<'a, Item: RlstBase, const NDIM: usize, const N: usize> ChunkedAccess<N>
+    for ArrayReshape<'a, Item, NDIM>
+{
+    type Item = Item;
+
+    fn get_chunk(&self, chunk_index: usize) -> Option<DataChunk<Self::Item, N>> {
+        let nelements = self.shape().iter().product();
+        if let Some(mut chunk) = empty_chunk(chunk_index, nelements) {
+            for count in 0..chunk.valid_entries {
+                unsafe {
+                    chunk.data[count] = self.get_value_unchecked(convert_1d_nd_from_shape(
+                        chunk.start_index + count,
+                        self.shape(),
+                    ))
+                }
+            }
+            Some(chunk)
+        } else {
+            None
+        }
+    }
+}
+
+impl<
+        Item: RlstBase,
+        ArrayImpl: UnsafeRandomAccessByValue<NDIM, Item = Item>
+            + Shape<NDIM>
+            + Stride<NDIM>
+            + RawAccess<Item = Item>,
+        const NDIM: usize,
+    > Array<Item, ArrayImpl, NDIM>
+{
+    /// Reshape the array into an array with `MDIM` axes and shape `new_shape`.
+    ///
+    /// The elements are taken in column-major order. If the stride of the array allows it
+    /// the result is a view onto the existing data. Otherwise, the data is copied.
+    pub fn reshape<const MDIM: usize>(
+        &self,
+        new_shape: [usize; MDIM],
+    ) -> Array<Item, ArrayReshape<'_, Item, MDIM>, MDIM> {
+        if let Some(new_stride) = reshape_stride(self.shape(), self.stride(), new_shape) {
+            Array::new(ArrayReshape::View(SliceArray::from_shape_with_stride(
+                self.data(),
+                new_shape,
+                new_stride,
+            )))
+        } else {
+            let mut copy = DynamicArray::<Item, MDIM>::from_shape(new_shape);
+            for (item, other) in copy.data_mut().iter_mut().zip(self.iter()) {
+                *item = other;
+            }
+            Array::new(ArrayReshape::Copy(copy))
+        }
+    }
+}
+
+impl<
+        Item: RlstBase,
+        ArrayImpl: UnsafeRandomAccessByValue<NDIM, Item = Item> + Shape<NDIM>,
+        const NDIM: usize,
+    > Array<Item, ArrayImpl, NDIM>
+{
+    /// Return a copy of the array with contiguous column-major storage.
+    ///
+    /// This works for arbitrary arrays, including strided views and lazy operators such as
+    /// transposes, and produces an array that can be passed to Lapack routines.
+    pub fn to_contiguous(&self) -> DynamicArray<Item, NDIM>
+    where
+        ArrayImpl: MaybeSync,
+    {
+        let mut copy = DynamicArray::<Item, NDIM>::from_shape(self.shape());
+        copy.fill_from(self.view());
+        copy
+    }
+}
@@ -6,7 +6,8 @@
 use crate::dense::array::empty_chunk;
 use crate::dense::data_container::{DataContainer, DataContainerMut, ResizeableDataContainerMut};
 use crate::dense::layout::{
-    check_multi_index_in_bounds, convert_1d_nd_from_shape, convert_nd_raw, stride_from_shape,
+    check_multi_index_in_bounds, convert_1d_nd_from_shape, convert_nd_raw, layout_from_stride,
+    stride_from_shape, stride_from_shape_with_layout, MemoryLayout,
 };
 use crate::dense::traits::{
     ChunkedAccess, RawAccess, RawAccessMut, ResizeInPlace, Shape, Stride, UnsafeRandomAccessByRef,
@@ -32,6 +33,12 @@ impl<Item: RlstBase, Data: DataContainer<Item = Item>, const NDIM: usize>
         Self::new_with_stride(data, shape, stride)
     }
 
+    /// Create new with a given memory layout
+    pub fn new_with_layout(data: Data, shape: [usize; NDIM], layout: MemoryLayout) -> Self {
+        let stride = stride_from_shape_with_layout(shape, layout);
+        Self::new_with_stride(data, shape, stride)
+    }
+
     /// Create new with stride
     pub fn new_with_stride(data: Data, shape: [usize; NDIM], stride: [usize; NDIM]) -> Self {
         if *shape.iter().min().unwrap() == 0 {
@@ -179,9 +186,14 @@ impl<Item: RlstBase, Data: ResizeableDataContainerMut<Item = Item>, const NDIM:
     ResizeInPlace<NDIM> for BaseArray<Item, Data, NDIM>
 {
     fn resize_in_place(&mut self, shape: [usize; NDIM]) {
+        // Row-major arrays keep their layout. Everything else becomes column-major.
+        let layout = match layout_from_stride(self.shape, self.stride) {
+            Some(MemoryLayout::RowMajor) => MemoryLayout::RowMajor,
+            _ => MemoryLayout::ColumnMajor,
+        };
         let new_len = shape.iter().product();
         self.data.resize(new_len);
-        self.stride = stride_from_shape(shape);
+        self.stride = stride_from_shape_with_layout(shape, layout);
         self.shape = shape;
     }
 }
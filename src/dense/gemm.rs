@@ -1,5 +1,9 @@
 //! Gemm trait for matrix multiplication
-use crate::dense::types::{c32, c64, TransMode};
+//!
+//! Input and output matrices may be stored in column-major or row-major order.
+//! Row-major matrices are passed to Blas as transposed column-major matrices.
+use crate::dense::layout::{leading_dimension, MemoryLayout};
+use crate::dense::types::{c32, c64, RlstScalar, TransMode};
 use blas::{cgemm, dgemm, sgemm, zgemm};
 
 /// Gemm
@@ -46,8 +50,47 @@ fn assert_data_size(nelems: usize, stride: [usize; 2], shape: [usize; 2]) {
     );
 }
 
+/// Return the transpose mode that applies the transpose of `trans`.
+fn transpose_mode(trans: TransMode) -> TransMode {
+    match trans {
+        TransMode::NoTrans => TransMode::Trans,
+        TransMode::ConjNoTrans => TransMode::ConjTrans,
+        TransMode::Trans => TransMode::NoTrans,
+        TransMode::ConjTrans => TransMode::ConjNoTrans,
+    }
+}
+
+/// Return the Blas transpose flag and the leading dimension for an input matrix with
+/// the given `shape` and `stride`.
+///
+/// A row-major matrix is the column-major storage of its transpose, so the transpose flag
+/// is adjusted accordingly. The returned boolean is true if the data needs to be conjugated
+/// before it is passed to Blas.
+fn blas_operand(trans: TransMode, shape: [usize; 2], stride: [usize; 2]) -> (u8, usize, bool) {
+    if let Some(ld) = leading_dimension(shape, stride, MemoryLayout::ColumnMajor) {
+        match trans {
+            TransMode::NoTrans => (b'N', ld, false),
+            TransMode::ConjNoTrans => (b'N', ld, true),
+            TransMode::Trans => (b'T', ld, false),
+            TransMode::ConjTrans => (b'C', ld, false),
+        }
+    } else if let Some(ld) = leading_dimension(shape, stride, MemoryLayout::RowMajor) {
+        match trans {
+            TransMode::NoTrans => (b'T', ld, false),
+            TransMode::ConjNoTrans => (b'C', ld, false),
+            TransMode::Trans => (b'N', ld, false),
+            TransMode::ConjTrans => (b'N', ld, true),
+        }
+    } else {
+        panic!(
+            "Input matrix for gemm must have unit stride along one axis. Actual stride [{}, {}]",
+            stride[0], stride[1]
+        );
+    }
+}
+
 macro_rules! impl_gemm {
-    ($scalar:ty, $gemm:ident) => {
+    ($scalar:ty, $gemm:ident, $complex:expr) => {
         impl Gemm for $scalar {
             fn gemm(
                 transa: TransMode,
@@ -83,48 +126,75 @@ macro_rules! impl_gemm {
 
                 assert_data_size(c.len(), [rsc, csc], [m, n]);
 
-                assert_eq!(
-                    rsa, 1,
-                    "Input matrix for dgemm must have rsa=1. Actual {}",
-                    rsa
-                );
-                assert_eq!(
-                    rsa, 1,
-                    "Input matrix for dgemm must have rsb=1. Actual {}",
-                    rsb
-                );
-                assert_eq!(
-                    rsa, 1,
-                    "Input matrix for dgemm must have rsc=1. Actual {}",
-                    rsc
-                );
-
-                let lda = csa as i32;
-                let ldb = csb as i32;
-                let ldc = csc as i32;
-
-                let transa = match transa {
-                    TransMode::NoTrans => b'N',
-                    TransMode::ConjNoTrans => {
-                        panic!("TransMode::ConjNoTrans not supported for gemm implementation.")
-                    }
-                    TransMode::Trans => b'T',
-                    TransMode::ConjTrans => b'C',
+                let Some(ldc) = leading_dimension([m, n], [rsc, csc], MemoryLayout::ColumnMajor)
+                else {
+                    // A row-major output is computed as its transpose C^T = op(B)^T op(A)^T,
+                    // which is stored in column-major order.
+                    assert!(
+                        leading_dimension([m, n], [rsc, csc], MemoryLayout::RowMajor).is_some(),
+                        "Output matrix for gemm must have unit stride along one axis. Actual stride [{}, {}]",
+                        rsc,
+                        csc
+                    );
+                    return Self::gemm(
+                        transpose_mode(transb),
+                        transpose_mode(transa),
+                        n,
+                        m,
+                        k,
+                        alpha,
+                        b,
+                        rsb,
+                        csb,
+                        a,
+                        rsa,
+                        csa,
+                        beta,
+                        c,
+                        csc,
+                        rsc,
+                    );
+                };
+
+                let a_shape = match transa {
+                    TransMode::NoTrans | TransMode::ConjNoTrans => [m, k],
+                    TransMode::Trans | TransMode::ConjTrans => [k, m],
+                };
+
+                let b_shape = match transb {
+                    TransMode::NoTrans | TransMode::ConjNoTrans => [k, n],
+                    TransMode::Trans | TransMode::ConjTrans => [n, k],
                 };
 
-                let transb = match transb {
-                    TransMode::NoTrans => b'N',
-                    TransMode::ConjNoTrans => {
-                        panic!("TransMode::ConjNoTrans not supported for gemm implementation.")
-                    }
-                    TransMode::Trans => b'T',
-                    TransMode::ConjTrans => b'C',
+                let (transa, lda, conj_a) = blas_operand(transa, a_shape, [rsa, csa]);
+                let (transb, ldb, conj_b) = blas_operand(transb, b_shape, [rsb, csb]);
+
+                // Blas has no mode for conjugation without transposition. In that case
+                // the conjugated data is copied.
+                let a_conj: Vec<$scalar>;
+                let a = if $complex && conj_a {
+                    a_conj = a.iter().map(|elem| elem.conj()).collect();
+                    &a_conj
+                } else {
+                    a
                 };
 
+                let b_conj: Vec<$scalar>;
+                let b = if $complex && conj_b {
+                    b_conj = b.iter().map(|elem| elem.conj()).collect();
+                    &b_conj
+                } else {
+                    b
+                };
+
+                let lda = lda as i32;
+                let ldb = ldb as i32;
+                let ldc = ldc as i32;
+
                 unsafe {
                     $gemm(
-                        transa as u8,
-                        transb as u8,
+                        transa,
+                        transb,
                         m as i32,
                         n as i32,
                         k as i32,
@@ -164,7 +234,7 @@ macro_rules! impl_gemm {
     };
 }
 
-impl_gemm!(f32, sgemm);
-impl_gemm!(f64, dgemm);
-impl_gemm!(c32, cgemm);
-impl_gemm!(c64, zgemm);
+impl_gemm!(f32, sgemm, false);
+impl_gemm!(f64, dgemm, false);
+impl_gemm!(c32, cgemm, true);
+impl_gemm!(c64, zgemm, true);
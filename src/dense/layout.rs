@@ -2,8 +2,19 @@
 //!
 //! The default layout is a column-major format such that in an
 //! index `[a0, a1, a2, ...]` the left-most axis is stored consecutively in memory.
+//! Row-major arrays, in which the right-most axis is stored consecutively, can be
+//! created explicitly by specifying [MemoryLayout::RowMajor].
 //!
 
+/// Memory layout of contiguous array data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryLayout {
+    /// Column-major (Fortran) order. The left-most axis is stored consecutively.
+    ColumnMajor,
+    /// Row-major (C) order. The right-most axis is stored consecutively.
+    RowMajor,
+}
+
 /// Compute stride from a shape.
 ///
 /// Given an array shape, compute the corresponding stride assuming column-major ordering.
@@ -18,6 +29,69 @@ pub fn stride_from_shape<const NDIM: usize>(shape: [usize; NDIM]) -> [usize; NDI
     output
 }
 
+/// Compute stride from a shape assuming row-major ordering.
+pub fn stride_from_shape_row_major<const NDIM: usize>(shape: [usize; NDIM]) -> [usize; NDIM] {
+    let mut output = [0; NDIM];
+
+    let mut state = 1;
+    for (elem, s) in output.iter_mut().zip(shape.iter()).rev() {
+        *elem = state;
+        state *= s;
+    }
+    output
+}
+
+/// Compute stride from a shape for a given memory layout.
+pub fn stride_from_shape_with_layout<const NDIM: usize>(
+    shape: [usize; NDIM],
+    layout: MemoryLayout,
+) -> [usize; NDIM] {
+    match layout {
+        MemoryLayout::ColumnMajor => stride_from_shape(shape),
+        MemoryLayout::RowMajor => stride_from_shape_row_major(shape),
+    }
+}
+
+/// Return the memory layout of contiguous data with the given `shape` and `stride`.
+///
+/// Returns `None` if the data is not contiguous in either column-major or row-major
+/// order. If both layouts match, e.g. for one-dimensional arrays, column-major is returned.
+pub fn layout_from_stride<const NDIM: usize>(
+    shape: [usize; NDIM],
+    stride: [usize; NDIM],
+) -> Option<MemoryLayout> {
+    if stride == stride_from_shape(shape) {
+        Some(MemoryLayout::ColumnMajor)
+    } else if stride == stride_from_shape_row_major(shape) {
+        Some(MemoryLayout::RowMajor)
+    } else {
+        None
+    }
+}
+
+/// Return the leading dimension of a matrix if it is stored in the given `layout`.
+///
+/// A matrix is stored in column-major layout if consecutive rows have unit stride, and
+/// in row-major layout if consecutive columns have unit stride. Axes of length one are
+/// compatible with either layout. The leading dimension is the stride along the other axis,
+/// as expected by Blas and Lapack. Returns `None` if the stride does not match the layout.
+pub fn leading_dimension(
+    shape: [usize; 2],
+    stride: [usize; 2],
+    layout: MemoryLayout,
+) -> Option<usize> {
+    let (axis, other) = match layout {
+        MemoryLayout::ColumnMajor => (0, 1),
+        MemoryLayout::RowMajor => (1, 0),
+    };
+
+    if stride[axis] == 1 || shape[axis] <= 1 {
+        Some(*[stride[other], shape[axis], 1].iter().max().unwrap())
+    } else {
+        None
+    }
+}
+
 /// Return true if `multi_index` in bounds with respect to `shape`.
 pub fn check_multi_index_in_bounds<const N: usize>(
     multi_index: [usize; N],
@@ -60,3 +134,83 @@ pub fn convert_1d_nd_from_shape<const NDIM: usize>(
     }
     res
 }
+
+/// Compute the stride of a reshaped array without copying data.
+///
+/// Given the `shape` and `stride` of an array, compute the stride of an array
+/// with shape `new_shape` that addresses the same data in the same column-major
+/// element order. Returns `None` if the existing stride does not allow this, in
+/// which case the data needs to be copied.
+pub fn reshape_stride<const NDIM: usize, const MDIM: usize>(
+    shape: [usize; NDIM],
+    stride: [usize; NDIM],
+    new_shape: [usize; MDIM],
+) -> Option<[usize; MDIM]> {
+    assert_eq!(
+        shape.iter().product::<usize>(),
+        new_shape.iter().product::<usize>(),
+        "Cannot reshape array with {} elements into shape with {} elements.",
+        shape.iter().product::<usize>(),
+        new_shape.iter().product::<usize>()
+    );
+
+    if shape.iter().product::<usize>() == 0 {
+        return None;
+    }
+
+    // Axes of length one do not influence the memory layout.
+    let (old_dims, old_strides): (Vec<usize>, Vec<usize>) = shape
+        .iter()
+        .zip(stride.iter())
+        .filter(|(&dim, _)| dim != 1)
+        .unzip();
+
+    let mut new_stride = [0; MDIM];
+
+    let mut old_index = 0;
+    let mut new_index = 0;
+
+    while old_index < old_dims.len() && new_index < MDIM {
+        // Find the smallest groups of old and new axes that contain the same number of elements.
+        let mut old_end = old_index + 1;
+        let mut new_end = new_index + 1;
+        let mut old_count = old_dims[old_index];
+        let mut new_count = new_shape[new_index];
+
+        while old_count != new_count {
+            if new_count < old_count {
+                new_count *= new_shape[new_end];
+                new_end += 1;
+            } else {
+                old_count *= old_dims[old_end];
+                old_end += 1;
+            }
+        }
+
+        // The old axes in the group must be contiguous with respect to each other.
+        for axis in old_index..old_end - 1 {
+            if old_strides[axis + 1] != old_dims[axis] * old_strides[axis] {
+                return None;
+            }
+        }
+
+        new_stride[new_index] = old_strides[old_index];
+        for axis in 1 + new_index..new_end {
+            new_stride[axis] = new_stride[axis - 1] * new_shape[axis - 1];
+        }
+
+        old_index = old_end;
+        new_index = new_end;
+    }
+
+    // Any remaining new axes have length one. Continue the stride of the last axis.
+    for axis in new_index..MDIM {
+        new_stride[axis] = if axis == 0 {
+            1
+        } else {
+            new_stride[axis - 1] * new_shape[axis - 1]
+        };
+    }
+
+    Some(new_stride)
+}
@@ -1,6 +1,7 @@
 //! Linear algebra routines
 
 
+use crate::dense::layout::{leading_dimension, MemoryLayout};
 use crate::{MatrixInverse, MatrixPseudoInverse, MatrixQr, MatrixId, MatrixSvd, MatrixNull, RlstScalar};
 
 use self::lu::MatrixLu;
@@ -17,11 +18,28 @@ pub mod null_space;
 pub fn assert_lapack_stride(stride: [usize; 2]) {
     assert_eq!(
         stride[0], 1,
-        "Incorrect stride for Lapack. Stride[0] is {} but expected 1.",
+        "Incorrect stride for Lapack. Stride[0] is {} but expected 1. Use `to_contiguous` to create a column-major copy.",
         stride[0]
     );
 }
 
+/// Return the memory layout and leading dimension of a matrix passed to Lapack.
+///
+/// Row-major matrices are passed to Lapack as the column-major storage of their transpose.
+/// If a matrix is compatible with both layouts, column-major is returned.
+pub fn lapack_layout(shape: [usize; 2], stride: [usize; 2]) -> (MemoryLayout, usize) {
+    if let Some(ld) = leading_dimension(shape, stride, MemoryLayout::ColumnMajor) {
+        (MemoryLayout::ColumnMajor, ld)
+    } else if let Some(ld) = leading_dimension(shape, stride, MemoryLayout::RowMajor) {
+        (MemoryLayout::RowMajor, ld)
+    } else {
+        panic!(
+            "Incorrect stride for Lapack. Stride is [{}, {}] but expected unit stride along one axis. Use `to_contiguous` to create a column-major copy.",
+            stride[0], stride[1]
+        );
+    }
+}
+
 /// Marker trait for objects that support Matrix decompositions.
 pub trait LinAlg: MatrixInverse + MatrixQr + MatrixSvd + MatrixLu + MatrixPseudoInverse + MatrixId + MatrixNull{}
 
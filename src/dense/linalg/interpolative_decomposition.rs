@@ -4,6 +4,7 @@ use crate::dense::types::{RlstResult, RlstScalar};
 use crate::dense::traits::accessors::RandomAccessMut;
 use crate::{BaseArray, VectorContainer, rlst_dynamic_array1, empty_array, rlst_dynamic_array2};
 use crate::dense::types::{c32, c64};
+use crate::threading::MaybeSync;
 use num::One;
 
 /// Compute the matrix interpolative decomposition, by providing a rank and an interpolation matrix.
@@ -30,6 +31,7 @@ pub trait MatrixId: RlstScalar {
             + Stride<2>
             + Shape<2>
             + RawAccessMut<Item = Self>
+            + MaybeSync
     >(
        arr: Array<Self, ArrayImpl, 2>, tol: <Self as RlstScalar>::Real, k: Option<usize>
     ) -> RlstResult<IdDecomposition<Self, ArrayImpl>>;
@@ -43,6 +45,7 @@ macro_rules! implement_into_id {
                     + Stride<2>
                     + Shape<2>
                     + RawAccessMut<Item = Self>
+                    + MaybeSync
             >(
                 arr: Array<Self, ArrayImpl, 2>, tol: <Self as RlstScalar>::Real, k: Option<usize>
             ) -> RlstResult<IdDecomposition<Self, ArrayImpl>> {
@@ -62,6 +65,7 @@ impl<
         ArrayImplId: UnsafeRandomAccessByValue<2, Item = Item>
             + Stride<2>
             + RawAccessMut<Item = Item>
+            + MaybeSync
             + Shape<2>,
     > Array<Item, ArrayImplId, 2>
 {
@@ -79,6 +83,7 @@ pub trait MatrixIdDecomposition: Sized {
     type ArrayImpl: UnsafeRandomAccessByValue<2, Item = Self::Item>
         + Stride<2>
         + RawAccessMut<Item = Self::Item>
+        + MaybeSync
         + Shape<2>;
 
     /// Create a new Interpolative Decomposition from a given array.
@@ -120,7 +125,8 @@ macro_rules! impl_id {
                 ArrayImpl: UnsafeRandomAccessByValue<2, Item = $scalar>
                     + Stride<2>
                     + Shape<2>
-                    + RawAccessMut<Item = $scalar>,
+                    + RawAccessMut<Item = $scalar>
+                    + MaybeSync,
             > MatrixIdDecomposition for IdDecomposition<$scalar, ArrayImpl>
         {
             type Item = $scalar;
@@ -177,9 +183,10 @@ macro_rules! impl_id {
                     .simple_mult_into_resize(perm_mat.view_mut(), arr.view());
 
 
+                let stride = arr.stride();
                 for col in 0..arr.shape()[1]{
                     for row in 0..arr.shape()[0]{
-                        arr.data_mut()[col*arr_qr_shape[1] + row] = *perm_arr.get_mut([row, col]).unwrap()
+                        arr.data_mut()[row * stride[0] + col * stride[1]] = *perm_arr.get_mut([row, col]).unwrap()
                     }
                 }
 
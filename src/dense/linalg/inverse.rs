@@ -7,7 +7,7 @@ use crate::dense::types::{c32, c64, RlstError, RlstResult, RlstScalar};
 use lapack::{cgetrf, cgetri, dgetrf, dgetri, sgetrf, sgetri, zgetrf, zgetri};
 use num::traits::Zero;
 
-use super::assert_lapack_stride;
+use super::lapack_layout;
 
 /// Compute the matrix inverse.
 ///
@@ -17,7 +17,8 @@ use super::assert_lapack_stride;
 /// # Example
 ///
 /// The following command computes the inverse of an array `a`. The content
-/// of `a` is replaced by the inverse.
+/// of `a` is replaced by the inverse. The array may be stored in column-major
+/// or row-major order.
 /// ```
 /// # use rlst::rlst_dynamic_array2;
 /// # use rlst::dense::linalg::inverse::MatrixInverse;
@@ -55,7 +56,9 @@ macro_rules! impl_inverse {
             >(
                 mut arr: Array<Self, ArrayImpl, 2>,
             ) -> RlstResult<()> {
-                assert_lapack_stride(arr.stride());
+                // The inverse of the transpose is the transpose of the inverse. Hence,
+                // row-major arrays can be passed to Lapack unchanged.
+                let (_, lda) = lapack_layout(arr.shape(), arr.stride());
 
                 let m = arr.shape()[0] as i32;
                 let n = arr.shape()[1] as i32;
@@ -64,7 +67,7 @@ macro_rules! impl_inverse {
 
                 assert_eq!(m, n);
 
-                let lda = arr.stride()[1] as i32;
+                let lda = lda as i32;
                 let mut ipiv = vec![0; m as usize];
 
                 let mut lwork = -1;
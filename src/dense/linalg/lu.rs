@@ -1,6 +1,7 @@
 //! LU Decomposition and linear system solves.
-use super::assert_lapack_stride;
+use super::lapack_layout;
 use crate::dense::array::Array;
+use crate::dense::layout::{leading_dimension, MemoryLayout};
 use crate::dense::traits::{
     RandomAccessByValue, RawAccess, RawAccessMut, ResizeInPlace, Shape, Stride,
     UnsafeRandomAccessByRef, UnsafeRandomAccessByValue, UnsafeRandomAccessMut,
@@ -58,6 +59,10 @@ impl<
     /// by `A = PLU`, where `P` is an `(m, m)` permutation matrix,
     /// `L` is a `(m, k)` unit lower triangular matrix, and `U` is
     /// an `(k, n)` upper triangular matrix.
+    ///
+    /// Row-major arrays are factorized without copying as the transpose. In that
+    /// case the columns are pivoted instead of the rows, so that `AP = LU` with an
+    /// `(n, n)` permutation matrix `P`.
     pub fn into_lu_alloc(self) -> RlstResult<LuDecomposition<Item, ArrayImpl>> {
         <Item as MatrixLu>::into_lu_alloc(self)
     }
@@ -182,12 +187,15 @@ pub trait MatrixLuDecomposition: Sized {
     /// Get the permutation vector from the LU decomposition.
     ///
     /// If `perm[i] = j` then the ith row of `LU` corresponds to the jth row of `A`.
+    /// For row-major arrays the ith column of `LU` corresponds to the jth column of `A`.
     fn get_perm(&self) -> Vec<usize>;
 
     /// Get the P matrix of the LU Decomposition.
     ///
     /// If A has the dimension `(m, n)` then the P matrix
-    /// has the dimension (m, m).
+    /// has the dimension (m, m). For row-major arrays the
+    /// columns are pivoted, so that `AP = LU` and P has the
+    /// dimension (n, n).
     fn get_p<
         ArrayImplMut: UnsafeRandomAccessByValue<2, Item = Self::Item>
             + Shape<2>
@@ -211,6 +219,31 @@ pub struct LuDecomposition<
     ipiv: Vec<i32>,
 }
 
+impl<
+        Item: RlstScalar,
+        ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item>
+            + Stride<2>
+            + Shape<2>
+            + RawAccessMut<Item = Item>,
+    > LuDecomposition<Item, ArrayImpl>
+{
+    /// Memory layout of the factorized array.
+    ///
+    /// For row-major arrays Lapack computes the factorization `A^T = P L U` of the
+    /// transpose. The factors of `A` are then obtained from `A P = U^T L^T`.
+    fn layout(&self) -> MemoryLayout {
+        lapack_layout(self.arr.shape(), self.arr.stride()).0
+    }
+
+    /// Dimension of the permutation matrix.
+    fn perm_dim(&self) -> usize {
+        match self.layout() {
+            MemoryLayout::ColumnMajor => self.arr.shape()[0],
+            MemoryLayout::RowMajor => self.arr.shape()[1],
+        }
+    }
+}
+
 macro_rules! impl_lu {
     ($scalar:ty, $getrf:expr, $getrs:expr) => {
         impl<
@@ -225,22 +258,26 @@ macro_rules! impl_lu {
 
             fn new(mut arr: Array<$scalar, ArrayImpl, 2>) -> RlstResult<Self> {
                 let shape = arr.shape();
-                let stride = arr.stride();
-
-                assert_lapack_stride(stride);
-
                 let dim = std::cmp::min(shape[0], shape[1]);
                 if dim == 0 {
                     return Err(RlstError::MatrixIsEmpty((shape[0], shape[1])));
                 }
+
+                // A row-major array is factorized as its transpose.
+                let (layout, lda) = lapack_layout(shape, arr.stride());
+                let (m, n) = match layout {
+                    MemoryLayout::ColumnMajor => (shape[0], shape[1]),
+                    MemoryLayout::RowMajor => (shape[1], shape[0]),
+                };
+
                 let mut ipiv = vec![0; dim];
                 let mut info = 0;
                 unsafe {
                     $getrf(
-                        shape[0] as i32,
-                        shape[1] as i32,
+                        m as i32,
+                        n as i32,
                         arr.data_mut(),
-                        stride[1] as i32,
+                        lda as i32,
                         &mut ipiv,
                         &mut info,
                     );
@@ -284,22 +321,33 @@ macro_rules! impl_lu {
 
                 let nrhs = rhs.shape()[1];
 
-                let arr_stride = self.arr.stride();
-                let rhs_stride = rhs.stride();
-
-                let lda = self.arr.stride()[1];
-                let ldb = rhs.stride()[1];
-
-                assert_lapack_stride(arr_stride);
-                assert_lapack_stride(rhs_stride);
-
-                let trans_param = match trans {
-                    TransMode::NoTrans => b'N',
-                    TransMode::Trans => b'T',
-                    TransMode::ConjTrans => b'C',
-                    _ => panic!("Transposition mode not supported for LU solve."),
+                let (layout, lda) = lapack_layout(self.arr.shape(), self.arr.stride());
+                let ldb = leading_dimension(rhs.shape(), rhs.stride(), MemoryLayout::ColumnMajor)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "Right-hand side must be stored in column-major order. Actual stride [{}, {}]",
+                            rhs.stride()[0],
+                            rhs.stride()[1]
+                        )
+                    });
+
+                // For a row-major array the factorization is that of the transpose. The
+                // conjugate is handled by solving for the conjugate of the solution.
+                let (trans_param, conj) = match (layout, trans) {
+                    (MemoryLayout::ColumnMajor, TransMode::NoTrans) => (b'N', false),
+                    (MemoryLayout::ColumnMajor, TransMode::ConjNoTrans) => (b'N', true),
+                    (MemoryLayout::ColumnMajor, TransMode::Trans) => (b'T', false),
+                    (MemoryLayout::ColumnMajor, TransMode::ConjTrans) => (b'C', false),
+                    (MemoryLayout::RowMajor, TransMode::NoTrans) => (b'T', false),
+                    (MemoryLayout::RowMajor, TransMode::ConjNoTrans) => (b'C', false),
+                    (MemoryLayout::RowMajor, TransMode::Trans) => (b'N', false),
+                    (MemoryLayout::RowMajor, TransMode::ConjTrans) => (b'N', true),
                 };
 
+                if conj {
+                    rhs.data_mut().iter_mut().for_each(|elem| *elem = elem.conj());
+                }
+
                 let mut info = 0;
                 unsafe {
                     $getrs(
@@ -315,6 +363,10 @@ macro_rules! impl_lu {
                     )
                 };
 
+                if conj {
+                    rhs.data_mut().iter_mut().for_each(|elem| *elem = elem.conj());
+                }
+
                 match info {
                     0 => Ok(()),
                     _ => Err(RlstError::LapackError(info)),
@@ -361,11 +413,17 @@ macro_rules! impl_lu {
                     arr.shape()[1]
                 );
 
+                let row_major = self.layout() == MemoryLayout::RowMajor;
+
                 arr.set_zero();
                 for col in 0..k {
                     for row in col..m {
                         if col == row {
                             arr[[row, col]] = <$scalar as One>::one();
+                        } else if row_major {
+                            // Column of U^T scaled to unit diagonal.
+                            arr[[row, col]] = self.arr.get_value([row, col]).unwrap()
+                                / self.arr.get_value([col, col]).unwrap();
                         } else {
                             arr[[row, col]] = self.arr.get_value([row, col]).unwrap();
                         }
@@ -413,10 +471,18 @@ macro_rules! impl_lu {
                     arr.shape()[1]
                 );
 
+                let row_major = self.layout() == MemoryLayout::RowMajor;
+
                 arr.set_zero();
                 for col in 0..n {
                     for row in 0..=std::cmp::min(col, k - 1) {
-                        arr[[row, col]] = self.arr.get_value([row, col]).unwrap();
+                        if row_major && row != col {
+                            // Row of the unit upper triangular L^T scaled by the diagonal of U.
+                            arr[[row, col]] = self.arr.get_value([row, row]).unwrap()
+                                * self.arr.get_value([row, col]).unwrap();
+                        } else {
+                            arr[[row, col]] = self.arr.get_value([row, col]).unwrap();
+                        }
                     }
                 }
             }
@@ -431,7 +497,7 @@ macro_rules! impl_lu {
                 &self,
                 mut arr: Array<$scalar, ArrayImplMut, 2>,
             ) {
-                let m = self.arr.shape()[0];
+                let m = self.perm_dim();
 
                 arr.resize_in_place([m, m]);
                 self.get_p(arr);
@@ -440,8 +506,9 @@ macro_rules! impl_lu {
             /// Get the permutation vector from the LU decomposition.
             ///
             /// If `perm[i] = j` then the ith row of `LU` corresponds to the jth row of `A`.
+            /// For row-major arrays the ith column of `LU` corresponds to the jth column of `A`.
             fn get_perm(&self) -> Vec<usize> {
-                let m = self.arr.shape()[0];
+                let m = self.perm_dim();
                 let ipiv: Vec<usize> = self.ipiv.iter().map(|&elem| (elem as usize) - 1).collect();
 
                 let mut perm = (0..m).collect::<Vec<_>>();
@@ -462,7 +529,7 @@ macro_rules! impl_lu {
                 &self,
                 mut arr: Array<$scalar, ArrayImplMut, 2>,
             ) {
-                let m = self.arr.shape()[0];
+                let m = self.perm_dim();
                 assert_eq!(
                     arr.shape(),
                     [m, m],
@@ -6,6 +6,7 @@ use crate::dense::types::{c32, c64, RlstResult, RlstScalar};
 use crate::{BaseArray, VectorContainer, rlst_dynamic_array1, rlst_dynamic_array2};
 use crate::dense::traits::DefaultIterator;
 use crate::empty_array;
+use crate::threading::MaybeSync;
 use itertools::min;
 
 /// Compute the matrix nullspace.
@@ -38,7 +39,8 @@ pub trait MatrixNull: RlstScalar {
         ArrayImpl: UnsafeRandomAccessByValue<2, Item = Self>
             + Stride<2>
             + Shape<2>
-            + RawAccessMut<Item = Self>,
+            + RawAccessMut<Item = Self>
+            + MaybeSync,
     >(
         arr: Array<Self, ArrayImpl, 2>,
         null_space_type: NullSpaceType
@@ -52,7 +54,8 @@ macro_rules! implement_into_null {
                 ArrayImpl: UnsafeRandomAccessByValue<2, Item = Self>
                     + Stride<2>
                     + Shape<2>
-                    + RawAccessMut<Item = Self>,
+                    + RawAccessMut<Item = Self>
+                    + MaybeSync,
             >(
                 arr: Array<Self, ArrayImpl, 2>,
                 null_space_type: NullSpaceType
@@ -73,6 +76,7 @@ impl<
         ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item>
             + Stride<2>
             + RawAccessMut<Item = Item>
+            + MaybeSync
             + Shape<2>,
     > Array<Item, ArrayImpl, 2>
 {
@@ -110,7 +114,8 @@ macro_rules! implement_null_space {
             ArrayImpl: UnsafeRandomAccessByValue<2, Item = $scalar>
                 + Stride<2>
                 + Shape<2>
-                + RawAccessMut<Item = $scalar>,
+                + RawAccessMut<Item = $scalar>
+                + MaybeSync,
         >(arr: Array<$scalar, ArrayImpl, 2>, null_space_type: NullSpaceType) -> RlstResult<Self> {
 
                 let shape = arr.shape();
@@ -1,6 +1,8 @@
 //! Pseudo-Inverse of a matrix.
 
-use crate::dense::array::Array;
+use crate::dense::array::{Array, DynamicArray};
+use crate::dense::layout::MemoryLayout;
+use crate::dense::linalg::lapack_layout;
 use crate::dense::traits::{
     MultInto, RawAccessMut, ResizeInPlace, Shape, Stride, UnsafeRandomAccessByValue,
     UnsafeRandomAccessMut,
@@ -171,8 +173,17 @@ macro_rules! impl_pinv {
                 let k = std::cmp::min(arr.shape()[0], arr.shape()[1]);
                 let mode = crate::dense::linalg::svd::SvdMode::Reduced;
                 let mut singvals = vec![<<$scalar as RlstScalar>::Real as Zero>::zero(); k];
-                let mut u = rlst_dynamic_array2!($scalar, [arr.shape()[0], k]);
-                let mut vt = rlst_dynamic_array2!($scalar, [k, arr.shape()[1]]);
+                // The SVD requires `u` and `vt` to have the same layout as `arr`.
+                let (mut u, mut vt) = match lapack_layout(shape, arr.stride()).0 {
+                    MemoryLayout::ColumnMajor => (
+                        rlst_dynamic_array2!($scalar, [arr.shape()[0], k]),
+                        rlst_dynamic_array2!($scalar, [k, arr.shape()[1]]),
+                    ),
+                    MemoryLayout::RowMajor => (
+                        DynamicArray::<$scalar, 2>::from_shape_row_major([arr.shape()[0], k]),
+                        DynamicArray::<$scalar, 2>::from_shape_row_major([k, arr.shape()[1]]),
+                    ),
+                };
 
                 arr.into_svd_alloc(u.view_mut(), vt.view_mut(), &mut singvals, mode)?;
 
@@ -1,7 +1,8 @@
 //! Pivoted QR Decomposition.
 
-use super::assert_lapack_stride;
+use super::lapack_layout;
 use crate::dense::array::Array;
+use crate::dense::layout::MemoryLayout;
 use crate::dense::traits::{
     RandomAccessByValue, RandomAccessMut, RawAccess, RawAccessMut, Shape, Stride,
     UnsafeRandomAccessByValue, UnsafeRandomAccessMut,
@@ -56,6 +57,8 @@ impl<
     > Array<Item, ArrayImpl, 2>
 {
     /// Compute the QR decomposition of a given 2-dimensional array.
+    ///
+    /// Row-major arrays are factorized in a column-major copy.
     pub fn into_qr_alloc(self) -> RlstResult<QrDecomposition<Item, ArrayImpl>> {
         <Item as MatrixQr>::into_qr_alloc(self)
     }
@@ -90,6 +93,38 @@ pub struct QrDecomposition<
     jpvt: Vec<i32>,
 }
 
+/// Copy the first `ncols` columns of an array into column-major storage.
+fn column_major_copy<
+    Item: RlstScalar,
+    ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+>(
+    arr: &Array<Item, ArrayImpl, 2>,
+    ncols: usize,
+) -> Vec<Item> {
+    (0..ncols)
+        .cartesian_product(0..arr.shape()[0])
+        .map(|(col, row)| arr.get_value([row, col]).unwrap())
+        .collect()
+}
+
+/// Write column-major `data` into an array.
+fn write_column_major<
+    Item: RlstScalar,
+    ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item> + Stride<2> + Shape<2> + RawAccessMut<Item = Item>,
+>(
+    arr: &mut Array<Item, ArrayImpl, 2>,
+    data: &[Item],
+) {
+    let [m, n] = arr.shape();
+    let stride = arr.stride();
+    let arr_data = arr.data_mut();
+    for col in 0..n {
+        for row in 0..m {
+            arr_data[row * stride[0] + col * stride[1]] = data[col * m + row];
+        }
+    }
+}
+
 macro_rules! implement_qr_real {
     ($scalar:ty, $geqp3:expr, $ormqr:expr) => {
         impl<
@@ -109,11 +144,18 @@ macro_rules! implement_qr_real {
                     return Err(RlstError::MatrixIsEmpty((shape[0], shape[1])));
                 }
 
-                assert_lapack_stride(stride);
-
                 let m = shape[0] as i32;
                 let n = shape[1] as i32;
-                let lda = stride[1] as i32;
+
+                // Lapack has no pivoted LQ decomposition, so row-major arrays are factorized
+                // in a column-major copy that is written back afterwards.
+                let (layout, lda) = lapack_layout(shape, stride);
+                let mut copy =
+                    (layout == MemoryLayout::RowMajor).then(|| column_major_copy(&arr, shape[1]));
+                let (data, lda) = match copy.as_mut() {
+                    Some(copy) => (copy.as_mut_slice(), m),
+                    None => (arr.data_mut(), lda as i32),
+                };
 
                 let mut jpvt = vec![0 as i32; n as usize];
                 let mut tau = vec![<$scalar as Zero>::zero(); k];
@@ -127,7 +169,7 @@ macro_rules! implement_qr_real {
                     $geqp3(
                         m,
                         n,
-                        arr.data_mut(),
+                        data,
                         lda,
                         &mut jpvt,
                         &mut tau,
@@ -147,20 +189,17 @@ macro_rules! implement_qr_real {
 
                 unsafe {
                     $geqp3(
-                        m,
-                        n,
-                        arr.data_mut(),
-                        lda,
-                        &mut jpvt,
-                        &mut tau,
-                        &mut work,
-                        lwork,
-                        &mut info,
+                        m, n, data, lda, &mut jpvt, &mut tau, &mut work, lwork, &mut info,
                     );
                 }
 
                 match info {
-                    0 => Ok(Self { arr, tau, jpvt }),
+                    0 => {
+                        if let Some(copy) = copy {
+                            write_column_major(&mut arr, &copy);
+                        }
+                        Ok(Self { arr, tau, jpvt })
+                    }
                     _ => Err(RlstError::LapackError(info)),
                 }
             }
@@ -282,7 +321,14 @@ macro_rules! implement_qr_real {
                     ApplyQSide::Right => k <= n,
                 });
 
-                let lda = self.arr.stride()[1] as i32;
+                let (layout, lda) = lapack_layout(self.arr.shape(), self.arr.stride());
+                let reflectors;
+                let (a, lda) = if layout == MemoryLayout::RowMajor {
+                    reflectors = column_major_copy(&self.arr, k as usize);
+                    (reflectors.as_slice(), self.arr.shape()[0] as i32)
+                } else {
+                    (self.arr.data(), lda as i32)
+                };
 
                 assert!(match side {
                     ApplyQSide::Left => lda >= std::cmp::max(1, m),
@@ -306,7 +352,7 @@ macro_rules! implement_qr_real {
                         m,
                         n,
                         k,
-                        self.arr.data(),
+                        a,
                         lda,
                         self.tau.as_slice(),
                         arr.data_mut(),
@@ -333,7 +379,7 @@ macro_rules! implement_qr_real {
                         m,
                         n,
                         k,
-                        self.arr.data(),
+                        a,
                         lda,
                         self.tau.as_slice(),
                         arr.data_mut(),
@@ -372,11 +418,18 @@ macro_rules! implement_qr_complex {
                     return Err(RlstError::MatrixIsEmpty((shape[0], shape[1])));
                 }
 
-                assert_lapack_stride(stride);
-
                 let m = shape[0] as i32;
                 let n = shape[1] as i32;
-                let lda = stride[1] as i32;
+
+                // Lapack has no pivoted LQ decomposition, so row-major arrays are factorized
+                // in a column-major copy that is written back afterwards.
+                let (layout, lda) = lapack_layout(shape, stride);
+                let mut copy =
+                    (layout == MemoryLayout::RowMajor).then(|| column_major_copy(&arr, shape[1]));
+                let (data, lda) = match copy.as_mut() {
+                    Some(copy) => (copy.as_mut_slice(), m),
+                    None => (arr.data_mut(), lda as i32),
+                };
 
                 let mut jpvt = vec![0 as i32; n as usize];
                 let mut tau = vec![<$scalar as Zero>::zero(); k];
@@ -393,7 +446,7 @@ macro_rules! implement_qr_complex {
                     $geqp3(
                         m,
                         n,
-                        arr.data_mut(),
+                        data,
                         lda,
                         &mut jpvt,
                         &mut tau,
@@ -414,21 +467,18 @@ macro_rules! implement_qr_complex {
 
                 unsafe {
                     $geqp3(
-                        m,
-                        n,
-                        arr.data_mut(),
-                        lda,
-                        &mut jpvt,
-                        &mut tau,
-                        &mut work,
-                        lwork,
-                        &mut rwork,
+                        m, n, data, lda, &mut jpvt, &mut tau, &mut work, lwork, &mut rwork,
                         &mut info,
                     );
                 }
 
                 match info {
-                    0 => Ok(Self { arr, tau, jpvt }),
+                    0 => {
+                        if let Some(copy) = copy {
+                            write_column_major(&mut arr, &copy);
+                        }
+                        Ok(Self { arr, tau, jpvt })
+                    }
                     _ => Err(RlstError::LapackError(info)),
                 }
             }
@@ -550,7 +600,14 @@ macro_rules! implement_qr_complex {
                     ApplyQSide::Right => k <= n,
                 });
 
-                let lda = self.arr.stride()[1] as i32;
+                let (layout, lda) = lapack_layout(self.arr.shape(), self.arr.stride());
+                let reflectors;
+                let (a, lda) = if layout == MemoryLayout::RowMajor {
+                    reflectors = column_major_copy(&self.arr, k as usize);
+                    (reflectors.as_slice(), self.arr.shape()[0] as i32)
+                } else {
+                    (self.arr.data(), lda as i32)
+                };
 
                 assert!(match side {
                     ApplyQSide::Left => lda >= std::cmp::max(1, m),
@@ -574,7 +631,7 @@ macro_rules! implement_qr_complex {
                         m,
                         n,
                         k,
-                        self.arr.data(),
+                        a,
                         lda,
                         self.tau.as_slice(),
                         arr.data_mut(),
@@ -601,7 +658,7 @@ macro_rules! implement_qr_complex {
                         m,
                         n,
                         k,
-                        self.arr.data(),
+                        a,
                         lda,
                         self.tau.as_slice(),
                         arr.data_mut(),
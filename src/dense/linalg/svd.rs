@@ -5,7 +5,8 @@ use crate::dense::types::{c32, c64, RlstError, RlstResult, RlstScalar};
 use lapack::{cgesvd, dgesvd, sgesvd, zgesvd};
 use num::traits::Zero;
 
-use super::assert_lapack_stride;
+use super::lapack_layout;
+use crate::dense::layout::{leading_dimension, MemoryLayout};
 
 /// Singular value decomposition
 pub trait MatrixSvd: RlstScalar {
@@ -44,6 +45,9 @@ pub trait MatrixSvd: RlstScalar {
     /// - `singular_values` - Stores the `k` singular values of `A`.
     /// - `mode` - Choose between full SVD [SvdMode::Full] or reduced SVD [SvdMode::Reduced].
     ///
+    /// The arrays `u` and `vt` must have the same memory layout as `A`, which can be
+    /// column-major or row-major.
+    ///
     /// This method allocates temporary memory during execution.
     fn into_svd_alloc<
         ArrayImpl: UnsafeRandomAccessByValue<2, Item = Self>
@@ -91,12 +95,15 @@ macro_rules! impl_svd_real {
                 let mut work = [<$scalar as Zero>::zero(); 1];
                 assert!(!arr.is_empty(), "Matrix is empty.");
 
-                assert_lapack_stride(arr.stride());
-                let m = arr.shape()[0] as i32;
-                let n = arr.shape()[1] as i32;
+                // The transpose of a matrix has the same singular values.
+                let (layout, lda) = lapack_layout(arr.shape(), arr.stride());
+                let (m, n) = match layout {
+                    MemoryLayout::ColumnMajor => (arr.shape()[0] as i32, arr.shape()[1] as i32),
+                    MemoryLayout::RowMajor => (arr.shape()[1] as i32, arr.shape()[0] as i32),
+                };
                 let k = std::cmp::min(m, n);
                 assert_eq!(k, singular_values.len() as i32);
-                let lda = arr.stride()[1] as i32;
+                let lda = lda as i32;
                 let mut u = [<$scalar as Zero>::zero(); 1];
                 let mut vt = [<$scalar as Zero>::zero(); 1];
                 let ldu = 1;
@@ -182,10 +189,6 @@ macro_rules! impl_svd_real {
 
                 assert!(!arr.is_empty(), "Matrix is empty.");
 
-                assert_lapack_stride(arr.stride());
-                assert_lapack_stride(u.stride());
-                assert_lapack_stride(vt.stride());
-
                 let m = arr.shape()[0] as i32;
                 let n = arr.shape()[1] as i32;
                 let k = std::cmp::min(m, n);
@@ -208,9 +211,23 @@ macro_rules! impl_svd_real {
                     }
                 }
 
-                let lda = arr.stride()[1] as i32;
-                let ldu = u.stride()[1] as i32;
-                let ldvt = vt.stride()[1] as i32;
+                // For a row-major matrix A Lapack computes the SVD of its transpose.
+                // If u and vt are also row-major the storage of U is the Vt of the
+                // transpose and vice versa.
+                let (layout, lda) = lapack_layout(arr.shape(), arr.stride());
+                let ldu = leading_dimension(u.shape(), u.stride(), layout)
+                    .unwrap_or_else(|| panic!("`u` must have the same memory layout as `arr`."));
+                let ldvt = leading_dimension(vt.shape(), vt.stride(), layout)
+                    .unwrap_or_else(|| panic!("`vt` must have the same memory layout as `arr`."));
+
+                let (m, n, u_data, ldu, vt_data, ldvt) = match layout {
+                    MemoryLayout::ColumnMajor => (m, n, u.data_mut(), ldu, vt.data_mut(), ldvt),
+                    MemoryLayout::RowMajor => (n, m, vt.data_mut(), ldvt, u.data_mut(), ldu),
+                };
+
+                let lda = lda as i32;
+                let ldu = ldu as i32;
+                let ldvt = ldvt as i32;
                 let mut info = 0;
 
                 unsafe {
@@ -222,9 +239,9 @@ macro_rules! impl_svd_real {
                         arr.data_mut(),
                         lda,
                         singular_values,
-                        u.data_mut(),
+                        u_data,
                         ldu,
-                        vt.data_mut(),
+                        vt_data,
                         ldvt,
                         &mut work,
                         lwork,
@@ -248,9 +265,9 @@ macro_rules! impl_svd_real {
                         arr.data_mut(),
                         lda,
                         singular_values,
-                        u.data_mut(),
+                        u_data,
                         ldu,
-                        vt.data_mut(),
+                        vt_data,
                         ldvt,
                         &mut work,
                         lwork,
@@ -284,12 +301,15 @@ macro_rules! impl_svd_complex {
                 let mut work = [<$scalar as Zero>::zero(); 1];
                 assert!(!arr.is_empty(), "Matrix is empty.");
 
-                assert_lapack_stride(arr.stride());
-                let m = arr.shape()[0] as i32;
-                let n = arr.shape()[1] as i32;
+                // The transpose of a matrix has the same singular values.
+                let (layout, lda) = lapack_layout(arr.shape(), arr.stride());
+                let (m, n) = match layout {
+                    MemoryLayout::ColumnMajor => (arr.shape()[0] as i32, arr.shape()[1] as i32),
+                    MemoryLayout::RowMajor => (arr.shape()[1] as i32, arr.shape()[0] as i32),
+                };
                 let k = std::cmp::min(m, n);
                 assert_eq!(k, singular_values.len() as i32);
-                let lda = arr.stride()[1] as i32;
+                let lda = lda as i32;
                 let mut u = [<$scalar as Zero>::zero(); 1];
                 let mut vt = [<$scalar as Zero>::zero(); 1];
                 let ldu = 1;
@@ -380,10 +400,6 @@ macro_rules! impl_svd_complex {
 
                 assert!(!arr.is_empty(), "Matrix is empty.");
 
-                assert_lapack_stride(arr.stride());
-                assert_lapack_stride(u.stride());
-                assert_lapack_stride(vt.stride());
-
                 let m = arr.shape()[0] as i32;
                 let n = arr.shape()[1] as i32;
                 let k = std::cmp::min(m, n);
@@ -406,9 +422,23 @@ macro_rules! impl_svd_complex {
                     }
                 }
 
-                let lda = arr.stride()[1] as i32;
-                let ldu = u.stride()[1] as i32;
-                let ldvt = vt.stride()[1] as i32;
+                // For a row-major matrix A Lapack computes the SVD of its transpose.
+                // If u and vt are also row-major the storage of U is the Vt of the
+                // transpose and vice versa.
+                let (layout, lda) = lapack_layout(arr.shape(), arr.stride());
+                let ldu = leading_dimension(u.shape(), u.stride(), layout)
+                    .unwrap_or_else(|| panic!("`u` must have the same memory layout as `arr`."));
+                let ldvt = leading_dimension(vt.shape(), vt.stride(), layout)
+                    .unwrap_or_else(|| panic!("`vt` must have the same memory layout as `arr`."));
+
+                let (m, n, u_data, ldu, vt_data, ldvt) = match layout {
+                    MemoryLayout::ColumnMajor => (m, n, u.data_mut(), ldu, vt.data_mut(), ldvt),
+                    MemoryLayout::RowMajor => (n, m, vt.data_mut(), ldvt, u.data_mut(), ldu),
+                };
+
+                let lda = lda as i32;
+                let ldu = ldu as i32;
+                let ldvt = ldvt as i32;
                 let mut info = 0;
 
                 let mut rwork =
@@ -423,9 +453,9 @@ macro_rules! impl_svd_complex {
                         arr.data_mut(),
                         lda,
                         singular_values,
-                        u.data_mut(),
+                        u_data,
                         ldu,
-                        vt.data_mut(),
+                        vt_data,
                         ldvt,
                         &mut work,
                         lwork,
@@ -450,9 +480,9 @@ macro_rules! impl_svd_complex {
                         arr.data_mut(),
                         lda,
                         singular_values,
-                        u.data_mut(),
+                        u_data,
                         ldu,
-                        vt.data_mut(),
+                        vt_data,
                         ldvt,
                         &mut work,
                         lwork,
@@ -514,6 +544,9 @@ impl<
     /// - `singular_values` - Stores the `k` singular values of `A`.
     /// - `mode` - Choose between full SVD [SvdMode::Full] or reduced SVD [SvdMode::Reduced].
     ///
+    /// The arrays `u` and `vt` must have the same memory layout as `A`, which can be
+    /// column-major or row-major.
+    ///
     /// This method allocates temporary memory during execution.
     pub fn into_svd_alloc<
         ArrayImplU: UnsafeRandomAccessByValue<2, Item = Item>
@@ -50,6 +50,17 @@ pub enum RlstError {
     /// Matrix is not Hermitian
     #[error("Matrix is not Hermitian (complex conjugate symmetric).")]
     MatrixNotHermitian,
+    /// Breakdown of a factorisation
+    #[error("Factorisation broke down at pivot {pivot}: {reason}")]
+    FactorisationBreakdown {
+        /// Index of the pivot
+        pivot: usize,
+        /// Reason for the breakdown
+        reason: String,
+    },
+    /// Invalid sparse matrix
+    #[error("Invalid sparse matrix: {0}")]
+    InvalidSparseMatrix(String),
 }
 
 /// Alias for an Rlst Result type.
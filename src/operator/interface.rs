@@ -6,5 +6,11 @@ pub mod sparse_operator;
 
 pub use array_vector_space::{ArrayVectorSpace, ArrayVectorSpaceElement};
 pub use dense_matrix_operator::DenseMatrixOperator;
+pub use sparse_operator::AmgOperator;
+pub use sparse_operator::BsrMatrixOperator;
 pub use sparse_operator::CscMatrixOperator;
 pub use sparse_operator::CsrMatrixOperator;
+pub use sparse_operator::IncompleteCholeskyOperator;
+pub use sparse_operator::IncompleteLuOperator;
+pub use sparse_operator::SparseCholeskyOperator;
+pub use sparse_operator::StationaryIterationOperator;
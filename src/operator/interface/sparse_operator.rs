@@ -1,6 +1,15 @@
 //! Sparse operator
+use crate::dense::base_array::BaseArray;
+use crate::dense::data_container::VectorContainer;
+use crate::dense::linalg::lu::MatrixLuDecomposition;
 use crate::dense::traits::{RawAccess, RawAccessMut, Shape};
-use crate::dense::types::RlstScalar;
+use crate::dense::types::{RlstResult, RlstScalar};
+use crate::sparse::precond::amg::{AmgHierarchy, CoarseLu};
+use crate::sparse::precond::incomplete_cholesky::IncompleteCholesky;
+use crate::sparse::precond::incomplete_lu::IncompleteLu;
+use crate::sparse::precond::stationary::StationaryIteration;
+use crate::sparse::sparse_mat::bsr_mat::BsrMatrix;
+use crate::sparse::sparse_mat::cholesky::SparseCholesky;
 use crate::sparse::sparse_mat::csc_mat::CscMatrix;
 use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
 use crate::{
@@ -11,6 +20,32 @@ use crate::{
 
 use super::array_vector_space::ArrayVectorSpace;
 
+/// Set `y = alpha * z + beta * y`, where `solve(x, z)` computes `z` from a zero initial guess.
+///
+/// If `beta` is zero the solution is computed directly in `y`.
+fn apply_solve<Item: RlstScalar>(
+    alpha: Item,
+    x: &[Item],
+    beta: Item,
+    y: &mut [Item],
+    solve: impl FnOnce(&[Item], &mut [Item]) -> RlstResult<()>,
+) -> RlstResult<()> {
+    if beta == Item::zero() {
+        y.fill(Item::zero());
+        solve(x, y)?;
+        if alpha != Item::one() {
+            y.iter_mut().for_each(|out| *out *= alpha);
+        }
+    } else {
+        let mut solution = vec![Item::zero(); y.len()];
+        solve(x, &mut solution)?;
+        for (out, value) in y.iter_mut().zip(solution) {
+            *out = beta * *out + alpha * value;
+        }
+    }
+    Ok(())
+}
+
 /// CSR matrix operator
 pub struct CsrMatrixOperator<'a, Item: RlstScalar> {
     csr_mat: &'a CsrMatrix<Item>,
@@ -134,3 +169,394 @@ impl<Item: RlstScalar> AsApply for CscMatrixOperator<'_, Item> {
         Ok(())
     }
 }
+
+/// BSR matrix operator
+pub struct BsrMatrixOperator<'a, Item: RlstScalar> {
+    bsr_mat: &'a BsrMatrix<Item>,
+    domain: &'a ArrayVectorSpace<Item>,
+    range: &'a ArrayVectorSpace<Item>,
+}
+
+impl<Item: RlstScalar> std::fmt::Debug for BsrMatrixOperator<'_, Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BsrMatrixOperator")
+            .field("Dimension", &self.bsr_mat.shape())
+            .field("Block size", &self.bsr_mat.block_size())
+            .field("Type", &"bsr")
+            .finish()
+    }
+}
+
+impl<'a, Item: RlstScalar> BsrMatrixOperator<'a, Item> {
+    /// Create a new BSR matrix operator
+    pub fn new(
+        bsr_mat: &'a BsrMatrix<Item>,
+        domain: &'a ArrayVectorSpace<Item>,
+        range: &'a ArrayVectorSpace<Item>,
+    ) -> Self {
+        let shape = bsr_mat.shape();
+        assert_eq!(domain.dimension(), shape[1]);
+        assert_eq!(range.dimension(), shape[0]);
+        Self {
+            bsr_mat,
+            domain,
+            range,
+        }
+    }
+}
+
+impl<Item: RlstScalar> OperatorBase for BsrMatrixOperator<'_, Item> {
+    type Domain = ArrayVectorSpace<Item>;
+
+    type Range = ArrayVectorSpace<Item>;
+
+    fn domain(&self) -> &Self::Domain {
+        self.domain
+    }
+
+    fn range(&self) -> &Self::Range {
+        self.range
+    }
+}
+
+impl<Item: RlstScalar> AsApply for BsrMatrixOperator<'_, Item> {
+    fn apply_extended(
+        &self,
+        alpha: <Self::Range as LinearSpace>::F,
+        x: &<Self::Domain as LinearSpace>::E,
+        beta: <Self::Range as LinearSpace>::F,
+        y: &mut <Self::Range as LinearSpace>::E,
+    ) -> crate::dense::types::RlstResult<()> {
+        self.bsr_mat
+            .matmul(alpha, x.view().data(), beta, y.view_mut().data_mut());
+        Ok(())
+    }
+}
+
+/// Operator that applies the inverse of a matrix through its sparse Cholesky or LDL factorisation
+pub struct SparseCholeskyOperator<'a, Item: RlstScalar> {
+    factorisation: &'a SparseCholesky<Item>,
+    space: &'a ArrayVectorSpace<Item>,
+}
+
+impl<Item: RlstScalar> std::fmt::Debug for SparseCholeskyOperator<'_, Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SparseCholeskyOperator")
+            .field("Dimension", &self.factorisation.symbolic().dim())
+            .field(
+                "Type",
+                &if self.factorisation.is_ldl() {
+                    "ldl"
+                } else {
+                    "cholesky"
+                },
+            )
+            .finish()
+    }
+}
+
+impl<'a, Item: RlstScalar> SparseCholeskyOperator<'a, Item> {
+    /// Create a new sparse Cholesky solve operator
+    pub fn new(factorisation: &'a SparseCholesky<Item>, space: &'a ArrayVectorSpace<Item>) -> Self {
+        assert_eq!(space.dimension(), factorisation.symbolic().dim());
+        Self {
+            factorisation,
+            space,
+        }
+    }
+}
+
+impl<Item: RlstScalar> OperatorBase for SparseCholeskyOperator<'_, Item> {
+    type Domain = ArrayVectorSpace<Item>;
+
+    type Range = ArrayVectorSpace<Item>;
+
+    fn domain(&self) -> &Self::Domain {
+        self.space
+    }
+
+    fn range(&self) -> &Self::Range {
+        self.space
+    }
+}
+
+impl<Item: RlstScalar> AsApply for SparseCholeskyOperator<'_, Item> {
+    fn apply_extended(
+        &self,
+        alpha: <Self::Range as LinearSpace>::F,
+        x: &<Self::Domain as LinearSpace>::E,
+        beta: <Self::Range as LinearSpace>::F,
+        y: &mut <Self::Range as LinearSpace>::E,
+    ) -> crate::dense::types::RlstResult<()> {
+        apply_solve(
+            alpha,
+            x.view().data(),
+            beta,
+            y.view_mut().data_mut(),
+            |rhs, solution| {
+                self.factorisation.solve(rhs, solution);
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Incomplete LU preconditioner operator
+pub struct IncompleteLuOperator<'a, Item: RlstScalar> {
+    factorisation: &'a IncompleteLu<Item>,
+    space: &'a ArrayVectorSpace<Item>,
+}
+
+impl<Item: RlstScalar> std::fmt::Debug for IncompleteLuOperator<'_, Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IncompleteLuOperator")
+            .field("Dimension", &self.factorisation.dim())
+            .field("Nelems", &self.factorisation.nelems())
+            .finish()
+    }
+}
+
+impl<'a, Item: RlstScalar> IncompleteLuOperator<'a, Item> {
+    /// Create a new operator that applies `(L U)^{-1}` of an incomplete LU factorisation
+    pub fn new(factorisation: &'a IncompleteLu<Item>, space: &'a ArrayVectorSpace<Item>) -> Self {
+        assert_eq!(space.dimension(), factorisation.dim());
+        Self {
+            factorisation,
+            space,
+        }
+    }
+}
+
+impl<Item: RlstScalar> OperatorBase for IncompleteLuOperator<'_, Item> {
+    type Domain = ArrayVectorSpace<Item>;
+
+    type Range = ArrayVectorSpace<Item>;
+
+    fn domain(&self) -> &Self::Domain {
+        self.space
+    }
+
+    fn range(&self) -> &Self::Range {
+        self.space
+    }
+}
+
+impl<Item: RlstScalar> AsApply for IncompleteLuOperator<'_, Item> {
+    fn apply_extended(
+        &self,
+        alpha: <Self::Range as LinearSpace>::F,
+        x: &<Self::Domain as LinearSpace>::E,
+        beta: <Self::Range as LinearSpace>::F,
+        y: &mut <Self::Range as LinearSpace>::E,
+    ) -> crate::dense::types::RlstResult<()> {
+        apply_solve(
+            alpha,
+            x.view().data(),
+            beta,
+            y.view_mut().data_mut(),
+            |rhs, solution| {
+                self.factorisation.solve(rhs, solution);
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Incomplete Cholesky preconditioner operator
+pub struct IncompleteCholeskyOperator<'a, Item: RlstScalar> {
+    factorisation: &'a IncompleteCholesky<Item>,
+    space: &'a ArrayVectorSpace<Item>,
+}
+
+impl<Item: RlstScalar> std::fmt::Debug for IncompleteCholeskyOperator<'_, Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IncompleteCholeskyOperator")
+            .field("Dimension", &self.factorisation.dim())
+            .field("Nelems", &self.factorisation.nelems())
+            .finish()
+    }
+}
+
+impl<'a, Item: RlstScalar> IncompleteCholeskyOperator<'a, Item> {
+    /// Create a new operator that applies `(L L^H)^{-1}` of an incomplete Cholesky factorisation
+    pub fn new(
+        factorisation: &'a IncompleteCholesky<Item>,
+        space: &'a ArrayVectorSpace<Item>,
+    ) -> Self {
+        assert_eq!(space.dimension(), factorisation.dim());
+        Self {
+            factorisation,
+            space,
+        }
+    }
+}
+
+impl<Item: RlstScalar> OperatorBase for IncompleteCholeskyOperator<'_, Item> {
+    type Domain = ArrayVectorSpace<Item>;
+
+    type Range = ArrayVectorSpace<Item>;
+
+    fn domain(&self) -> &Self::Domain {
+        self.space
+    }
+
+    fn range(&self) -> &Self::Range {
+        self.space
+    }
+}
+
+impl<Item: RlstScalar> AsApply for IncompleteCholeskyOperator<'_, Item> {
+    fn apply_extended(
+        &self,
+        alpha: <Self::Range as LinearSpace>::F,
+        x: &<Self::Domain as LinearSpace>::E,
+        beta: <Self::Range as LinearSpace>::F,
+        y: &mut <Self::Range as LinearSpace>::E,
+    ) -> crate::dense::types::RlstResult<()> {
+        apply_solve(
+            alpha,
+            x.view().data(),
+            beta,
+            y.view_mut().data_mut(),
+            |rhs, solution| {
+                self.factorisation.solve(rhs, solution);
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Algebraic multigrid preconditioner operator
+///
+/// Each application performs one V-cycle with a zero initial guess.
+pub struct AmgOperator<'a, Item: RlstScalar> {
+    hierarchy: &'a AmgHierarchy<Item>,
+    space: &'a ArrayVectorSpace<Item>,
+}
+
+impl<Item: RlstScalar> std::fmt::Debug for AmgOperator<'_, Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AmgOperator")
+            .field("Dimension", &self.space.dimension())
+            .field("Type", &"amg")
+            .finish()
+    }
+}
+
+impl<'a, Item: RlstScalar> AmgOperator<'a, Item>
+where
+    CoarseLu<Item>:
+        MatrixLuDecomposition<Item = Item, ArrayImpl = BaseArray<Item, VectorContainer<Item>, 2>>,
+{
+    /// Create a new operator that applies a V-cycle of a multigrid hierarchy
+    pub fn new(hierarchy: &'a AmgHierarchy<Item>, space: &'a ArrayVectorSpace<Item>) -> Self {
+        assert_eq!(space.dimension(), hierarchy.dim());
+        Self { hierarchy, space }
+    }
+}
+
+impl<Item: RlstScalar> OperatorBase for AmgOperator<'_, Item> {
+    type Domain = ArrayVectorSpace<Item>;
+
+    type Range = ArrayVectorSpace<Item>;
+
+    fn domain(&self) -> &Self::Domain {
+        self.space
+    }
+
+    fn range(&self) -> &Self::Range {
+        self.space
+    }
+}
+
+impl<Item: RlstScalar> AsApply for AmgOperator<'_, Item>
+where
+    CoarseLu<Item>:
+        MatrixLuDecomposition<Item = Item, ArrayImpl = BaseArray<Item, VectorContainer<Item>, 2>>,
+{
+    fn apply_extended(
+        &self,
+        alpha: <Self::Range as LinearSpace>::F,
+        x: &<Self::Domain as LinearSpace>::E,
+        beta: <Self::Range as LinearSpace>::F,
+        y: &mut <Self::Range as LinearSpace>::E,
+    ) -> crate::dense::types::RlstResult<()> {
+        apply_solve(
+            alpha,
+            x.view().data(),
+            beta,
+            y.view_mut().data_mut(),
+            |rhs, solution| self.hierarchy.vcycle(rhs, solution),
+        )
+    }
+}
+
+/// Stationary iteration smoother operator
+///
+/// Each application performs a fixed number of iterations with a zero initial guess.
+pub struct StationaryIterationOperator<'a, Item: RlstScalar> {
+    iteration: &'a StationaryIteration<'a, Item>,
+    sweeps: usize,
+    space: &'a ArrayVectorSpace<Item>,
+}
+
+impl<Item: RlstScalar> std::fmt::Debug for StationaryIterationOperator<'_, Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StationaryIterationOperator")
+            .field("Dimension", &self.iteration.dim())
+            .field("Method", &self.iteration.method())
+            .field("Sweeps", &self.sweeps)
+            .finish()
+    }
+}
+
+impl<'a, Item: RlstScalar> StationaryIterationOperator<'a, Item> {
+    /// Create a new operator that applies `sweeps` iterations of a stationary method
+    pub fn new(
+        iteration: &'a StationaryIteration<'a, Item>,
+        sweeps: usize,
+        space: &'a ArrayVectorSpace<Item>,
+    ) -> Self {
+        assert_eq!(space.dimension(), iteration.dim());
+        Self {
+            iteration,
+            sweeps,
+            space,
+        }
+    }
+}
+
+impl<Item: RlstScalar> OperatorBase for StationaryIterationOperator<'_, Item> {
+    type Domain = ArrayVectorSpace<Item>;
+
+    type Range = ArrayVectorSpace<Item>;
+
+    fn domain(&self) -> &Self::Domain {
+        self.space
+    }
+
+    fn range(&self) -> &Self::Range {
+        self.space
+    }
+}
+
+impl<Item: RlstScalar> AsApply for StationaryIterationOperator<'_, Item> {
+    fn apply_extended(
+        &self,
+        alpha: <Self::Range as LinearSpace>::F,
+        x: &<Self::Domain as LinearSpace>::E,
+        beta: <Self::Range as LinearSpace>::F,
+        y: &mut <Self::Range as LinearSpace>::E,
+    ) -> crate::dense::types::RlstResult<()> {
+        apply_solve(
+            alpha,
+            x.view().data(),
+            beta,
+            y.view_mut().data_mut(),
+            |rhs, solution| {
+                self.iteration.smooth(rhs, solution, self.sweeps);
+                Ok(())
+            },
+        )
+    }
+}
@@ -11,6 +11,7 @@ pub struct CgIteration<'a, Space: InnerProductSpace, Op: AsApply<Domain = Space,
     x: Space::E,
     max_iter: usize,
     tol: <Space::F as RlstScalar>::Real,
+    preconditioner: Option<&'a dyn AsApply<Domain = Space, Range = Space>>,
     #[allow(clippy::type_complexity)]
     callable: Option<Box<dyn FnMut(&<Space as LinearSpace>::E, &<Space as LinearSpace>::E) + 'a>>,
     print_debug: bool,
@@ -28,6 +29,7 @@ impl<'a, Space: InnerProductSpace, Op: AsApply<Domain = Space, Range = Space>>
             x: op.domain().zero(),
             max_iter: 1000,
             tol: num::cast::<f64, <Space::F as RlstScalar>::Real>(1E-6).unwrap(),
+            preconditioner: None,
             callable: None,
             print_debug: false,
         }
@@ -51,6 +53,18 @@ impl<'a, Space: InnerProductSpace, Op: AsApply<Domain = Space, Range = Space>>
         self
     }
 
+    /// Set a preconditioner
+    ///
+    /// The preconditioner is applied to the residual in each iteration and should
+    /// approximate the inverse of the operator. It must be Hermitian positive definite.
+    pub fn set_preconditioner(
+        mut self,
+        preconditioner: &'a impl AsApply<Domain = Space, Range = Space>,
+    ) -> Self {
+        self.preconditioner = Some(preconditioner);
+        self
+    }
+
     /// Set the cammable
     pub fn set_callable(mut self, callable: impl FnMut(&Space::E, &Space::E) + 'a) -> Self {
         self.callable = Some(Box::new(callable));
@@ -82,11 +96,19 @@ impl<'a, Space: InnerProductSpace, Op: AsApply<Domain = Space, Range = Space>>
         let mut res = self.space.new_from(self.rhs);
         res.sum_inplace(&self.operator.apply(&self.x).neg());
 
-        let mut p = self.space.new_from(&res);
+        let space = self.space;
+        let preconditioner = self.preconditioner;
+        let precondition = |res: &Space::E| match preconditioner {
+            Some(preconditioner) => preconditioner.apply(res),
+            None => space.new_from(res),
+        };
+
+        let mut z = precondition(&res);
+        let mut p = self.space.new_from(&z);
 
         let rhs_norm = self.space.norm(self.rhs);
-        let mut res_inner = self.space.inner(&res, &res);
-        let mut res_norm = res_inner.abs().sqrt();
+        let mut res_inner = self.space.inner(&z, &res);
+        let mut res_norm = self.space.norm(&res);
         let mut rel_res = res_norm / rhs_norm;
 
         if rel_res < self.tol {
@@ -113,8 +135,7 @@ impl<'a, Space: InnerProductSpace, Op: AsApply<Domain = Space, Range = Space>>
                 callable(&self.x, &res);
             }
             let res_inner_previous = res_inner;
-            res_inner = self.space.inner(&res, &res);
-            res_norm = res_inner.abs().sqrt();
+            res_norm = self.space.norm(&res);
             rel_res = res_norm / rhs_norm;
             if res_norm < self.tol {
                 if self.print_debug {
@@ -122,9 +143,11 @@ impl<'a, Space: InnerProductSpace, Op: AsApply<Domain = Space, Range = Space>>
                 }
                 return (self.x, rel_res);
             }
+            z = precondition(&res);
+            res_inner = self.space.inner(&z, &res);
             let beta = res_inner / res_inner_previous;
             p.scale_inplace(beta);
-            p.sum_inplace(&res);
+            p.sum_inplace(&z);
         }
 
         if self.print_debug {
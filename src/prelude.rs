@@ -58,6 +58,8 @@ pub use crate::dense::data_container::{
 };
 
 pub use crate::dense::array::empty_axis::AxisPosition;
+pub use crate::dense::array::sort::{SearchSide, Sortable};
+pub use crate::dense::layout::MemoryLayout;
 
 pub use crate::dense::linalg::inverse::MatrixInverse;
 pub use crate::dense::linalg::lu::{LuDecomposition, MatrixLuDecomposition};
@@ -67,6 +69,8 @@ pub use crate::dense::linalg::svd::{MatrixSvd, SvdMode};
 pub use crate::dense::linalg::interpolative_decomposition::{MatrixId, IdDecomposition};
 pub use crate::dense::linalg::null_space::{MatrixNull, NullSpace};
 
+pub use crate::dense::array::contraction::{contract, einsum};
+pub use crate::dense::array::kronecker::{khatri_rao, kron, KhatriRaoArray, KronArray};
 pub use crate::dense::array::rank1_array::Rank1Array;
 
 pub use crate::dense::array::{DynamicArray, SliceArray, SliceArrayMut};
@@ -80,13 +84,31 @@ pub use crate::sparse::{
 };
 
 pub use crate::sparse::index_layout::DefaultSerialIndexLayout;
-pub use crate::sparse::sparse_mat::csc_mat::CscMatrix;
-pub use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+pub use crate::sparse::ordering::{
+    approximate_minimum_degree, nested_dissection, reverse_cuthill_mckee, BandStats,
+    SparseOrdering,
+};
+pub use crate::sparse::precond::amg::{AmgBuilder, AmgHierarchy, AmgSmoother};
+pub use crate::sparse::precond::incomplete_cholesky::IncompleteCholesky;
+pub use crate::sparse::precond::incomplete_lu::IncompleteLu;
+pub use crate::sparse::precond::stationary::{
+    jacobi_eigenvalue_bounds, StationaryIteration, StationaryMethod, SweepDirection,
+};
+pub use crate::sparse::sparse_mat::bsr_mat::BsrMatrix;
+pub use crate::sparse::sparse_mat::cholesky::{CholeskySymbolic, SparseCholesky};
+pub use crate::sparse::sparse_mat::csc_mat::{CscColRange, CscMatrix, CscMatrixView};
+pub use crate::sparse::sparse_mat::csr_mat::{CsrMatrix, CsrMatrixView, CsrRowRange};
+pub use crate::sparse::sparse_mat::sell_mat::SellMatrix;
+pub use crate::sparse::sparse_mat::sparse_builder::SparseBuilder;
+pub use crate::sparse::sparse_mat::spgemm::SpGemmSymbolic;
+pub use crate::sparse::sparse_mat::tools::SparseStats;
+pub use crate::sparse::sparse_mat::SparseIndex;
 pub use crate::sparse::traits::index_layout::IndexLayout;
 
 pub use crate::operator::interface::{
-    ArrayVectorSpace, ArrayVectorSpaceElement, CscMatrixOperator, CsrMatrixOperator,
-    DenseMatrixOperator,
+    AmgOperator, ArrayVectorSpace, ArrayVectorSpaceElement, BsrMatrixOperator, CscMatrixOperator,
+    CsrMatrixOperator, DenseMatrixOperator, IncompleteCholeskyOperator, IncompleteLuOperator,
+    SparseCholeskyOperator, StationaryIterationOperator,
 };
 
 pub use crate::operator::operations::conjugate_gradients::CgIteration;
@@ -9,6 +9,8 @@ pub mod distributed_vector;
 pub mod ghost_communicator;
 
 pub mod index_layout;
+pub mod ordering;
+pub mod precond;
 pub mod sparse_mat;
 pub mod tools;
 pub mod traits;
@@ -1,5 +1,6 @@
 //! Communication of ghost elements
 
+use std::mem::MaybeUninit;
 use std::os::raw::c_void;
 
 use crate::dense::types::RlstScalar;
@@ -168,10 +169,7 @@ impl GhostCommunicator {
         ghost_values: &mut [T],
     ) {
         assert_eq!(ghost_values.len(), self.total_receive_count);
-        let mut send_values = Vec::<T>::with_capacity(self.local_send_indices.len());
-        for &index in &self.local_send_indices {
-            send_values.push(local_values[index]);
-        }
+        let send_values = self.forward_send_values(local_values);
 
         unsafe {
             mpi_sys::MPI_Neighbor_alltoallv(
@@ -187,6 +185,41 @@ impl GhostCommunicator {
             );
         }
     }
+
+    /// Forward send ghosts while running `compute`.
+    ///
+    /// The communication is started before `compute` is called and has completed when this
+    /// function returns, so that it can be overlapped with computations on the local values.
+    pub fn forward_send_ghosts_overlapped<T: RlstScalar + Equivalence, R>(
+        &self,
+        local_values: &[T],
+        ghost_values: &mut [T],
+        compute: impl FnOnce() -> R,
+    ) -> R {
+        assert_eq!(ghost_values.len(), self.total_receive_count);
+        let send_values = self.forward_send_values(local_values);
+        let mut request = PendingRequest(unsafe { mpi_sys::RSMPI_REQUEST_NULL });
+
+        unsafe {
+            mpi_sys::MPI_Ineighbor_alltoallv(
+                send_values.as_ptr() as *const c_void,
+                self.neighborhood_send_counts.as_ptr(),
+                self.neighborhood_send_displacements.as_ptr(),
+                <T as Equivalence>::equivalent_datatype().as_raw(),
+                ghost_values.as_mut_ptr() as *mut c_void,
+                self.neighborhood_receive_counts.as_ptr(),
+                self.neighborhood_receive_displacements.as_ptr(),
+                <T as Equivalence>::equivalent_datatype().as_raw(),
+                self.neighbor_comm.as_raw(),
+                &mut request.0,
+            );
+        }
+
+        let result = compute();
+        request.wait();
+        result
+    }
+
     /// Backward send ghosts
     pub fn backward_send_ghosts<Acc: Fn(&mut T, &T), T: RlstScalar + Equivalence>(
         &self,
@@ -216,4 +249,34 @@ impl GhostCommunicator {
             acc(&mut local_values[local_index], &value);
         }
     }
+
+    fn forward_send_values<T: RlstScalar>(&self, local_values: &[T]) -> Vec<T> {
+        self.local_send_indices
+            .iter()
+            .map(|&index| local_values[index])
+            .collect()
+    }
+}
+
+/// A non-blocking request that is waited for when it goes out of scope.
+///
+/// This keeps the buffers of the request alive if the computation that overlaps the
+/// communication panics.
+struct PendingRequest(mpi_sys::MPI_Request);
+
+impl PendingRequest {
+    fn wait(&mut self) {
+        if self.0 != unsafe { mpi_sys::RSMPI_REQUEST_NULL } {
+            let mut status = MaybeUninit::<mpi_sys::MPI_Status>::uninit();
+            unsafe {
+                mpi_sys::MPI_Wait(&mut self.0, status.as_mut_ptr());
+            }
+        }
+    }
+}
+
+impl Drop for PendingRequest {
+    fn drop(&mut self) {
+        self.wait();
+    }
 }
@@ -0,0 +1,442 @@
+//! Fill-reducing and bandwidth-reducing orderings of sparse matrices.
+//!
+//! The orderings are computed from the sparsity graph of a square [CsrMatrix], i.e. the
+//! pattern of `A + A^T` without the diagonal. Each ordering is returned as a
+//! [SparseOrdering] whose permutation can be passed to [CsrMatrix::permute] to obtain
+//! `P A P^T`. A [SparseOrdering] also reports the bandwidth and profile of the matrix
+//! before and after the permutation.
+//!
+//! - [reverse_cuthill_mckee] reduces the bandwidth, e.g. for banded factorisations.
+//! - [approximate_minimum_degree] reduces the fill-in of sparse direct factorisations.
+//! - [nested_dissection] recursively splits the graph by vertex separators.
+
+use std::collections::BTreeSet;
+
+use crate::dense::traits::Shape;
+use crate::dense::types::RlstScalar;
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::SparseIndex;
+
+/// Bandwidth and profile of a sparsity pattern.
+///
+/// Both are computed for the symmetric pattern of `A + A^T`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BandStats {
+    bandwidth: usize,
+    profile: usize,
+}
+
+impl BandStats {
+    /// Compute the statistics of a square matrix.
+    pub fn from_csr<Item: RlstScalar, I: SparseIndex>(mat: &CsrMatrix<Item, I>) -> Self {
+        let graph = Graph::from_csr(mat);
+        graph.stats(&(0..graph.nnodes()).collect::<Vec<_>>())
+    }
+
+    /// Largest distance `|i - j|` of a stored entry `(i, j)` from the diagonal.
+    pub fn bandwidth(&self) -> usize {
+        self.bandwidth
+    }
+
+    /// Sum over all rows `i` of `i - j`, where `j <= i` is the first column of the
+    /// envelope in row `i`.
+    pub fn profile(&self) -> usize {
+        self.profile
+    }
+}
+
+/// A permutation of the rows and columns of a sparse matrix.
+#[derive(Clone, Debug)]
+pub struct SparseOrdering {
+    permutation: Vec<usize>,
+    before: BandStats,
+    after: BandStats,
+}
+
+impl SparseOrdering {
+    fn new(graph: &Graph, permutation: Vec<usize>) -> Self {
+        debug_assert_eq!(permutation.len(), graph.nnodes());
+        Self {
+            before: graph.stats(&(0..graph.nnodes()).collect::<Vec<_>>()),
+            after: graph.stats(&permutation),
+            permutation,
+        }
+    }
+
+    /// The permutation. Row `i` of the permuted matrix is row `permutation[i]` of the original.
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// The inverse permutation. Row `i` of the original matrix is row
+    /// `inverse_permutation[i]` of the permuted matrix.
+    pub fn inverse_permutation(&self) -> Vec<usize> {
+        let mut inverse = vec![0; self.permutation.len()];
+        for (new, &old) in self.permutation.iter().enumerate() {
+            inverse[old] = new;
+        }
+        inverse
+    }
+
+    /// Statistics of the original matrix.
+    pub fn before(&self) -> BandStats {
+        self.before
+    }
+
+    /// Statistics of the permuted matrix.
+    pub fn after(&self) -> BandStats {
+        self.after
+    }
+}
+
+/// Compute the reverse Cuthill–McKee ordering of a square matrix.
+///
+/// Each connected component is traversed breadth-first from a pseudo-peripheral node,
+/// visiting neighbours in order of increasing degree. The resulting order is reversed.
+pub fn reverse_cuthill_mckee<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+) -> SparseOrdering {
+    let graph = Graph::from_csr(mat);
+    let nnodes = graph.nnodes();
+
+    let mut permutation = Vec::<usize>::with_capacity(nnodes);
+    let mut visited = vec![false; nnodes];
+    let all = vec![true; nnodes];
+
+    // Start each component from a node of minimum degree.
+    let mut nodes = (0..nnodes).collect::<Vec<_>>();
+    nodes.sort_by_key(|&node| graph.degree(node));
+
+    for &node in &nodes {
+        if visited[node] {
+            continue;
+        }
+        let root = graph.pseudo_peripheral_node(node, &all);
+
+        let start = permutation.len();
+        permutation.push(root);
+        visited[root] = true;
+        let mut next = start;
+        while next < permutation.len() {
+            let current = permutation[next];
+            next += 1;
+
+            let first_new = permutation.len();
+            for &neighbour in graph.neighbours(current) {
+                if !visited[neighbour] {
+                    visited[neighbour] = true;
+                    permutation.push(neighbour);
+                }
+            }
+            permutation[first_new..].sort_by_key(|&neighbour| graph.degree(neighbour));
+        }
+    }
+
+    permutation.reverse();
+    SparseOrdering::new(&graph, permutation)
+}
+
+/// Compute an approximate minimum degree ordering of a square matrix.
+///
+/// The elimination is simulated on a quotient graph. Each step eliminates a node of
+/// minimum approximate degree, where the degree bound is the one of Amestoy, Davis and
+/// Duff. Supervariables and dense rows are not treated separately.
+pub fn approximate_minimum_degree<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+) -> SparseOrdering {
+    let graph = Graph::from_csr(mat);
+    let nnodes = graph.nnodes();
+
+    // Variables adjacent to each variable that are not covered by an element.
+    let mut variables = (0..nnodes)
+        .map(|node| graph.neighbours(node).to_vec())
+        .collect::<Vec<_>>();
+    // Elements adjacent to each variable.
+    let mut elements = vec![Vec::<usize>::new(); nnodes];
+    // Variables of each element. An element is identified by its pivot.
+    let mut element_variables = vec![Vec::<usize>::new(); nnodes];
+
+    let mut eliminated = vec![false; nnodes];
+    let mut absorbed = vec![false; nnodes];
+    let mut degree = (0..nnodes)
+        .map(|node| graph.degree(node))
+        .collect::<Vec<_>>();
+    let mut queue = (0..nnodes)
+        .map(|node| (degree[node], node))
+        .collect::<BTreeSet<_>>();
+
+    // `in_pivot[node] == pivot` if `node` is in the element of `pivot`.
+    let mut in_pivot = vec![usize::MAX; nnodes];
+    // `external[e]` is the number of variables of element `e` outside the current pivot
+    // element, valid if `external_mark[e] == pivot`.
+    let mut external = vec![0; nnodes];
+    let mut external_mark = vec![usize::MAX; nnodes];
+
+    let mut permutation = Vec::<usize>::with_capacity(nnodes);
+
+    while let Some((_, pivot)) = queue.pop_first() {
+        permutation.push(pivot);
+        eliminated[pivot] = true;
+
+        // The new element contains all uneliminated neighbours of the pivot.
+        let mut pivot_variables = Vec::<usize>::new();
+        in_pivot[pivot] = pivot;
+        for &node in variables[pivot].iter().chain(
+            elements[pivot]
+                .iter()
+                .flat_map(|&element| element_variables[element].iter()),
+        ) {
+            if !eliminated[node] && in_pivot[node] != pivot {
+                in_pivot[node] = pivot;
+                pivot_variables.push(node);
+            }
+        }
+        for &element in &elements[pivot] {
+            absorbed[element] = true;
+            element_variables[element].clear();
+        }
+        variables[pivot].clear();
+        elements[pivot].clear();
+
+        // Update the adjacency of the variables in the new element.
+        for &node in &pivot_variables {
+            variables[node].retain(|&other| !eliminated[other] && in_pivot[other] != pivot);
+            elements[node].retain(|&element| !absorbed[element]);
+            elements[node].push(pivot);
+        }
+
+        // Compute the number of external variables of all neighbouring elements.
+        for &node in &pivot_variables {
+            for &element in &elements[node] {
+                if element == pivot {
+                    continue;
+                }
+                if external_mark[element] != pivot {
+                    external_mark[element] = pivot;
+                    external[element] = element_variables[element].len();
+                }
+                external[element] -= 1;
+            }
+        }
+
+        let remaining = nnodes - permutation.len();
+        let pivot_size = pivot_variables.len();
+        for &node in &pivot_variables {
+            let element_degree: usize = elements[node]
+                .iter()
+                .filter(|&&element| element != pivot)
+                .map(|&element| external[element])
+                .sum();
+            let new_degree = (remaining - 1)
+                .min(degree[node] + pivot_size - 1)
+                .min(variables[node].len() + pivot_size - 1 + element_degree);
+
+            queue.remove(&(degree[node], node));
+            degree[node] = new_degree;
+            queue.insert((new_degree, node));
+        }
+
+        element_variables[pivot] = pivot_variables;
+    }
+
+    SparseOrdering::new(&graph, permutation)
+}
+
+/// Compute a nested dissection ordering of a square matrix.
+///
+/// Each connected component is split into two parts by a level set of a breadth-first
+/// traversal from a pseudo-peripheral node. The parts are ordered recursively, followed
+/// by the separator. Components with at most `leaf_size` nodes are ordered by their
+/// reversed level structure.
+pub fn nested_dissection<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+    leaf_size: usize,
+) -> SparseOrdering {
+    let graph = Graph::from_csr(mat);
+    let nnodes = graph.nnodes();
+
+    let mut permutation = Vec::<usize>::with_capacity(nnodes);
+    let mut active = vec![true; nnodes];
+    dissect(
+        &graph,
+        (0..nnodes).collect(),
+        leaf_size.max(1),
+        &mut active,
+        &mut permutation,
+    );
+
+    SparseOrdering::new(&graph, permutation)
+}
+
+/// Order the nodes `nodes` by nested dissection and append them to `permutation`.
+///
+/// `active[node]` is true exactly for the nodes in `nodes` on entry and false on exit.
+fn dissect(
+    graph: &Graph,
+    nodes: Vec<usize>,
+    leaf_size: usize,
+    active: &mut [bool],
+    permutation: &mut Vec<usize>,
+) {
+    for &start in &nodes {
+        if !active[start] {
+            continue;
+        }
+        let root = graph.pseudo_peripheral_node(start, active);
+        let levels = graph.level_structure(root, active);
+
+        if levels.len() < 3 || levels.iter().map(|level| level.len()).sum::<usize>() <= leaf_size {
+            // Leaves are ordered by reverse level structure.
+            for &node in levels.iter().rev().flatten() {
+                active[node] = false;
+                permutation.push(node);
+            }
+            continue;
+        }
+
+        // Use the level containing the median node as separator.
+        let ncomponent = levels.iter().map(|level| level.len()).sum::<usize>();
+        let mut count = 0;
+        let mut separator = 1;
+        for (index, level) in levels.iter().enumerate() {
+            count += level.len();
+            if 2 * count >= ncomponent {
+                separator = index.clamp(1, levels.len() - 2);
+                break;
+            }
+        }
+
+        for &node in levels.iter().flatten() {
+            active[node] = false;
+        }
+        for part in [&levels[..separator], &levels[1 + separator..]] {
+            let part = part.iter().flatten().copied().collect::<Vec<_>>();
+            for &node in &part {
+                active[node] = true;
+            }
+            dissect(graph, part, leaf_size, active, permutation);
+        }
+        permutation.extend(levels[separator].iter().copied());
+    }
+}
+
+/// The symmetric sparsity graph of a square matrix without self loops.
+struct Graph {
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+}
+
+impl Graph {
+    fn from_csr<Item: RlstScalar, I: SparseIndex>(mat: &CsrMatrix<Item, I>) -> Self {
+        assert_eq!(
+            mat.shape()[0],
+            mat.shape()[1],
+            "Matrix must be square. {} != {}",
+            mat.shape()[0],
+            mat.shape()[1]
+        );
+        let nnodes = mat.shape()[0];
+
+        let mut adjacency = vec![Vec::<usize>::new(); nnodes];
+        for row in 0..nnodes {
+            for &col in
+                &mat.indices()[mat.indptr()[row].to_usize()..mat.indptr()[1 + row].to_usize()]
+            {
+                let col = col.to_usize();
+                if row != col {
+                    adjacency[row].push(col);
+                    adjacency[col].push(row);
+                }
+            }
+        }
+
+        let mut indptr = Vec::<usize>::with_capacity(1 + nnodes);
+        let mut indices = Vec::<usize>::new();
+        indptr.push(0);
+        for mut neighbours in adjacency {
+            neighbours.sort_unstable();
+            neighbours.dedup();
+            indices.extend(neighbours);
+            indptr.push(indices.len());
+        }
+
+        Self { indptr, indices }
+    }
+
+    fn nnodes(&self) -> usize {
+        self.indptr.len() - 1
+    }
+
+    fn neighbours(&self, node: usize) -> &[usize] {
+        &self.indices[self.indptr[node]..self.indptr[1 + node]]
+    }
+
+    fn degree(&self, node: usize) -> usize {
+        self.indptr[1 + node] - self.indptr[node]
+    }
+
+    /// Compute the statistics of the graph with nodes ordered by `permutation`.
+    fn stats(&self, permutation: &[usize]) -> BandStats {
+        let mut inverse = vec![0; permutation.len()];
+        for (new, &old) in permutation.iter().enumerate() {
+            inverse[old] = new;
+        }
+
+        let mut bandwidth = 0;
+        let mut profile = 0;
+        for (row, &node) in permutation.iter().enumerate() {
+            let first = self
+                .neighbours(node)
+                .iter()
+                .map(|&neighbour| inverse[neighbour])
+                .min()
+                .map_or(row, |col| col.min(row));
+            bandwidth = bandwidth.max(row - first);
+            profile += row - first;
+        }
+
+        BandStats { bandwidth, profile }
+    }
+
+    /// Breadth-first level structure of the component of `root` restricted to `active` nodes.
+    fn level_structure(&self, root: usize, active: &[bool]) -> Vec<Vec<usize>> {
+        let mut visited = BTreeSet::<usize>::new();
+        visited.insert(root);
+        let mut levels = vec![vec![root]];
+        loop {
+            let mut next = Vec::<usize>::new();
+            for &node in levels.last().unwrap() {
+                for &neighbour in self.neighbours(node) {
+                    if active[neighbour] && visited.insert(neighbour) {
+                        next.push(neighbour);
+                    }
+                }
+            }
+            if next.is_empty() {
+                return levels;
+            }
+            levels.push(next);
+        }
+    }
+
+    /// Find a pseudo-peripheral node in the component of `start` with the algorithm of
+    /// George and Liu.
+    fn pseudo_peripheral_node(&self, start: usize, active: &[bool]) -> usize {
+        let mut node = start;
+        let mut levels = self.level_structure(node, active);
+        loop {
+            let candidate = *levels
+                .last()
+                .unwrap()
+                .iter()
+                .min_by_key(|&&other| self.degree(other))
+                .unwrap();
+            let candidate_levels = self.level_structure(candidate, active);
+            if candidate_levels.len() <= levels.len() {
+                return node;
+            }
+            node = candidate;
+            levels = candidate_levels;
+        }
+    }
+}
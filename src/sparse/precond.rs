@@ -0,0 +1,31 @@
+//! Preconditioners for sparse matrices.
+
+pub mod amg;
+pub mod incomplete_cholesky;
+pub mod incomplete_lu;
+pub mod stationary;
+
+use crate::dense::traits::Shape;
+use crate::dense::types::{RlstError, RlstResult, RlstScalar};
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::tools::sorted_compressed_row;
+use crate::sparse::sparse_mat::SparseIndex;
+
+/// Return the entries of row `row` of `mat` sorted by column index with duplicates summed.
+pub(crate) fn sorted_row<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+    row: usize,
+) -> Vec<(usize, Item)> {
+    sorted_compressed_row(mat.indptr(), mat.indices(), mat.data(), row)
+}
+
+/// Check that `mat` is square and return its dimension.
+pub(crate) fn square_dim<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+) -> RlstResult<usize> {
+    let shape = mat.shape();
+    if shape[0] != shape[1] {
+        return Err(RlstError::MatrixNotSquare(shape[0], shape[1]));
+    }
+    Ok(shape[0])
+}
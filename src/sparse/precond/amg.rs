@@ -0,0 +1,465 @@
+//! Smoothed aggregation algebraic multigrid.
+//!
+//! The hierarchy is built from a CSR matrix `A` level by level. On each level
+//!
+//! 1. the strong connections `|a_ij| >= θ sqrt(|a_ii a_jj|)` are determined,
+//! 2. the nodes are grouped into aggregates of strongly connected nodes,
+//! 3. the tentative prolongator `T` interpolates constants on each aggregate,
+//! 4. the prolongator `P = (I - ω D^{-1} A) T` is obtained by one step of damped Jacobi
+//!    with `ω = 4 / (3 ρ(D^{-1} A))`,
+//! 5. the coarse operator is the Galerkin product `P^H A P`.
+//!
+//! Once the coarse operator is small enough it is factorised with a dense
+//! [LuDecomposition]. A V-cycle applies Jacobi or Gauss-Seidel smoothing on all other
+//! levels.
+
+use rand::SeedableRng;
+
+use crate::dense::base_array::BaseArray;
+use crate::dense::data_container::VectorContainer;
+use crate::dense::linalg::lu::{LuDecomposition, MatrixLuDecomposition};
+use crate::dense::traits::{RawAccess, RawAccessMut, Shape};
+use crate::dense::types::{RlstError, RlstResult, RlstScalar, TransMode};
+use crate::sparse::precond::stationary::{jacobi_step, nonzero_diagonal, sor_step};
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::tools::to_index;
+use crate::sparse::sparse_mat::SparseIndex;
+use crate::{rlst_dynamic_array1, rlst_dynamic_array2};
+
+/// Dense LU decomposition used on the coarsest level.
+pub type CoarseLu<Item> = LuDecomposition<Item, BaseArray<Item, VectorContainer<Item>, 2>>;
+
+/// Smoother used in the V-cycle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AmgSmoother<Real> {
+    /// Damped Jacobi with the given weight
+    Jacobi(Real),
+    /// Gauss-Seidel. Forward sweeps are used for pre-smoothing and backward sweeps for
+    /// post-smoothing, such that the V-cycle is symmetric.
+    GaussSeidel,
+}
+
+/// A level of the multigrid hierarchy other than the coarsest level.
+struct AmgLevel<Item: RlstScalar, I: SparseIndex> {
+    mat: CsrMatrix<Item, I>,
+    diagonal: Vec<Item>,
+    prolongator: CsrMatrix<Item, I>,
+    restriction: CsrMatrix<Item, I>,
+}
+
+/// Builder for a smoothed aggregation multigrid hierarchy.
+pub struct AmgBuilder<'a, Item: RlstScalar, I: SparseIndex = usize> {
+    mat: &'a CsrMatrix<Item, I>,
+    strength_threshold: Item::Real,
+    max_levels: usize,
+    max_coarse_size: usize,
+    smoother: AmgSmoother<Item::Real>,
+    presmooth: usize,
+    postsmooth: usize,
+}
+
+/// A smoothed aggregation multigrid hierarchy.
+pub struct AmgHierarchy<Item: RlstScalar, I: SparseIndex = usize> {
+    levels: Vec<AmgLevel<Item, I>>,
+    coarse_mat: CsrMatrix<Item, I>,
+    coarse_lu: CoarseLu<Item>,
+    smoother: AmgSmoother<Item::Real>,
+    presmooth: usize,
+    postsmooth: usize,
+}
+
+/// Convert an `f64` constant to the real type of `Item`.
+fn real<Item: RlstScalar>(value: f64) -> Item::Real {
+    num::cast::<f64, Item::Real>(value).unwrap()
+}
+
+/// Return the strongly connected neighbours of each node.
+fn strength_of_connection<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+    diagonal: &[Item],
+    threshold: Item::Real,
+) -> Vec<Vec<usize>> {
+    (0..mat.shape()[0])
+        .map(|row| {
+            (mat.indptr()[row].to_usize()..mat.indptr()[1 + row].to_usize())
+                .filter(|&pos| {
+                    let col = mat.indices()[pos].to_usize();
+                    col != row
+                        && mat.data()[pos].abs()
+                            >= threshold
+                                * RlstScalar::sqrt(diagonal[row].abs() * diagonal[col].abs())
+                })
+                .map(|pos| mat.indices()[pos].to_usize())
+                .collect()
+        })
+        .collect()
+}
+
+/// Group the nodes into aggregates. Returns the aggregate of each node and the number of
+/// aggregates.
+fn aggregate(strong: &[Vec<usize>]) -> (Vec<usize>, usize) {
+    let n = strong.len();
+    let mut aggregates = vec![usize::MAX; n];
+    let mut count = 0;
+
+    // Phase 1: nodes whose neighbourhood is not aggregated yet form a new aggregate.
+    for node in 0..n {
+        if aggregates[node] == usize::MAX
+            && strong[node]
+                .iter()
+                .all(|&other| aggregates[other] == usize::MAX)
+        {
+            aggregates[node] = count;
+            for &other in &strong[node] {
+                aggregates[other] = count;
+            }
+            count += 1;
+        }
+    }
+
+    // Phase 2: remaining nodes join an aggregate of a strong neighbour.
+    let phase1 = aggregates.clone();
+    for node in 0..n {
+        if aggregates[node] == usize::MAX {
+            if let Some(&other) = strong[node]
+                .iter()
+                .find(|&&other| phase1[other] != usize::MAX)
+            {
+                aggregates[node] = phase1[other];
+            }
+        }
+    }
+
+    // Phase 3: remaining nodes form aggregates with their unaggregated neighbours.
+    for node in 0..n {
+        if aggregates[node] == usize::MAX {
+            aggregates[node] = count;
+            for &other in &strong[node] {
+                if aggregates[other] == usize::MAX {
+                    aggregates[other] = count;
+                }
+            }
+            count += 1;
+        }
+    }
+
+    (aggregates, count)
+}
+
+/// Estimate the spectral radius of `D^{-1} A` with a few steps of the power iteration.
+fn spectral_radius<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+    diagonal: &[Item],
+) -> Item::Real {
+    let n = mat.shape()[0];
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+    let mut x = (0..n).map(|_| Item::rand(&mut rng)).collect::<Vec<_>>();
+    let mut y = vec![Item::zero(); n];
+    let norm = |v: &[Item]| RlstScalar::sqrt(v.iter().map(|e| e.square()).sum::<Item::Real>());
+
+    let mut rho = <Item::Real as num::Zero>::zero();
+    for _ in 0..15 {
+        let x_norm = norm(&x);
+        if x_norm == <Item::Real as num::Zero>::zero() {
+            break;
+        }
+        x.iter_mut().for_each(|e| *e = e.div_real(x_norm));
+        mat.matmul(Item::one(), &x, Item::zero(), &mut y);
+        for (e, &d) in y.iter_mut().zip(diagonal) {
+            *e /= d;
+        }
+        rho = norm(&y);
+        std::mem::swap(&mut x, &mut y);
+    }
+    rho
+}
+
+/// Return the smoothed prolongator and the number of aggregates.
+fn prolongator<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+    diagonal: &[Item],
+    strength_threshold: Item::Real,
+) -> (CsrMatrix<Item, I>, usize) {
+    let n = mat.shape()[0];
+    let strong = strength_of_connection(mat, diagonal, strength_threshold);
+    let (aggregates, count) = aggregate(&strong);
+
+    // The tentative prolongator interpolates constants, normalised on each aggregate.
+    let mut sizes = vec![0usize; count];
+    for &agg in &aggregates {
+        sizes[agg] += 1;
+    }
+    let tentative = CsrMatrix::new(
+        [n, count],
+        aggregates.iter().map(|&agg| to_index(agg)).collect(),
+        (0..=n).map(to_index).collect(),
+        aggregates
+            .iter()
+            .map(|&agg| Item::from_real(RlstScalar::sqrt(real::<Item>(1.0 / sizes[agg] as f64))))
+            .collect(),
+    );
+
+    let omega = real::<Item>(4.0 / 3.0) / spectral_radius(mat, diagonal);
+    let mut smoothing = mat.matmul_sparse(&tentative);
+    smoothing.scale_rows(
+        &diagonal
+            .iter()
+            .map(|&d| Item::one() / d)
+            .collect::<Vec<_>>(),
+    );
+
+    (
+        tentative.add(&smoothing, Item::one(), -Item::from_real(omega)),
+        count,
+    )
+}
+
+impl<'a, Item: RlstScalar, I: SparseIndex> AmgBuilder<'a, Item, I>
+where
+    CoarseLu<Item>:
+        MatrixLuDecomposition<Item = Item, ArrayImpl = BaseArray<Item, VectorContainer<Item>, 2>>,
+{
+    /// Create a new builder for the hierarchy of a square matrix.
+    pub fn new(mat: &'a CsrMatrix<Item, I>) -> Self {
+        Self {
+            mat,
+            strength_threshold: real::<Item>(0.08),
+            max_levels: 10,
+            max_coarse_size: 100,
+            smoother: AmgSmoother::GaussSeidel,
+            presmooth: 1,
+            postsmooth: 1,
+        }
+    }
+
+    /// Set the threshold `θ` for strong connections
+    pub fn set_strength_threshold(mut self, threshold: Item::Real) -> Self {
+        self.strength_threshold = threshold;
+        self
+    }
+
+    /// Set the maximum number of levels, including the coarsest level
+    pub fn set_max_levels(mut self, max_levels: usize) -> Self {
+        assert!(max_levels > 0, "At least one level is required.");
+        self.max_levels = max_levels;
+        self
+    }
+
+    /// Set the size below which a level is solved with a dense LU decomposition
+    pub fn set_max_coarse_size(mut self, max_coarse_size: usize) -> Self {
+        self.max_coarse_size = max_coarse_size;
+        self
+    }
+
+    /// Set the smoother
+    pub fn set_smoother(mut self, smoother: AmgSmoother<Item::Real>) -> Self {
+        self.smoother = smoother;
+        self
+    }
+
+    /// Set the number of pre- and post-smoothing sweeps
+    pub fn set_sweeps(mut self, presmooth: usize, postsmooth: usize) -> Self {
+        self.presmooth = presmooth;
+        self.postsmooth = postsmooth;
+        self
+    }
+
+    /// Build the hierarchy
+    pub fn build(self) -> RlstResult<AmgHierarchy<Item, I>> {
+        let shape = self.mat.shape();
+        if shape[0] != shape[1] {
+            return Err(RlstError::MatrixNotSquare(shape[0], shape[1]));
+        }
+
+        let mut levels = Vec::<AmgLevel<Item, I>>::new();
+        let mut mat = self.mat.clone();
+
+        while 1 + levels.len() < self.max_levels && mat.shape()[0] > self.max_coarse_size {
+            let diagonal = nonzero_diagonal(&mat)?;
+            let (prolongator, count) = prolongator(&mat, &diagonal, self.strength_threshold);
+            if count == mat.shape()[0] {
+                break;
+            }
+
+            let restriction = prolongator.conj_transpose();
+            let coarse = restriction.matmul_sparse(&mat).matmul_sparse(&prolongator);
+
+            levels.push(AmgLevel {
+                mat: std::mem::replace(&mut mat, coarse),
+                diagonal,
+                prolongator,
+                restriction,
+            });
+        }
+
+        let n = mat.shape()[0];
+        let mut dense = rlst_dynamic_array2!(Item, [n, n]);
+        for row in 0..n {
+            for pos in mat.indptr()[row].to_usize()..mat.indptr()[1 + row].to_usize() {
+                dense[[row, mat.indices()[pos].to_usize()]] += mat.data()[pos];
+            }
+        }
+
+        Ok(AmgHierarchy {
+            levels,
+            coarse_mat: mat,
+            coarse_lu: CoarseLu::<Item>::new(dense)?,
+            smoother: self.smoother,
+            presmooth: self.presmooth,
+            postsmooth: self.postsmooth,
+        })
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> AmgLevel<Item, I> {
+    /// Apply `sweeps` smoothing steps to `A x = b`.
+    fn smooth(
+        &self,
+        smoother: AmgSmoother<Item::Real>,
+        sweeps: usize,
+        forward: bool,
+        b: &[Item],
+        x: &mut [Item],
+    ) {
+        for _ in 0..sweeps {
+            match smoother {
+                AmgSmoother::Jacobi(weight) => jacobi_step(&self.mat, &self.diagonal, weight, b, x),
+                AmgSmoother::GaussSeidel => {
+                    sor_step(&self.mat, &self.diagonal, Item::real(1.0), forward, b, x)
+                }
+            }
+        }
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> AmgHierarchy<Item, I>
+where
+    CoarseLu<Item>:
+        MatrixLuDecomposition<Item = Item, ArrayImpl = BaseArray<Item, VectorContainer<Item>, 2>>,
+{
+    /// Number of levels, including the coarsest level
+    pub fn nlevels(&self) -> usize {
+        1 + self.levels.len()
+    }
+
+    /// Dimension of the matrix on each level, starting with the finest level
+    pub fn level_dims(&self) -> Vec<usize> {
+        self.level_mats().map(|mat| mat.shape()[0]).collect()
+    }
+
+    /// Operator complexity
+    ///
+    /// The number of stored entries of the matrices on all levels divided by the number of
+    /// stored entries of the finest matrix.
+    pub fn operator_complexity(&self) -> f64 {
+        let nelems = self
+            .level_mats()
+            .map(|mat| mat.nelems())
+            .collect::<Vec<_>>();
+        nelems.iter().sum::<usize>() as f64 / nelems[0] as f64
+    }
+
+    /// Grid complexity
+    ///
+    /// The sum of the dimensions on all levels divided by the dimension of the finest
+    /// level.
+    pub fn grid_complexity(&self) -> f64 {
+        let dims = self.level_dims();
+        dims.iter().sum::<usize>() as f64 / dims[0] as f64
+    }
+
+    /// The matrices on all levels, starting with the finest level.
+    fn level_mats(&self) -> impl Iterator<Item = &CsrMatrix<Item, I>> {
+        self.levels
+            .iter()
+            .map(|level| &level.mat)
+            .chain(std::iter::once(&self.coarse_mat))
+    }
+
+    /// Dimension of the finest level
+    pub fn dim(&self) -> usize {
+        self.level_mats().next().unwrap().shape()[0]
+    }
+
+    /// Apply one V-cycle to `A x = rhs`, using `x` as the initial guess.
+    pub fn vcycle(&self, rhs: &[Item], x: &mut [Item]) -> RlstResult<()> {
+        assert_eq!(rhs.len(), self.dim());
+        assert_eq!(x.len(), self.dim());
+        self.vcycle_level(0, rhs, x)
+    }
+
+    fn vcycle_level(&self, index: usize, rhs: &[Item], x: &mut [Item]) -> RlstResult<()> {
+        let Some(level) = self.levels.get(index) else {
+            let mut solution = rlst_dynamic_array1!(Item, [rhs.len()]);
+            solution.data_mut().copy_from_slice(rhs);
+            self.coarse_lu
+                .solve_vec(TransMode::NoTrans, solution.view_mut())?;
+            x.copy_from_slice(solution.data());
+            return Ok(());
+        };
+
+        level.smooth(self.smoother, self.presmooth, true, rhs, x);
+
+        let mut residual = rhs.to_vec();
+        level
+            .mat
+            .matmul(-Item::one(), x, Item::one(), &mut residual);
+
+        let coarse_dim = level.restriction.shape()[0];
+        let mut coarse_rhs = vec![Item::zero(); coarse_dim];
+        let mut correction = vec![Item::zero(); coarse_dim];
+        level
+            .restriction
+            .matmul(Item::one(), &residual, Item::zero(), &mut coarse_rhs);
+        self.vcycle_level(1 + index, &coarse_rhs, &mut correction)?;
+        level
+            .prolongator
+            .matmul(Item::one(), &correction, Item::one(), x);
+
+        level.smooth(self.smoother, self.postsmooth, false, rhs, x);
+        Ok(())
+    }
+
+    /// Solve `A x = rhs` with V-cycles, using `x` as the initial guess.
+    ///
+    /// Iterates until the residual norm is below `tol` times the norm of `rhs` or
+    /// `max_iter` cycles are reached. Returns the final relative residual.
+    pub fn solve(
+        &self,
+        rhs: &[Item],
+        x: &mut [Item],
+        tol: Item::Real,
+        max_iter: usize,
+    ) -> RlstResult<Item::Real> {
+        let mat = self.level_mats().next().unwrap();
+        let norm = |v: &[Item]| RlstScalar::sqrt(v.iter().map(|e| e.square()).sum::<Item::Real>());
+        let rhs_norm = norm(rhs);
+        let mut residual = rhs.to_vec();
+
+        for _ in 0..max_iter {
+            residual.copy_from_slice(rhs);
+            mat.matmul(-Item::one(), x, Item::one(), &mut residual);
+            if norm(&residual) <= tol * rhs_norm {
+                break;
+            }
+            self.vcycle(rhs, x)?;
+        }
+
+        residual.copy_from_slice(rhs);
+        mat.matmul(-Item::one(), x, Item::one(), &mut residual);
+        Ok(norm(&residual) / rhs_norm)
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CsrMatrix<Item, I>
+where
+    CoarseLu<Item>:
+        MatrixLuDecomposition<Item = Item, ArrayImpl = BaseArray<Item, VectorContainer<Item>, 2>>,
+{
+    /// Build a smoothed aggregation multigrid hierarchy with default parameters.
+    ///
+    /// Use [AmgBuilder] to set the parameters.
+    pub fn amg(&self) -> RlstResult<AmgHierarchy<Item, I>> {
+        AmgBuilder::new(self).build()
+    }
+}
@@ -0,0 +1,146 @@
+//! Incomplete Cholesky factorisation of Hermitian CSR matrices.
+//!
+//! IC(0) computes `A ≈ L L^H` where `L` has the sparsity pattern of the lower triangle of
+//! `A`. Only the lower triangle of the matrix is accessed. The matrix is assumed to be
+//! Hermitian, i.e. symmetric for real scalar types.
+
+use crate::dense::types::{RlstError, RlstResult, RlstScalar};
+use crate::sparse::precond::{sorted_row, square_dim};
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::SparseIndex;
+
+/// An incomplete Cholesky factorisation.
+#[derive(Clone)]
+pub struct IncompleteCholesky<Item: RlstScalar> {
+    n: usize,
+    // Strictly lower triangular part of `L` in CSR format with sorted column indices.
+    lower: CsrMatrix<Item>,
+    diagonal: Vec<Item::Real>,
+}
+
+impl<Item: RlstScalar> IncompleteCholesky<Item> {
+    /// Compute the IC(0) factorisation.
+    ///
+    /// Returns [RlstError::FactorisationBreakdown] if a pivot is not positive, which can
+    /// happen for Hermitian positive definite matrices that are not M-matrices.
+    pub fn ic0<I: SparseIndex>(mat: &CsrMatrix<Item, I>) -> RlstResult<Self> {
+        let n = square_dim(mat)?;
+
+        let mut indptr = Vec::<usize>::with_capacity(1 + n);
+        let mut indices = Vec::<usize>::new();
+        let mut data = Vec::<Item>::new();
+        let mut diagonal = Vec::<Item::Real>::with_capacity(n);
+
+        // `work[col]` holds the computed entries of the current row of `L`.
+        let mut work = vec![Item::zero(); n];
+
+        indptr.push(0);
+        for row in 0..n {
+            let entries = sorted_row(mat, row);
+            let mut pivot = <Item::Real as num::Zero>::zero();
+
+            for &(col, value) in entries.iter().take_while(|&&(col, _)| col <= row) {
+                if col == row {
+                    pivot = value.re();
+                    continue;
+                }
+                // The entries of the current row of `L` left of `col` are already in
+                // `work` and row `col` of `L` only has entries left of `col`.
+                let mut sum = value;
+                for pos in indptr[col]..indptr[1 + col] {
+                    sum -= work[indices[pos]] * data[pos].conj();
+                }
+                let entry = sum.div_real(diagonal[col]);
+                work[col] = entry;
+                indices.push(col);
+                data.push(entry);
+            }
+
+            let start = indptr[row];
+            for (&col, &value) in indices[start..].iter().zip(data[start..].iter()) {
+                pivot -= value.square();
+                work[col] = Item::zero();
+            }
+
+            if pivot <= <Item::Real as num::Zero>::zero() {
+                return Err(RlstError::FactorisationBreakdown {
+                    pivot: row,
+                    reason: "non-positive pivot".to_string(),
+                });
+            }
+
+            diagonal.push(RlstScalar::sqrt(pivot));
+            indptr.push(indices.len());
+        }
+
+        Ok(Self {
+            n,
+            lower: CsrMatrix::new([n, n], indices, indptr, data),
+            diagonal,
+        })
+    }
+
+    /// Dimension of the factorised matrix
+    pub fn dim(&self) -> usize {
+        self.n
+    }
+
+    /// Number of stored entries of `L`, including the diagonal
+    pub fn nelems(&self) -> usize {
+        self.lower.nelems() + self.n
+    }
+
+    /// The lower triangular factor `L`
+    pub fn l_factor(&self) -> CsrMatrix<Item> {
+        let mut indptr = Vec::<usize>::with_capacity(1 + self.n);
+        let mut indices = Vec::<usize>::with_capacity(self.nelems());
+        let mut data = Vec::<Item>::with_capacity(self.nelems());
+
+        indptr.push(0);
+        for (row, &value) in self.diagonal.iter().enumerate() {
+            let range = self.lower.indptr()[row]..self.lower.indptr()[1 + row];
+            indices.extend_from_slice(&self.lower.indices()[range.clone()]);
+            data.extend_from_slice(&self.lower.data()[range]);
+            indices.push(row);
+            data.push(Item::from_real(value));
+            indptr.push(indices.len());
+        }
+
+        CsrMatrix::new([self.n, self.n], indices, indptr, data)
+    }
+
+    /// Solve `L L^H x = rhs`.
+    pub fn solve(&self, rhs: &[Item], x: &mut [Item]) {
+        assert_eq!(rhs.len(), self.n);
+        assert_eq!(x.len(), self.n);
+
+        let indptr = self.lower.indptr();
+        let indices = self.lower.indices();
+        let data = self.lower.data();
+
+        // Forward substitution with `L`.
+        for row in 0..self.n {
+            let mut value = rhs[row];
+            for pos in indptr[row]..indptr[1 + row] {
+                value -= data[pos] * x[indices[pos]];
+            }
+            x[row] = value.div_real(self.diagonal[row]);
+        }
+
+        // Backward substitution with `L^H`, using the rows of `L` as columns of `L^H`.
+        for row in (0..self.n).rev() {
+            let value = x[row].div_real(self.diagonal[row]);
+            x[row] = value;
+            for pos in indptr[row]..indptr[1 + row] {
+                x[indices[pos]] -= data[pos].conj() * value;
+            }
+        }
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CsrMatrix<Item, I> {
+    /// Compute the IC(0) factorisation of the matrix.
+    pub fn ic0(&self) -> RlstResult<IncompleteCholesky<Item>> {
+        IncompleteCholesky::ic0(self)
+    }
+}
@@ -0,0 +1,332 @@
+//! Incomplete LU factorisations of CSR matrices.
+//!
+//! An incomplete LU factorisation computes `A ≈ L U` with a unit lower triangular `L` and
+//! an upper triangular `U` that are sparser than the exact factors. ILU(0) keeps the
+//! sparsity pattern of `A`. ILUT(τ, p) allows fill-in, but drops entries that are smaller
+//! than `τ` times the norm of their row and keeps at most `p` entries in each row of `L`
+//! and `U`.
+
+use std::collections::BTreeSet;
+
+use crate::dense::types::{RlstError, RlstResult, RlstScalar};
+use crate::sparse::precond::{sorted_row, square_dim};
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::SparseIndex;
+
+/// An incomplete LU factorisation.
+#[derive(Clone)]
+pub struct IncompleteLu<Item: RlstScalar> {
+    n: usize,
+    // Strictly lower triangular part of `L` and strictly upper triangular part of `U` in
+    // CSR format with sorted column indices.
+    lower: CsrMatrix<Item>,
+    upper: CsrMatrix<Item>,
+    diagonal: Vec<Item>,
+}
+
+/// Triangular factors that are assembled row by row.
+struct RowFactors<Item: RlstScalar> {
+    lower: (Vec<usize>, Vec<usize>, Vec<Item>),
+    upper: (Vec<usize>, Vec<usize>, Vec<Item>),
+    diagonal: Vec<Item>,
+}
+
+impl<Item: RlstScalar> RowFactors<Item> {
+    fn new(n: usize) -> Self {
+        Self {
+            lower: (vec![0], Vec::new(), Vec::new()),
+            upper: (vec![0], Vec::new(), Vec::new()),
+            diagonal: Vec::with_capacity(n),
+        }
+    }
+
+    /// Upper triangular entries of the finished row `row`.
+    fn upper_row(&self, row: usize) -> impl Iterator<Item = (usize, Item)> + '_ {
+        let (indptr, indices, data) = &self.upper;
+        let range = indptr[row]..indptr[1 + row];
+        indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(data[range].iter().copied())
+    }
+
+    /// Append a row. The entries must be sorted by column index.
+    fn push_row(
+        &mut self,
+        row: usize,
+        lower: &[(usize, Item)],
+        diagonal: Item,
+        upper: &[(usize, Item)],
+    ) -> RlstResult<()> {
+        if diagonal == Item::zero() {
+            return Err(RlstError::FactorisationBreakdown {
+                pivot: row,
+                reason: "zero pivot".to_string(),
+            });
+        }
+        for (factor, entries) in [(&mut self.lower, lower), (&mut self.upper, upper)] {
+            for &(col, value) in entries {
+                factor.1.push(col);
+                factor.2.push(value);
+            }
+            factor.0.push(factor.1.len());
+        }
+        self.diagonal.push(diagonal);
+        Ok(())
+    }
+
+    fn finish(self, n: usize) -> IncompleteLu<Item> {
+        let (l_indptr, l_indices, l_data) = self.lower;
+        let (u_indptr, u_indices, u_data) = self.upper;
+        IncompleteLu {
+            n,
+            lower: CsrMatrix::new([n, n], l_indices, l_indptr, l_data),
+            upper: CsrMatrix::new([n, n], u_indices, u_indptr, u_data),
+            diagonal: self.diagonal,
+        }
+    }
+}
+
+/// Keep the `fill` entries of largest absolute value that are not smaller than
+/// `threshold`, sorted by column index.
+fn keep_largest<Item: RlstScalar>(
+    mut entries: Vec<(usize, Item)>,
+    threshold: Item::Real,
+    fill: usize,
+) -> Vec<(usize, Item)> {
+    entries.retain(|&(_, value)| value.abs() >= threshold);
+    if entries.len() > fill {
+        entries.select_nth_unstable_by(fill, |a, b| b.1.abs().partial_cmp(&a.1.abs()).unwrap());
+        entries.truncate(fill);
+    }
+    entries.sort_unstable_by_key(|&(col, _)| col);
+    entries
+}
+
+impl<Item: RlstScalar> IncompleteLu<Item> {
+    /// Compute the ILU(0) factorisation, which keeps the sparsity pattern of `mat`.
+    ///
+    /// Returns [RlstError::FactorisationBreakdown] if a pivot is zero, in particular if a
+    /// diagonal entry is not stored.
+    pub fn ilu0<I: SparseIndex>(mat: &CsrMatrix<Item, I>) -> RlstResult<Self> {
+        let n = square_dim(mat)?;
+        let mut factors = RowFactors::new(n);
+
+        // `work[col]` is the current value of entry `col` of the row and `in_pattern[col]`
+        // marks the pattern of the row.
+        let mut work = vec![Item::zero(); n];
+        let mut in_pattern = vec![false; n];
+
+        for row in 0..n {
+            let entries = sorted_row(mat, row);
+            for &(col, value) in &entries {
+                work[col] = value;
+                in_pattern[col] = true;
+            }
+
+            for &(col, _) in entries.iter().take_while(|&&(col, _)| col < row) {
+                let factor = work[col] / factors.diagonal[col];
+                work[col] = factor;
+                for (upper_col, value) in factors.upper_row(col) {
+                    if in_pattern[upper_col] {
+                        work[upper_col] -= factor * value;
+                    }
+                }
+            }
+
+            let lower = entries
+                .iter()
+                .filter(|&&(col, _)| col < row)
+                .map(|&(col, _)| (col, work[col]))
+                .collect::<Vec<_>>();
+            let upper = entries
+                .iter()
+                .filter(|&&(col, _)| col > row)
+                .map(|&(col, _)| (col, work[col]))
+                .collect::<Vec<_>>();
+            let diagonal = if in_pattern[row] {
+                work[row]
+            } else {
+                Item::zero()
+            };
+
+            for &(col, _) in &entries {
+                work[col] = Item::zero();
+                in_pattern[col] = false;
+            }
+
+            factors.push_row(row, &lower, diagonal, &upper)?;
+        }
+
+        Ok(factors.finish(n))
+    }
+
+    /// Compute the ILUT(τ, p) factorisation.
+    ///
+    /// Entries of row `i` whose absolute value is smaller than `drop_tol` times the 2-norm
+    /// of row `i` of `mat` are dropped. Of the remaining entries, at most `fill` entries of
+    /// largest absolute value are kept in each row of `L` and of `U`, in addition to the
+    /// diagonal.
+    ///
+    /// Returns [RlstError::FactorisationBreakdown] if a pivot is zero.
+    pub fn ilut<I: SparseIndex>(
+        mat: &CsrMatrix<Item, I>,
+        drop_tol: Item::Real,
+        fill: usize,
+    ) -> RlstResult<Self> {
+        let n = square_dim(mat)?;
+        let mut factors = RowFactors::new(n);
+
+        let mut work = vec![Item::zero(); n];
+        let mut in_pattern = vec![false; n];
+        let mut pattern = Vec::<usize>::new();
+        // Columns left of the diagonal that still need to be eliminated.
+        let mut pending = BTreeSet::<usize>::new();
+
+        for row in 0..n {
+            let entries = sorted_row(mat, row);
+            let mut norm = <Item::Real as num::Zero>::zero();
+            for &(col, value) in &entries {
+                norm += value.square();
+                work[col] = value;
+                if !in_pattern[col] {
+                    in_pattern[col] = true;
+                    pattern.push(col);
+                    if col < row {
+                        pending.insert(col);
+                    }
+                }
+            }
+            let threshold = drop_tol * RlstScalar::sqrt(norm);
+
+            let mut lower = Vec::<(usize, Item)>::new();
+            while let Some(col) = pending.pop_first() {
+                let factor = work[col] / factors.diagonal[col];
+                work[col] = Item::zero();
+                if factor.abs() < threshold {
+                    continue;
+                }
+                lower.push((col, factor));
+                for (upper_col, value) in factors.upper_row(col) {
+                    if !in_pattern[upper_col] {
+                        in_pattern[upper_col] = true;
+                        pattern.push(upper_col);
+                        if upper_col < row {
+                            pending.insert(upper_col);
+                        }
+                    }
+                    work[upper_col] -= factor * value;
+                }
+            }
+
+            let upper = pattern
+                .iter()
+                .filter(|&&col| col > row)
+                .map(|&col| (col, work[col]))
+                .collect::<Vec<_>>();
+            let diagonal = work[row];
+
+            for &col in &pattern {
+                work[col] = Item::zero();
+                in_pattern[col] = false;
+            }
+            pattern.clear();
+
+            factors.push_row(
+                row,
+                &keep_largest(lower, threshold, fill),
+                diagonal,
+                &keep_largest(upper, threshold, fill),
+            )?;
+        }
+
+        Ok(factors.finish(n))
+    }
+
+    /// Dimension of the factorised matrix
+    pub fn dim(&self) -> usize {
+        self.n
+    }
+
+    /// Number of stored entries of `L` and `U`, including the diagonal of `U`
+    pub fn nelems(&self) -> usize {
+        self.lower.nelems() + self.upper.nelems() + self.n
+    }
+
+    /// The unit lower triangular factor `L`
+    pub fn l_factor(&self) -> CsrMatrix<Item> {
+        self.assemble(&self.lower, &vec![Item::one(); self.n], true)
+    }
+
+    /// The upper triangular factor `U`
+    pub fn u_factor(&self) -> CsrMatrix<Item> {
+        self.assemble(&self.upper, &self.diagonal, false)
+    }
+
+    /// Add a diagonal to a strictly triangular factor.
+    fn assemble(
+        &self,
+        strict: &CsrMatrix<Item>,
+        diagonal: &[Item],
+        lower: bool,
+    ) -> CsrMatrix<Item> {
+        let mut indptr = Vec::<usize>::with_capacity(1 + self.n);
+        let mut indices = Vec::<usize>::with_capacity(strict.nelems() + self.n);
+        let mut data = Vec::<Item>::with_capacity(strict.nelems() + self.n);
+
+        indptr.push(0);
+        for (row, &value) in diagonal.iter().enumerate() {
+            let range = strict.indptr()[row]..strict.indptr()[1 + row];
+            if !lower {
+                indices.push(row);
+                data.push(value);
+            }
+            indices.extend_from_slice(&strict.indices()[range.clone()]);
+            data.extend_from_slice(&strict.data()[range]);
+            if lower {
+                indices.push(row);
+                data.push(value);
+            }
+            indptr.push(indices.len());
+        }
+
+        CsrMatrix::new([self.n, self.n], indices, indptr, data)
+    }
+
+    /// Solve `L U x = rhs`.
+    pub fn solve(&self, rhs: &[Item], x: &mut [Item]) {
+        assert_eq!(rhs.len(), self.n);
+        assert_eq!(x.len(), self.n);
+
+        // Forward substitution with `L`.
+        for row in 0..self.n {
+            let mut value = rhs[row];
+            for pos in self.lower.indptr()[row]..self.lower.indptr()[1 + row] {
+                value -= self.lower.data()[pos] * x[self.lower.indices()[pos]];
+            }
+            x[row] = value;
+        }
+
+        // Backward substitution with `U`.
+        for row in (0..self.n).rev() {
+            let mut value = x[row];
+            for pos in self.upper.indptr()[row]..self.upper.indptr()[1 + row] {
+                value -= self.upper.data()[pos] * x[self.upper.indices()[pos]];
+            }
+            x[row] = value / self.diagonal[row];
+        }
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CsrMatrix<Item, I> {
+    /// Compute the ILU(0) factorisation of the matrix.
+    pub fn ilu0(&self) -> RlstResult<IncompleteLu<Item>> {
+        IncompleteLu::ilu0(self)
+    }
+
+    /// Compute the ILUT(τ, p) factorisation of the matrix with drop tolerance `drop_tol`
+    /// and at most `fill` entries in each row of `L` and `U`.
+    pub fn ilut(&self, drop_tol: Item::Real, fill: usize) -> RlstResult<IncompleteLu<Item>> {
+        IncompleteLu::ilut(self, drop_tol, fill)
+    }
+}
@@ -0,0 +1,421 @@
+//! Stationary iterative methods for CSR matrices.
+//!
+//! The methods split `A = D + L + U` into its diagonal, strictly lower and strictly upper
+//! parts. Jacobi and SOR update the iterate with the residual scaled by the diagonal.
+//! Chebyshev iteration accelerates Jacobi with Chebyshev polynomials on an interval that
+//! contains the spectrum of `D^{-1} A`. All methods can be used as standalone solvers and
+//! as smoothers or preconditioners with a fixed number of sweeps.
+
+use rand::SeedableRng;
+
+use crate::dense::traits::Shape;
+use crate::dense::types::{RlstError, RlstResult, RlstScalar};
+use crate::sparse::precond::square_dim;
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::tools::compressed_diagonal;
+use crate::sparse::sparse_mat::SparseIndex;
+
+/// Order in which the rows are updated by Gauss-Seidel and SOR.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SweepDirection {
+    /// Rows in increasing order
+    Forward,
+    /// Rows in decreasing order
+    Backward,
+    /// A forward sweep followed by a backward sweep
+    Symmetric,
+}
+
+/// A stationary iterative method.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StationaryMethod<Real> {
+    /// Jacobi with the given weight
+    Jacobi(Real),
+    /// Successive over-relaxation with the given weight
+    Sor(Real, SweepDirection),
+    /// Chebyshev iteration of the given degree for the spectrum of `D^{-1} A` in the
+    /// interval `[lower, upper]`
+    Chebyshev {
+        /// Number of matrix-vector products per iteration
+        degree: usize,
+        /// Lower bound of the interval
+        lower: Real,
+        /// Upper bound of the interval
+        upper: Real,
+    },
+}
+
+impl<Real: num::Float> StationaryMethod<Real> {
+    /// Jacobi
+    pub fn jacobi() -> Self {
+        Self::Jacobi(Real::one())
+    }
+
+    /// Weighted Jacobi
+    pub fn weighted_jacobi(weight: Real) -> Self {
+        Self::Jacobi(weight)
+    }
+
+    /// Gauss-Seidel
+    pub fn gauss_seidel(direction: SweepDirection) -> Self {
+        Self::Sor(Real::one(), direction)
+    }
+
+    /// Successive over-relaxation (SOR) with forward sweeps
+    pub fn sor(weight: Real) -> Self {
+        Self::Sor(weight, SweepDirection::Forward)
+    }
+
+    /// Symmetric successive over-relaxation (SSOR)
+    pub fn ssor(weight: Real) -> Self {
+        Self::Sor(weight, SweepDirection::Symmetric)
+    }
+
+    /// Chebyshev iteration
+    pub fn chebyshev(degree: usize, lower: Real, upper: Real) -> Self {
+        assert!(degree > 0, "The degree must be positive.");
+        Self::Chebyshev {
+            degree,
+            lower,
+            upper,
+        }
+    }
+}
+
+/// Apply one step of weighted Jacobi to `A x = b`.
+pub(crate) fn jacobi_step<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+    diagonal: &[Item],
+    weight: Item::Real,
+    b: &[Item],
+    x: &mut [Item],
+) {
+    let mut residual = b.to_vec();
+    mat.matmul(-Item::one(), x, Item::one(), &mut residual);
+    for ((elem, &res), &diag) in x.iter_mut().zip(residual.iter()).zip(diagonal) {
+        *elem += (res / diag).mul_real(weight);
+    }
+}
+
+/// Apply one SOR sweep to `A x = b`. The rows are updated in increasing order if
+/// `forward` is true and in decreasing order otherwise.
+pub(crate) fn sor_step<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+    diagonal: &[Item],
+    weight: Item::Real,
+    forward: bool,
+    b: &[Item],
+    x: &mut [Item],
+) {
+    let n = b.len();
+    for step in 0..n {
+        let row = if forward { step } else { n - 1 - step };
+        let mut value = b[row];
+        for pos in mat.indptr()[row].to_usize()..mat.indptr()[1 + row].to_usize() {
+            value -= mat.data()[pos] * x[mat.indices()[pos].to_usize()];
+        }
+        x[row] += (value / diagonal[row]).mul_real(weight);
+    }
+}
+
+/// Return the diagonal of `mat` or an error if a diagonal entry is zero.
+pub(crate) fn nonzero_diagonal<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+) -> RlstResult<Vec<Item>> {
+    let diagonal = compressed_diagonal(mat.shape()[0], mat.indptr(), mat.indices(), mat.data());
+    match diagonal.iter().position(|&d| d == Item::zero()) {
+        Some(row) => Err(RlstError::FactorisationBreakdown {
+            pivot: row,
+            reason: "zero diagonal entry".to_string(),
+        }),
+        None => Ok(diagonal),
+    }
+}
+
+/// Euclidean norm of a vector.
+fn norm<Item: RlstScalar>(vec: &[Item]) -> Item::Real {
+    RlstScalar::sqrt(vec.iter().map(|elem| elem.square()).sum::<Item::Real>())
+}
+
+/// Return the number of eigenvalues of the symmetric tridiagonal matrix with diagonal
+/// `alpha` and off-diagonal `beta` that are smaller than `shift`.
+fn sturm_count<Real: num::Float>(alpha: &[Real], beta: &[Real], shift: Real) -> usize {
+    let mut count = 0;
+    let mut q = Real::one();
+    for (index, &a) in alpha.iter().enumerate() {
+        let b2 = if index == 0 {
+            Real::zero()
+        } else {
+            beta[index - 1] * beta[index - 1]
+        };
+        q = a - shift - b2 / q;
+        if q == Real::zero() {
+            q = Real::epsilon() * (Real::one() + shift.abs());
+        }
+        if q < Real::zero() {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Estimate the smallest and largest eigenvalue of `D^{-1} A` for a Hermitian matrix `A`
+/// with positive diagonal.
+///
+/// The estimates are the extreme Ritz values after `steps` Lanczos steps for the
+/// similar matrix `D^{-1/2} A D^{-1/2}`. The smallest Ritz value is an upper bound and the
+/// largest Ritz value a lower bound of the corresponding eigenvalue.
+pub fn jacobi_eigenvalue_bounds<Item: RlstScalar, I: SparseIndex>(
+    mat: &CsrMatrix<Item, I>,
+    steps: usize,
+) -> RlstResult<(Item::Real, Item::Real)> {
+    let n = square_dim(mat)?;
+    assert!(steps > 0, "At least one Lanczos step is required.");
+    let scaling = nonzero_diagonal(mat)?
+        .iter()
+        .map(|d| Item::one() / Item::from_real(RlstScalar::sqrt(d.re())))
+        .collect::<Vec<_>>();
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+    let mut v = (0..n).map(|_| Item::rand(&mut rng)).collect::<Vec<_>>();
+    let v_norm = norm(&v);
+    v.iter_mut().for_each(|elem| *elem = elem.div_real(v_norm));
+    let mut v_previous = vec![Item::zero(); n];
+    let mut w = vec![Item::zero(); n];
+    let mut scaled = vec![Item::zero(); n];
+
+    let mut alpha = Vec::<Item::Real>::with_capacity(steps);
+    let mut beta = Vec::<Item::Real>::with_capacity(steps);
+
+    for _ in 0..steps.min(n) {
+        for ((s, &elem), &scale) in scaled.iter_mut().zip(v.iter()).zip(scaling.iter()) {
+            *s = elem * scale;
+        }
+        mat.matmul(Item::one(), &scaled, Item::zero(), &mut w);
+        for (elem, &scale) in w.iter_mut().zip(scaling.iter()) {
+            *elem *= scale;
+        }
+        if let Some(&b) = beta.last() {
+            for (elem, &previous) in w.iter_mut().zip(v_previous.iter()) {
+                *elem -= previous.mul_real(b);
+            }
+        }
+        let a = w
+            .iter()
+            .zip(v.iter())
+            .map(|(&elem, &other)| other.conj() * elem)
+            .sum::<Item>()
+            .re();
+        for (elem, &other) in w.iter_mut().zip(v.iter()) {
+            *elem -= other.mul_real(a);
+        }
+        alpha.push(a);
+
+        let b = norm(&w);
+        if b <= Item::real(1E-12) * RlstScalar::abs(a) {
+            break;
+        }
+        beta.push(b);
+        std::mem::swap(&mut v_previous, &mut v);
+        for (elem, &next) in v.iter_mut().zip(w.iter()) {
+            *elem = next.div_real(b);
+        }
+    }
+    beta.truncate(alpha.len() - 1);
+
+    // Gershgorin bounds for the tridiagonal matrix, refined by bisection.
+    let mut lower = alpha[0];
+    let mut upper = alpha[0];
+    for (index, &a) in alpha.iter().enumerate() {
+        let radius = if index > 0 {
+            beta[index - 1]
+        } else {
+            Item::real(0.0)
+        } + beta.get(index).copied().unwrap_or(Item::real(0.0));
+        lower = num::Float::min(lower, a - radius);
+        upper = num::Float::max(upper, a + radius);
+    }
+
+    let bisect = |target: usize| {
+        let (mut left, mut right) = (lower, upper);
+        for _ in 0..100 {
+            let middle = (left + right) / Item::real(2.0);
+            if sturm_count(&alpha, &beta, middle) > target {
+                right = middle;
+            } else {
+                left = middle;
+            }
+        }
+        (left + right) / Item::real(2.0)
+    };
+
+    Ok((bisect(0), bisect(alpha.len() - 1)))
+}
+
+/// A stationary iterative method for a CSR matrix.
+pub struct StationaryIteration<'a, Item: RlstScalar, I: SparseIndex = usize> {
+    mat: &'a CsrMatrix<Item, I>,
+    diagonal: Vec<Item>,
+    method: StationaryMethod<Item::Real>,
+}
+
+impl<'a, Item: RlstScalar, I: SparseIndex> StationaryIteration<'a, Item, I> {
+    /// Create a new stationary iteration for a square matrix with nonzero diagonal.
+    ///
+    /// Returns [RlstError::FactorisationBreakdown] if a diagonal entry is zero.
+    pub fn new(
+        mat: &'a CsrMatrix<Item, I>,
+        method: StationaryMethod<Item::Real>,
+    ) -> RlstResult<Self> {
+        square_dim(mat)?;
+        Ok(Self {
+            mat,
+            diagonal: nonzero_diagonal(mat)?,
+            method,
+        })
+    }
+
+    /// Create a Chebyshev smoother for a Hermitian matrix with positive diagonal.
+    ///
+    /// The largest eigenvalue `λ` of `D^{-1} A` is estimated with `lanczos_steps` Lanczos
+    /// steps. The smoother targets the interval `[1.1 λ / 30, 1.1 λ]`, which contains the
+    /// upper part of the spectrum.
+    pub fn chebyshev_smoother(
+        mat: &'a CsrMatrix<Item, I>,
+        degree: usize,
+        lanczos_steps: usize,
+    ) -> RlstResult<Self> {
+        let (_, largest) = jacobi_eigenvalue_bounds(mat, lanczos_steps)?;
+        let upper = Item::real(1.1) * largest;
+        Self::new(
+            mat,
+            StationaryMethod::chebyshev(degree, upper / Item::real(30.0), upper),
+        )
+    }
+
+    /// Dimension of the matrix
+    pub fn dim(&self) -> usize {
+        self.diagonal.len()
+    }
+
+    /// The iterative method
+    pub fn method(&self) -> StationaryMethod<Item::Real> {
+        self.method
+    }
+
+    /// Apply one iteration to `A x = b`, using `x` as the current iterate.
+    pub fn iterate(&self, b: &[Item], x: &mut [Item]) {
+        assert_eq!(b.len(), self.dim());
+        assert_eq!(x.len(), self.dim());
+
+        match self.method {
+            StationaryMethod::Jacobi(weight) => {
+                jacobi_step(self.mat, &self.diagonal, weight, b, x);
+            }
+            StationaryMethod::Sor(weight, direction) => {
+                if direction != SweepDirection::Backward {
+                    sor_step(self.mat, &self.diagonal, weight, true, b, x);
+                }
+                if direction != SweepDirection::Forward {
+                    sor_step(self.mat, &self.diagonal, weight, false, b, x);
+                }
+            }
+            StationaryMethod::Chebyshev {
+                degree,
+                lower,
+                upper,
+            } => self.chebyshev_step(degree, lower, upper, b, x),
+        }
+    }
+
+    /// Apply `degree` steps of Chebyshev acceleration for Jacobi.
+    fn chebyshev_step(
+        &self,
+        degree: usize,
+        lower: Item::Real,
+        upper: Item::Real,
+        b: &[Item],
+        x: &mut [Item],
+    ) {
+        let theta = (upper + lower) / Item::real(2.0);
+        let delta = (upper - lower) / Item::real(2.0);
+        let sigma = theta / delta;
+        let mut rho = Item::real(1.0) / sigma;
+
+        // Preconditioned residual `D^{-1} (b - A x)` and update direction.
+        let mut residual = b.to_vec();
+        self.mat.matmul(-Item::one(), x, Item::one(), &mut residual);
+        for (elem, &diag) in residual.iter_mut().zip(self.diagonal.iter()) {
+            *elem /= diag;
+        }
+        let mut direction = residual
+            .iter()
+            .map(|elem| elem.div_real(theta))
+            .collect::<Vec<_>>();
+        let mut product = vec![Item::zero(); self.dim()];
+
+        for step in 0..degree {
+            for (elem, &update) in x.iter_mut().zip(direction.iter()) {
+                *elem += update;
+            }
+            if 1 + step == degree {
+                break;
+            }
+
+            self.mat
+                .matmul(Item::one(), &direction, Item::zero(), &mut product);
+            for ((elem, &prod), &diag) in residual
+                .iter_mut()
+                .zip(product.iter())
+                .zip(self.diagonal.iter())
+            {
+                *elem -= prod / diag;
+            }
+
+            let rho_next = Item::real(1.0) / (Item::real(2.0) * sigma - rho);
+            for (elem, &res) in direction.iter_mut().zip(residual.iter()) {
+                *elem = elem.mul_real(rho_next * rho)
+                    + res.mul_real(Item::real(2.0) * rho_next / delta);
+            }
+            rho = rho_next;
+        }
+    }
+
+    /// Apply `sweeps` iterations to `A x = b`, using `x` as the initial guess.
+    pub fn smooth(&self, b: &[Item], x: &mut [Item], sweeps: usize) {
+        for _ in 0..sweeps {
+            self.iterate(b, x);
+        }
+    }
+
+    /// Solve `A x = b`, using `x` as the initial guess.
+    ///
+    /// Iterates until the residual norm is below `tol` times the norm of `b` or
+    /// `max_iter` iterations are reached. Returns the number of iterations and the final
+    /// relative residual.
+    pub fn solve(
+        &self,
+        b: &[Item],
+        x: &mut [Item],
+        tol: Item::Real,
+        max_iter: usize,
+    ) -> (usize, Item::Real) {
+        let b_norm = norm(b);
+        let mut residual = b.to_vec();
+        let mut relative_residual = |x: &[Item]| {
+            residual.copy_from_slice(b);
+            self.mat.matmul(-Item::one(), x, Item::one(), &mut residual);
+            norm(&residual) / b_norm
+        };
+
+        let mut rel_res = relative_residual(x);
+        for iteration in 0..max_iter {
+            if rel_res <= tol {
+                return (iteration, rel_res);
+            }
+            self.iterate(b, x);
+            rel_res = relative_residual(x);
+        }
+        (max_iter, rel_res)
+    }
+}
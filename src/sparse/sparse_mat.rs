@@ -1,10 +1,15 @@
 //! Sparse matrices
+pub mod bsr_mat;
+pub mod cholesky;
 pub mod csc_mat;
 pub mod csr_mat;
 
 #[cfg(feature = "mpi")]
 pub mod mpi_csr_mat;
 
+pub mod sell_mat;
+pub mod sparse_builder;
+pub mod spgemm;
 pub mod tools;
 #[cfg(feature = "suitesparse")]
 pub mod umfpack;
@@ -17,3 +22,71 @@ pub enum SparseMatType {
     /// CSC matrix
     Csc,
 }
+
+/// Integer type of the indices of compressed sparse matrices.
+///
+/// Using `u32` or `i32` instead of the default `usize` halves the memory of the index
+/// arrays on 64-bit platforms. `i32` is the index type used by UMFPACK.
+pub trait SparseIndex:
+    Copy + Default + Ord + Send + Sync + std::fmt::Debug + std::fmt::Display + 'static
+{
+    /// Convert to `usize`.
+    ///
+    /// Negative values convert to indices that are out of bounds for any matrix.
+    fn to_usize(self) -> usize;
+
+    /// Convert from `usize` or return `None` if `value` is not representable.
+    fn from_usize(value: usize) -> Option<Self>;
+
+    /// Return the slice as `&[i32]` without copying if the index type is `i32`.
+    fn as_i32_slice(slice: &[Self]) -> Option<&[i32]> {
+        let _ = slice;
+        None
+    }
+
+    /// Return the vector as `Vec<i32>` without copying if the index type is `i32`.
+    ///
+    /// Returns the unchanged vector as error otherwise.
+    fn into_i32_vec(vec: Vec<Self>) -> Result<Vec<i32>, Vec<Self>> {
+        Err(vec)
+    }
+}
+
+macro_rules! impl_sparse_index {
+    ($index:ty) => {
+        impl SparseIndex for $index {
+            #[inline(always)]
+            fn to_usize(self) -> usize {
+                self as usize
+            }
+
+            fn from_usize(value: usize) -> Option<Self> {
+                Self::try_from(value).ok()
+            }
+        }
+    };
+}
+
+impl_sparse_index!(u32);
+impl_sparse_index!(u64);
+impl_sparse_index!(usize);
+impl_sparse_index!(i64);
+
+impl SparseIndex for i32 {
+    #[inline(always)]
+    fn to_usize(self) -> usize {
+        self as usize
+    }
+
+    fn from_usize(value: usize) -> Option<Self> {
+        Self::try_from(value).ok()
+    }
+
+    fn as_i32_slice(slice: &[Self]) -> Option<&[i32]> {
+        Some(slice)
+    }
+
+    fn into_i32_vec(vec: Vec<Self>) -> Result<Vec<i32>, Vec<Self>> {
+        Ok(vec)
+    }
+}
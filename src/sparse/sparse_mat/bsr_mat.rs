@@ -0,0 +1,293 @@
+//! Definition of BSR matrices.
+//!
+//! A block sparse row (BSR) matrix stores dense blocks of a fixed size in a CSR-like
+//! structure. Each block is stored contiguously in column-major order.
+
+use crate::dense::array::SliceArray;
+use crate::dense::gemm::Gemm;
+use crate::dense::traits::{AijIterator, Shape};
+use crate::dense::types::{RlstScalar, TransMode};
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::SparseIndex;
+
+/// Check that `block_size` divides `shape`.
+fn assert_block_size(shape: [usize; 2], block_size: [usize; 2]) {
+    assert!(
+        block_size[0] > 0
+            && block_size[1] > 0
+            && shape[0].is_multiple_of(block_size[0])
+            && shape[1].is_multiple_of(block_size[1]),
+        "Block size {:?} does not divide the shape {:?}",
+        block_size,
+        shape
+    );
+}
+
+/// A BSR matrix
+#[derive(Clone)]
+pub struct BsrMatrix<Item: RlstScalar> {
+    shape: [usize; 2],
+    block_size: [usize; 2],
+    indices: Vec<usize>,
+    indptr: Vec<usize>,
+    data: Vec<Item>,
+}
+
+impl<Item: RlstScalar> BsrMatrix<Item> {
+    /// Create a new BSR matrix
+    ///
+    /// `indptr` and `indices` describe the block sparsity pattern as for a CSR matrix with
+    /// `shape[0] / block_size[0]` rows and `shape[1] / block_size[1]` columns. `data`
+    /// contains the blocks in the same order, each stored in column-major order.
+    pub fn new(
+        shape: [usize; 2],
+        block_size: [usize; 2],
+        indices: Vec<usize>,
+        indptr: Vec<usize>,
+        data: Vec<Item>,
+    ) -> Self {
+        assert_block_size(shape, block_size);
+        assert_eq!(indptr.len(), 1 + shape[0] / block_size[0]);
+        assert_eq!(indices.len(), *indptr.last().unwrap());
+        assert_eq!(data.len(), indices.len() * block_size[0] * block_size[1]);
+
+        Self {
+            shape,
+            block_size,
+            indices,
+            indptr,
+            data,
+        }
+    }
+
+    /// Number of stored blocks
+    pub fn nblocks(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Number of stored elements, including zeros within blocks
+    pub fn nelems(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Shape of each block
+    pub fn block_size(&self) -> [usize; 2] {
+        self.block_size
+    }
+
+    /// Block column indices of the blocks
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Indices at which each block row starts
+    pub fn indptr(&self) -> &[usize] {
+        &self.indptr
+    }
+
+    /// Entries of the blocks
+    pub fn data(&self) -> &[Item] {
+        &self.data
+    }
+
+    /// Return the block with index `index` in the block storage as a dense array.
+    pub fn block(&self, index: usize) -> SliceArray<'_, Item, 2> {
+        let block_elems = self.block_size[0] * self.block_size[1];
+        SliceArray::from_shape(
+            &self.data[index * block_elems..(1 + index) * block_elems],
+            self.block_size,
+        )
+    }
+
+    /// Matrix multiplication
+    ///
+    /// Each block is applied with a dense matrix-vector product.
+    pub fn matmul(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        assert_eq!(self.shape()[0], y.len());
+        assert_eq!(self.shape()[1], x.len());
+
+        let [block_rows, block_cols] = self.block_size;
+        let block_elems = block_rows * block_cols;
+
+        for (block_row, y_block) in y.chunks_exact_mut(block_rows).enumerate() {
+            y_block.iter_mut().for_each(|elem| *elem = beta * *elem);
+            for index in self.indptr[block_row]..self.indptr[1 + block_row] {
+                let col = self.indices[index] * block_cols;
+                <Item as Gemm>::gemm(
+                    TransMode::NoTrans,
+                    TransMode::NoTrans,
+                    block_rows,
+                    1,
+                    block_cols,
+                    alpha,
+                    &self.data[index * block_elems..(1 + index) * block_elems],
+                    1,
+                    block_rows,
+                    &x[col..col + block_cols],
+                    1,
+                    block_cols,
+                    Item::one(),
+                    y_block,
+                    1,
+                    block_rows,
+                );
+            }
+        }
+    }
+
+    /// Create a BSR matrix from a CSR matrix.
+    ///
+    /// Every block that contains a stored entry of `csr` becomes a stored block.
+    pub fn from_csr<I: SparseIndex>(csr: &CsrMatrix<Item, I>, block_size: [usize; 2]) -> Self {
+        let shape = csr.shape();
+        assert_block_size(shape, block_size);
+
+        let [block_rows, block_cols] = block_size;
+        let nblock_rows = shape[0] / block_rows;
+        let nblock_cols = shape[1] / block_cols;
+        let block_elems = block_rows * block_cols;
+
+        let mut indptr = Vec::<usize>::with_capacity(1 + nblock_rows);
+        let mut indices = Vec::<usize>::new();
+        let mut data = Vec::<Item>::new();
+
+        // `position[block_col]` is the index of the block in the current block row.
+        let mut position = vec![usize::MAX; nblock_cols];
+
+        indptr.push(0);
+        for block_row in 0..nblock_rows {
+            let row_start = indices.len();
+            let rows = block_row * block_rows..(1 + block_row) * block_rows;
+
+            for row in rows.clone() {
+                for &col in
+                    &csr.indices()[csr.indptr()[row].to_usize()..csr.indptr()[1 + row].to_usize()]
+                {
+                    let block_col = col.to_usize() / block_cols;
+                    if position[block_col] == usize::MAX {
+                        position[block_col] = 0;
+                        indices.push(block_col);
+                    }
+                }
+            }
+            indices[row_start..].sort_unstable();
+            for (index, &block_col) in indices.iter().enumerate().skip(row_start) {
+                position[block_col] = index;
+            }
+
+            data.resize(indices.len() * block_elems, Item::zero());
+            for row in rows {
+                for pos in csr.indptr()[row].to_usize()..csr.indptr()[1 + row].to_usize() {
+                    let col = csr.indices()[pos].to_usize();
+                    let index = position[col / block_cols];
+                    data[index * block_elems
+                        + (col % block_cols) * block_rows
+                        + row % block_rows] += csr.data()[pos];
+                }
+            }
+
+            for &block_col in &indices[row_start..] {
+                position[block_col] = usize::MAX;
+            }
+            indptr.push(indices.len());
+        }
+
+        Self::new(shape, block_size, indices, indptr, data)
+    }
+
+    /// Convert to a CSR matrix
+    ///
+    /// Zero entries within blocks are not stored in the CSR matrix.
+    pub fn to_csr(&self) -> CsrMatrix<Item> {
+        let [block_rows, block_cols] = self.block_size;
+        let block_elems = block_rows * block_cols;
+
+        let mut indptr = Vec::<usize>::with_capacity(1 + self.shape[0]);
+        let mut indices = Vec::<usize>::new();
+        let mut data = Vec::<Item>::new();
+
+        indptr.push(0);
+        for row in 0..self.shape[0] {
+            let block_row = row / block_rows;
+            let local_row = row % block_rows;
+            for index in self.indptr[block_row]..self.indptr[1 + block_row] {
+                for local_col in 0..block_cols {
+                    let value = self.data[index * block_elems + local_col * block_rows + local_row];
+                    if value != Item::zero() {
+                        indices.push(self.indices[index] * block_cols + local_col);
+                        data.push(value);
+                    }
+                }
+            }
+            indptr.push(indices.len());
+        }
+
+        CsrMatrix::new(self.shape, indices, indptr, data)
+    }
+}
+
+impl<Item: RlstScalar> Shape<2> for BsrMatrix<Item> {
+    fn shape(&self) -> [usize; 2] {
+        self.shape
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CsrMatrix<Item, I> {
+    /// Convert to a BSR matrix with the given block size.
+    pub fn to_bsr(&self, block_size: [usize; 2]) -> BsrMatrix<Item> {
+        BsrMatrix::from_csr(self, block_size)
+    }
+}
+
+/// BSR iterator
+///
+/// Iterates over all stored entries, including zeros within blocks.
+pub struct BsrAijIterator<'a, Item: RlstScalar> {
+    mat: &'a BsrMatrix<Item>,
+    block_row: usize,
+    pos: usize,
+}
+
+impl<'a, Item: RlstScalar> std::iter::Iterator for BsrAijIterator<'a, Item> {
+    type Item = (usize, usize, Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.mat.data.len() {
+            return None;
+        }
+
+        let [block_rows, block_cols] = self.mat.block_size;
+        let block_elems = block_rows * block_cols;
+        let index = self.pos / block_elems;
+        let local = self.pos % block_elems;
+
+        // Skip over empty block rows.
+        while self.mat.indptr[1 + self.block_row] <= index {
+            self.block_row += 1;
+        }
+
+        let result = Some((
+            self.block_row * block_rows + local % block_rows,
+            self.mat.indices[index] * block_cols + local / block_rows,
+            self.mat.data[self.pos],
+        ));
+        self.pos += 1;
+        result
+    }
+}
+
+impl<Item: RlstScalar> AijIterator for BsrMatrix<Item> {
+    type Item = Item;
+    type Iter<'a>
+        = BsrAijIterator<'a, Item>
+    where
+        Self: 'a;
+
+    fn iter_aij(&self) -> Self::Iter<'_> {
+        BsrAijIterator {
+            mat: self,
+            block_row: 0,
+            pos: 0,
+        }
+    }
+}
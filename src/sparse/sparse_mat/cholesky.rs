@@ -0,0 +1,393 @@
+//! Sparse Cholesky and LDL factorisations of Hermitian CSC matrices.
+//!
+//! The factorisation is computed in two phases. The symbolic phase [CholeskySymbolic]
+//! computes a fill-reducing ordering, the elimination tree and the sparsity pattern of the
+//! factor. It only depends on the sparsity pattern and can be reused for matrices with the
+//! same pattern but different values. The numeric phase computes the factor with a
+//! left-looking algorithm.
+//!
+//! Only the lower triangle of the matrix is accessed. The matrix is assumed to be Hermitian,
+//! i.e. symmetric for real scalar types.
+
+use crate::dense::traits::Shape;
+use crate::dense::types::{RlstError, RlstResult, RlstScalar};
+use crate::sparse::ordering::approximate_minimum_degree;
+use crate::sparse::sparse_mat::csc_mat::CscMatrix;
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::tools::assert_permutation;
+use crate::sparse::sparse_mat::SparseIndex;
+
+/// Symbolic analysis of a sparse Cholesky or LDL factorisation.
+#[derive(Clone)]
+pub struct CholeskySymbolic {
+    n: usize,
+    nelems: usize,
+    permutation: Vec<usize>,
+    parent: Vec<usize>,
+    // Lower triangle of `P A P^H`. Entry `pos` is taken from entry `source[pos]` of the
+    // matrix and conjugated if `conjugate[pos]` is true.
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    source: Vec<usize>,
+    conjugate: Vec<bool>,
+    // Pattern of the factor `L`. The diagonal is the first entry of each column.
+    l_indptr: Vec<usize>,
+    l_indices: Vec<usize>,
+}
+
+impl CholeskySymbolic {
+    /// Symbolic analysis with an approximate minimum degree ordering.
+    pub fn new<Item: RlstScalar, I: SparseIndex>(mat: &CscMatrix<Item, I>) -> RlstResult<Self> {
+        let shape = mat.shape();
+        if shape[0] != shape[1] {
+            return Err(RlstError::MatrixNotSquare(shape[0], shape[1]));
+        }
+        // The CSC arrays of `A` are the CSR arrays of `A^T`, which has the same symmetric
+        // sparsity graph.
+        let pattern = CsrMatrix::new(
+            shape,
+            mat.indices().to_vec(),
+            mat.indptr().to_vec(),
+            vec![Item::zero(); mat.nelems()],
+        );
+        let ordering = approximate_minimum_degree(&pattern);
+        Self::with_permutation(mat, ordering.permutation())
+    }
+
+    /// Symbolic analysis with a given ordering.
+    ///
+    /// Row and column `i` of the factorised matrix `P A P^H` are row and column
+    /// `permutation[i]` of `A`, as for [CscMatrix::permute].
+    pub fn with_permutation<Item: RlstScalar, I: SparseIndex>(
+        mat: &CscMatrix<Item, I>,
+        permutation: &[usize],
+    ) -> RlstResult<Self> {
+        let shape = mat.shape();
+        if shape[0] != shape[1] {
+            return Err(RlstError::MatrixNotSquare(shape[0], shape[1]));
+        }
+        let n = shape[0];
+        assert_permutation(permutation, n);
+
+        let mut inverse = vec![0; n];
+        for (new, &old) in permutation.iter().enumerate() {
+            inverse[old] = new;
+        }
+
+        // Lower triangle of `P A P^H` in CSC format with the source of each entry.
+        let mut counts = vec![0; 1 + n];
+        for col in 0..n {
+            for &row in
+                &mat.indices()[mat.indptr()[col].to_usize()..mat.indptr()[1 + col].to_usize()]
+            {
+                let row = row.to_usize();
+                if row >= col {
+                    counts[1 + inverse[row].min(inverse[col])] += 1;
+                }
+            }
+        }
+        for col in 0..n {
+            counts[1 + col] += counts[col];
+        }
+        let indptr = counts.clone();
+        let mut indices = vec![0; indptr[n]];
+        let mut source = vec![0; indptr[n]];
+        let mut conjugate = vec![false; indptr[n]];
+        for col in 0..n {
+            for pos in mat.indptr()[col].to_usize()..mat.indptr()[1 + col].to_usize() {
+                let row = mat.indices()[pos].to_usize();
+                if row >= col {
+                    let (new_row, new_col) = (inverse[row], inverse[col]);
+                    let target_col = new_row.min(new_col);
+                    let next = &mut counts[target_col];
+                    indices[*next] = new_row.max(new_col);
+                    source[*next] = pos;
+                    conjugate[*next] = new_row < new_col;
+                    *next += 1;
+                }
+            }
+        }
+
+        // Column patterns of `L`. The pattern of column `j` is the pattern of column `j`
+        // of the matrix joined with the patterns of the children of `j` in the
+        // elimination tree.
+        let mut parent = vec![usize::MAX; n];
+        let mut children = vec![Vec::<usize>::new(); n];
+        let mut marker = vec![usize::MAX; n];
+        let mut l_indptr = Vec::<usize>::with_capacity(1 + n);
+        let mut l_indices = Vec::<usize>::new();
+
+        l_indptr.push(0);
+        for col in 0..n {
+            let start = l_indices.len();
+            marker[col] = col;
+            l_indices.push(col);
+            for &row in &indices[indptr[col]..indptr[1 + col]] {
+                if marker[row] != col {
+                    marker[row] = col;
+                    l_indices.push(row);
+                }
+            }
+            for &child in &children[col] {
+                for pos in l_indptr[child]..l_indptr[1 + child] {
+                    let row = l_indices[pos];
+                    if row > col && marker[row] != col {
+                        marker[row] = col;
+                        l_indices.push(row);
+                    }
+                }
+            }
+            l_indices[1 + start..].sort_unstable();
+            if l_indices.len() > 1 + start {
+                parent[col] = l_indices[1 + start];
+                children[parent[col]].push(col);
+            }
+            l_indptr.push(l_indices.len());
+        }
+
+        Ok(Self {
+            n,
+            nelems: mat.nelems(),
+            permutation: permutation.to_vec(),
+            parent,
+            indptr,
+            indices,
+            source,
+            conjugate,
+            l_indptr,
+            l_indices,
+        })
+    }
+
+    /// Dimension of the matrix.
+    pub fn dim(&self) -> usize {
+        self.n
+    }
+
+    /// Number of stored entries of the factor `L`, including the diagonal.
+    pub fn factor_nelems(&self) -> usize {
+        self.l_indices.len()
+    }
+
+    /// The fill-reducing permutation.
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// The elimination tree. The parent of a root is `usize::MAX`.
+    pub fn elimination_tree(&self) -> &[usize] {
+        &self.parent
+    }
+
+    /// Compute the Cholesky factorisation `P A P^H = L L^H`.
+    ///
+    /// The matrix must have the same sparsity pattern as the matrix passed to the
+    /// symbolic phase. Returns an error if the matrix is not positive definite.
+    pub fn factorize<Item: RlstScalar, I: SparseIndex>(
+        &self,
+        mat: &CscMatrix<Item, I>,
+    ) -> RlstResult<SparseCholesky<Item>> {
+        self.numeric(mat, false)
+    }
+
+    /// Compute the factorisation `P A P^H = L D L^H` with unit lower triangular `L`.
+    ///
+    /// The matrix must have the same sparsity pattern as the matrix passed to the
+    /// symbolic phase. This factorisation does not require the matrix to be definite.
+    /// Returns an error if a zero pivot occurs.
+    pub fn factorize_ldl<Item: RlstScalar, I: SparseIndex>(
+        &self,
+        mat: &CscMatrix<Item, I>,
+    ) -> RlstResult<SparseCholesky<Item>> {
+        self.numeric(mat, true)
+    }
+
+    /// Left-looking numeric factorisation.
+    fn numeric<Item: RlstScalar, I: SparseIndex>(
+        &self,
+        mat: &CscMatrix<Item, I>,
+        ldl: bool,
+    ) -> RlstResult<SparseCholesky<Item>> {
+        assert!(
+            mat.shape() == [self.n, self.n] && mat.nelems() == self.nelems,
+            "Matrix does not match the sparsity pattern of the symbolic phase."
+        );
+
+        let n = self.n;
+        let mut data = vec![Item::zero(); self.l_indices.len()];
+        let mut diagonal = vec![Item::zero(); if ldl { n } else { 0 }];
+        let mut work = vec![Item::zero(); n];
+
+        // `next[j]` is the position in column `j` of `L` of the next row to update and
+        // `pending[k]` contains the columns whose next row is `k`.
+        let mut next = self.l_indptr[..n].to_vec();
+        let mut pending = vec![Vec::<usize>::new(); n];
+
+        for col in 0..n {
+            let column = self.l_indptr[col]..self.l_indptr[1 + col];
+
+            for pos in self.indptr[col]..self.indptr[1 + col] {
+                let value = mat.data()[self.source[pos]];
+                work[self.indices[pos]] += if self.conjugate[pos] {
+                    value.conj()
+                } else {
+                    value
+                };
+            }
+
+            for other in std::mem::take(&mut pending[col]) {
+                let other_column = next[other]..self.l_indptr[1 + other];
+                let mut factor = data[other_column.start].conj();
+                if ldl {
+                    factor *= diagonal[other];
+                }
+                for pos in other_column {
+                    work[self.l_indices[pos]] -= data[pos] * factor;
+                }
+                next[other] += 1;
+                if next[other] < self.l_indptr[1 + other] {
+                    pending[self.l_indices[next[other]]].push(other);
+                }
+            }
+
+            let pivot = work[col];
+            work[col] = Item::zero();
+            let scale = if ldl {
+                if pivot == Item::zero() {
+                    return Err(RlstError::GeneralError(format!(
+                        "Zero pivot in column {} of the LDL factorisation.",
+                        col
+                    )));
+                }
+                diagonal[col] = pivot;
+                data[column.start] = Item::one();
+                pivot
+            } else {
+                if pivot.re() <= <Item::Real as num::Zero>::zero() {
+                    return Err(RlstError::GeneralError(format!(
+                        "Matrix is not positive definite. Pivot {} is {}.",
+                        col, pivot
+                    )));
+                }
+                let root = Item::from_real(pivot.re()).sqrt();
+                data[column.start] = root;
+                root
+            };
+
+            for (value, &row) in data[1 + column.start..column.end]
+                .iter_mut()
+                .zip(&self.l_indices[1 + column.start..column.end])
+            {
+                *value = work[row] / scale;
+                work[row] = Item::zero();
+            }
+
+            next[col] = 1 + column.start;
+            if next[col] < column.end {
+                pending[self.l_indices[next[col]]].push(col);
+            }
+        }
+
+        Ok(SparseCholesky {
+            symbolic: self.clone(),
+            data,
+            diagonal: if ldl { Some(diagonal) } else { None },
+        })
+    }
+}
+
+/// A sparse Cholesky `P A P^H = L L^H` or LDL `P A P^H = L D L^H` factorisation.
+#[derive(Clone)]
+pub struct SparseCholesky<Item: RlstScalar> {
+    symbolic: CholeskySymbolic,
+    data: Vec<Item>,
+    diagonal: Option<Vec<Item>>,
+}
+
+impl<Item: RlstScalar> SparseCholesky<Item> {
+    /// The symbolic analysis of the factorisation.
+    pub fn symbolic(&self) -> &CholeskySymbolic {
+        &self.symbolic
+    }
+
+    /// Return true if this is an LDL factorisation.
+    pub fn is_ldl(&self) -> bool {
+        self.diagonal.is_some()
+    }
+
+    /// The factor `L`.
+    pub fn l_factor(&self) -> CscMatrix<Item> {
+        let n = self.symbolic.n;
+        CscMatrix::new(
+            [n, n],
+            self.symbolic.l_indices.clone(),
+            self.symbolic.l_indptr.clone(),
+            self.data.clone(),
+        )
+    }
+
+    /// The diagonal `D` of an LDL factorisation.
+    pub fn d_factor(&self) -> Option<&[Item]> {
+        self.diagonal.as_deref()
+    }
+
+    /// Solve `A x = rhs`.
+    pub fn solve(&self, rhs: &[Item], x: &mut [Item]) {
+        let n = self.symbolic.n;
+        assert_eq!(rhs.len(), n);
+        assert_eq!(x.len(), n);
+
+        let indptr = &self.symbolic.l_indptr;
+        let indices = &self.symbolic.l_indices;
+        let permutation = &self.symbolic.permutation;
+
+        let mut work = permutation
+            .iter()
+            .map(|&index| rhs[index])
+            .collect::<Vec<_>>();
+
+        // Forward substitution with `L`.
+        for col in 0..n {
+            let value = work[col] / self.data[indptr[col]];
+            work[col] = value;
+            for pos in 1 + indptr[col]..indptr[1 + col] {
+                work[indices[pos]] -= self.data[pos] * value;
+            }
+        }
+
+        if let Some(diagonal) = &self.diagonal {
+            for (value, &d) in work.iter_mut().zip(diagonal.iter()) {
+                *value /= d;
+            }
+        }
+
+        // Backward substitution with `L^H`.
+        for col in (0..n).rev() {
+            let mut value = work[col];
+            for pos in 1 + indptr[col]..indptr[1 + col] {
+                value -= self.data[pos].conj() * work[indices[pos]];
+            }
+            work[col] = value / self.data[indptr[col]].conj();
+        }
+
+        for (&index, &value) in permutation.iter().zip(work.iter()) {
+            x[index] = value;
+        }
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CscMatrix<Item, I> {
+    /// Compute the sparse Cholesky factorisation of a Hermitian positive definite matrix.
+    ///
+    /// To reuse the symbolic analysis for several matrices use [CholeskySymbolic].
+    pub fn cholesky(&self) -> RlstResult<SparseCholesky<Item>> {
+        CholeskySymbolic::new(self)?.factorize(self)
+    }
+
+    /// Compute the sparse LDL factorisation of a Hermitian matrix.
+    ///
+    /// To reuse the symbolic analysis for several matrices use [CholeskySymbolic].
+    pub fn ldl(&self) -> RlstResult<SparseCholesky<Item>> {
+        CholeskySymbolic::new(self)?.factorize_ldl(self)
+    }
+}
@@ -1,33 +1,61 @@
 //! Definition of CSC matrices.
 
 use crate::dense::types::RlstResult;
-use crate::sparse::sparse_mat::SparseMatType;
+use crate::sparse::sparse_mat::{SparseIndex, SparseMatType};
 
+use crate::dense::array::{Array, DynamicArray};
 use crate::dense::traits::AijIterator;
 use crate::dense::traits::Shape;
+use crate::dense::traits::{
+    RandomAccessByValue, RandomAccessMut, UnsafeRandomAccessByValue, UnsafeRandomAccessMut,
+};
 use crate::dense::types::RlstScalar;
 use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
-use crate::sparse::sparse_mat::tools::normalize_aij;
+use crate::sparse::sparse_mat::tools::{
+    add_compressed, assert_permutation, compressed_diagonal, compressed_is_symmetric,
+    convert_indices, eliminate_zeros, extract_compressed, normalize_aij, set_compressed_diagonal,
+    transpose_compressed, validate_compressed, SparseStats,
+};
 use itertools::Itertools;
 
 /// A CSC matrix
+///
+/// The index type `I` of the row indices and column pointers defaults to `usize`.
 #[derive(Clone)]
-pub struct CscMatrix<Item: RlstScalar> {
+pub struct CscMatrix<Item: RlstScalar, I: SparseIndex = usize> {
     mat_type: SparseMatType,
     shape: [usize; 2],
-    indices: Vec<usize>,
-    indptr: Vec<usize>,
+    indices: Vec<I>,
+    indptr: Vec<I>,
     data: Vec<Item>,
 }
 
-impl<Item: RlstScalar> CscMatrix<Item> {
+/// A CSC matrix that borrows its arrays
+///
+/// The view wraps existing slices, e.g. of a memory mapped file, without copying them.
+/// The arrays are not checked on construction, see [CscMatrixView::validate].
+#[derive(Clone, Copy)]
+pub struct CscMatrixView<'a, Item: RlstScalar, I: SparseIndex = usize> {
+    shape: [usize; 2],
+    indices: &'a [I],
+    indptr: &'a [I],
+    data: &'a [Item],
+}
+
+/// Contiguous columns of a CSC matrix, see [CscMatrix::col_range]
+///
+/// Borrows the row indices and values of the columns. Only the column pointers are
+/// rebased, so that they start at zero.
+pub struct CscColRange<'a, T: RlstScalar, I: SparseIndex = usize> {
+    shape: [usize; 2],
+    indices: &'a [I],
+    indptr: Vec<I>,
+    data: &'a [T],
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CscMatrix<Item, I> {
     /// Create a new CSC matrix
-    pub fn new(
-        shape: [usize; 2],
-        indices: Vec<usize>,
-        indptr: Vec<usize>,
-        data: Vec<Item>,
-    ) -> Self {
+    pub fn new(shape: [usize; 2], indices: Vec<I>, indptr: Vec<I>, data: Vec<Item>) -> Self {
         Self {
             mat_type: SparseMatType::Csc,
             shape,
@@ -47,13 +75,13 @@ impl<Item: RlstScalar> CscMatrix<Item> {
         &self.mat_type
     }
 
-    /// Row indices of items
-    pub fn indices(&self) -> &[usize] {
+    /// Indices of items
+    pub fn indices(&self) -> &[I] {
         &self.indices
     }
 
     /// Indices at which each column starts
-    pub fn indptr(&self) -> &[usize] {
+    pub fn indptr(&self) -> &[I] {
         &self.indptr
     }
 
@@ -62,39 +90,473 @@ impl<Item: RlstScalar> CscMatrix<Item> {
         &self.data
     }
 
+    /// Return the arrays `(indices, indptr, data)` of the matrix without copying them.
+    pub fn into_parts(self) -> (Vec<I>, Vec<I>, Vec<Item>) {
+        (self.indices, self.indptr, self.data)
+    }
+
+    /// Return the columns `cols` of the matrix with all rows.
+    ///
+    /// This is faster than [CscMatrix::submatrix] as the row indices and values of the
+    /// columns are borrowed instead of copied. Only the column pointers are rebased.
+    pub fn col_range(&self, cols: std::ops::Range<usize>) -> CscColRange<'_, Item, I> {
+        assert!(
+            cols.start <= cols.end && cols.end <= self.shape[1],
+            "Column range {:?} out of bounds for matrix with {} columns.",
+            cols,
+            self.shape[1]
+        );
+        let start = self.indptr[cols.start].to_usize();
+        let end = self.indptr[cols.end].to_usize();
+        CscColRange {
+            shape: [self.shape[0], cols.len()],
+            indices: &self.indices[start..end],
+            indptr: self.indptr[cols.start..=cols.end]
+                .iter()
+                .map(|ptr| I::from_usize(ptr.to_usize() - start).unwrap())
+                .collect(),
+            data: &self.data[start..end],
+        }
+    }
+
+    /// Return a view of the matrix that borrows its arrays.
+    pub fn view(&self) -> CscMatrixView<'_, Item, I> {
+        CscMatrixView::new(self.shape, &self.indices, &self.indptr, &self.data)
+    }
+
+    /// Check that the compressed arrays describe a valid matrix.
+    ///
+    /// Checks that `indptr` has one entry per column plus one, starts at zero, is monotone
+    /// and ends at the number of entries, and that the indices in each column are in
+    /// bounds, sorted and distinct. Returns
+    /// [crate::dense::types::RlstError::InvalidSparseMatrix] describing the first problem
+    /// found.
+    pub fn validate(&self) -> RlstResult<()> {
+        self.view().validate()
+    }
+
+    /// Return statistics of the sparsity pattern and the entries of the matrix.
+    ///
+    /// The matrix should be valid, see [CscMatrix::validate].
+    pub fn stats(&self) -> SparseStats {
+        self.view().stats()
+    }
+
+    /// Return true if the matrix is square and `|a_ij - a_ji| <= tol * max(|a_ij|, |a_ji|)`
+    /// for all `i` and `j`.
+    pub fn is_symmetric(&self, tol: Item::Real) -> bool {
+        self.view().is_symmetric(tol)
+    }
+
+    /// Return true if the matrix is square and
+    /// `|a_ij - conj(a_ji)| <= tol * max(|a_ij|, |a_ji|)` for all `i` and `j`.
+    pub fn is_hermitian(&self, tol: Item::Real) -> bool {
+        self.view().is_hermitian(tol)
+    }
+
+    /// Matrix multiplication
+    pub fn matmul(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        self.view().matmul(alpha, x, beta, y);
+    }
+
+    /// Multiplication with the transpose of the matrix.
+    ///
+    /// Computes `y = alpha * A^T x + beta * y` without converting the matrix.
+    pub fn matmul_trans(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        self.view().matmul_trans(alpha, x, beta, y);
+    }
+
+    /// Multiplication with the conjugate transpose of the matrix.
+    ///
+    /// Computes `y = alpha * A^H x + beta * y` without converting the matrix.
+    pub fn matmul_conj_trans(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        self.view().matmul_conj_trans(alpha, x, beta, y);
+    }
+
+    /// Multiplication with a matrix of right-hand sides.
+    ///
+    /// Computes `y = alpha * A x + beta * y` for dense two-dimensional arrays `x` and `y`,
+    /// which may be arbitrary strided views.
+    pub fn matmul_mat<
+        ArrayImplX: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplY: UnsafeRandomAccessByValue<2, Item = Item>
+            + UnsafeRandomAccessMut<2, Item = Item>
+            + Shape<2>,
+    >(
+        &self,
+        alpha: Item,
+        x: &Array<Item, ArrayImplX, 2>,
+        beta: Item,
+        y: &mut Array<Item, ArrayImplY, 2>,
+    ) {
+        self.view().matmul_mat(alpha, x, beta, y);
+    }
+
+    /// Return the diagonal of the matrix.
+    pub fn diagonal(&self) -> Vec<Item> {
+        self.view().diagonal()
+    }
+
+    /// Return the matrix as a dense two-dimensional array.
+    pub fn to_dense(&self) -> DynamicArray<Item, 2> {
+        self.view().to_dense()
+    }
+
+    /// Return a copy of the matrix with index type `J`.
+    ///
+    /// Returns an error if an index or column pointer is not representable in `J`.
+    pub fn to_index_type<J: SparseIndex>(&self) -> RlstResult<CscMatrix<Item, J>> {
+        Ok(CscMatrix::new(
+            self.shape,
+            convert_indices(&self.indices)?,
+            convert_indices(&self.indptr)?,
+            self.data.clone(),
+        ))
+    }
+
+    /// Converts the matrix into a tuple (shape, indices, indptr, data)
+    pub fn into_tuple(self) -> ([usize; 2], Vec<I>, Vec<I>, Vec<Item>) {
+        (self.shape, self.indices, self.indptr, self.data)
+    }
+}
+
+impl<'a, Item: RlstScalar, I: SparseIndex> CscMatrixView<'a, Item, I> {
+    /// Create a new view of the CSC arrays `indices`, `indptr` and `data`
+    pub fn new(shape: [usize; 2], indices: &'a [I], indptr: &'a [I], data: &'a [Item]) -> Self {
+        Self {
+            shape,
+            indices,
+            indptr,
+            data,
+        }
+    }
+
+    /// Number of elements
+    pub fn nelems(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Indices of items
+    pub fn indices(&self) -> &'a [I] {
+        self.indices
+    }
+
+    /// Indices at which each column starts
+    pub fn indptr(&self) -> &'a [I] {
+        self.indptr
+    }
+
+    /// Entries of the matrix
+    pub fn data(&self) -> &'a [Item] {
+        self.data
+    }
+
+    /// Check that the compressed arrays describe a valid matrix, see [CscMatrix::validate].
+    pub fn validate(&self) -> RlstResult<()> {
+        validate_compressed(
+            self.shape,
+            SparseMatType::Csc,
+            self.indptr,
+            self.indices,
+            self.data.len(),
+        )
+    }
+
+    /// Return statistics of the sparsity pattern and the entries of the matrix.
+    pub fn stats(&self) -> SparseStats {
+        SparseStats::from_compressed(
+            self.shape,
+            SparseMatType::Csc,
+            self.indptr,
+            self.indices,
+            self.data,
+        )
+    }
+
+    /// Return true if the matrix is symmetric up to `tol`, see [CscMatrix::is_symmetric].
+    pub fn is_symmetric(&self, tol: Item::Real) -> bool {
+        self.shape[0] == self.shape[1]
+            && compressed_is_symmetric(self.indptr, self.indices, self.data, tol, false)
+    }
+
+    /// Return true if the matrix is Hermitian up to `tol`, see [CscMatrix::is_hermitian].
+    pub fn is_hermitian(&self, tol: Item::Real) -> bool {
+        self.shape[0] == self.shape[1]
+            && compressed_is_symmetric(self.indptr, self.indices, self.data, tol, true)
+    }
+
     /// Matrix multiplication
     pub fn matmul(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
         y.iter_mut().for_each(|elem| *elem = beta * *elem);
 
-        for (col, (&col_start, &col_end)) in self.indptr().iter().tuple_windows().enumerate() {
+        for (col, (&col_start, &col_end)) in self.indptr.iter().tuple_windows().enumerate() {
+            let range = col_start.to_usize()..col_end.to_usize();
             let x_elem = x[col];
-            for (&row, &elem) in self.indices[col_start..col_end]
+            for (&row, &elem) in self.indices[range.clone()]
                 .iter()
-                .zip(self.data[col_start..col_end].iter())
+                .zip(self.data[range].iter())
             {
-                y[row] += alpha * elem * x_elem;
+                y[row.to_usize()] += alpha * elem * x_elem;
             }
         }
     }
 
-    /// Converts the matrix into a tuple (shape, indices, indptr, data)
-    pub fn into_tuple(self) -> ([usize; 2], Vec<usize>, Vec<usize>, Vec<Item>) {
-        (self.shape, self.indices, self.indptr, self.data)
+    /// Multiplication with the transpose of the matrix.
+    pub fn matmul_trans(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        self.matmul_trans_impl(alpha, x, beta, y, false);
     }
 
-    /// Convert to CSR matrix
-    pub fn into_csr(self) -> CsrMatrix<Item> {
-        let mut rows = Vec::<usize>::with_capacity(self.nelems());
-        let mut cols = Vec::<usize>::with_capacity(self.nelems());
-        let mut data = Vec::<Item>::with_capacity(self.nelems());
+    /// Multiplication with the conjugate transpose of the matrix.
+    pub fn matmul_conj_trans(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        self.matmul_trans_impl(alpha, x, beta, y, true);
+    }
+
+    fn matmul_trans_impl(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item], conj: bool) {
+        assert_eq!(self.shape[1], y.len());
+        assert_eq!(self.shape[0], x.len());
+
+        for (out, (&col_start, &col_end)) in y.iter_mut().zip(self.indptr.iter().tuple_windows()) {
+            let range = col_start.to_usize()..col_end.to_usize();
+            let mut acc = Item::zero();
+            for (&row, &elem) in self.indices[range.clone()]
+                .iter()
+                .zip(self.data[range].iter())
+            {
+                let elem = if conj { elem.conj() } else { elem };
+                acc += elem * x[row.to_usize()];
+            }
+            *out = beta * *out + alpha * acc;
+        }
+    }
+
+    /// Multiplication with a matrix of right-hand sides, see [CscMatrix::matmul_mat].
+    pub fn matmul_mat<
+        ArrayImplX: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplY: UnsafeRandomAccessByValue<2, Item = Item>
+            + UnsafeRandomAccessMut<2, Item = Item>
+            + Shape<2>,
+    >(
+        &self,
+        alpha: Item,
+        x: &Array<Item, ArrayImplX, 2>,
+        beta: Item,
+        y: &mut Array<Item, ArrayImplY, 2>,
+    ) {
+        assert_eq!(self.shape[0], y.shape()[0]);
+        assert_eq!(self.shape[1], x.shape()[0]);
+        assert_eq!(x.shape()[1], y.shape()[1]);
+
+        for rhs in 0..x.shape()[1] {
+            for row in 0..self.shape[0] {
+                *y.get_mut([row, rhs]).unwrap() *= beta;
+            }
+            for (col, (&col_start, &col_end)) in self.indptr.iter().tuple_windows().enumerate() {
+                let range = col_start.to_usize()..col_end.to_usize();
+                let x_elem = alpha
+                    * x.get_value([col, rhs])
+                        .expect("Column index out of bounds.");
+                for (&row, &elem) in self.indices[range.clone()]
+                    .iter()
+                    .zip(self.data[range].iter())
+                {
+                    *y.get_mut([row.to_usize(), rhs])
+                        .expect("Row index out of bounds.") += elem * x_elem;
+                }
+            }
+        }
+    }
+
+    /// Return the diagonal of the matrix.
+    pub fn diagonal(&self) -> Vec<Item> {
+        compressed_diagonal(
+            self.shape[0].min(self.shape[1]),
+            self.indptr,
+            self.indices,
+            self.data,
+        )
+    }
 
+    /// Return the matrix as a dense two-dimensional array.
+    pub fn to_dense(&self) -> DynamicArray<Item, 2> {
+        let mut arr = DynamicArray::<Item, 2>::from_shape(self.shape);
         for (row, col, elem) in self.iter_aij() {
-            rows.push(row);
-            cols.push(col);
-            data.push(elem);
+            arr[[row, col]] += elem;
         }
+        arr
+    }
+
+    /// Copy the arrays into an owned CSC matrix.
+    pub fn into_owned(self) -> CscMatrix<Item, I> {
+        CscMatrix::new(
+            self.shape,
+            self.indices.to_vec(),
+            self.indptr.to_vec(),
+            self.data.to_vec(),
+        )
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CscMatrix<Item, I> {
+    /// Return `alpha * self + beta * other`.
+    ///
+    /// The sparsity pattern of the result is the union of the patterns of both matrices
+    /// with numerically zero entries removed.
+    pub fn add(&self, other: &Self, alpha: Item, beta: Item) -> Self {
+        assert_eq!(
+            self.shape, other.shape,
+            "Matrices must have the same shape. {:?} != {:?}",
+            self.shape, other.shape
+        );
+        let (indptr, indices, data) = add_compressed(
+            self.shape[0],
+            (&self.indptr, &self.indices, &self.data),
+            alpha,
+            (&other.indptr, &other.indices, &other.data),
+            beta,
+        );
+        Self::new(self.shape, indices, indptr, data)
+    }
+
+    /// Multiply all entries of the matrix by `alpha`.
+    pub fn scale_inplace(&mut self, alpha: Item) {
+        self.data.iter_mut().for_each(|elem| *elem *= alpha);
+        eliminate_zeros(&mut self.indptr, &mut self.indices, &mut self.data);
+    }
+
+    /// Return the transpose of the matrix.
+    pub fn transpose(&self) -> Self {
+        let (indptr, indices, data) =
+            transpose_compressed(self.shape[0], &self.indptr, &self.indices, &self.data);
+        Self::new([self.shape[1], self.shape[0]], indices, indptr, data)
+    }
+
+    /// Return the conjugate transpose of the matrix.
+    pub fn conj_transpose(&self) -> Self {
+        let mut result = self.transpose();
+        result.data.iter_mut().for_each(|elem| *elem = elem.conj());
+        result
+    }
+
+    /// Replace the diagonal of the matrix by `diag`.
+    ///
+    /// Missing diagonal entries are inserted and zero entries of `diag` are removed.
+    pub fn set_diagonal(&mut self, diag: &[Item]) {
+        assert_eq!(
+            diag.len(),
+            self.shape[0].min(self.shape[1]),
+            "Diagonal has wrong length. {} != {}",
+            diag.len(),
+            self.shape[0].min(self.shape[1])
+        );
+        let (indptr, indices, data) =
+            set_compressed_diagonal(diag, &self.indptr, &self.indices, &self.data);
+        self.indptr = indptr;
+        self.indices = indices;
+        self.data = data;
+    }
 
-        CsrMatrix::from_aij(self.shape(), &rows, &cols, &data).unwrap()
+    /// Multiply row `i` of the matrix by `scale[i]`.
+    pub fn scale_rows(&mut self, scale: &[Item]) {
+        assert_eq!(
+            scale.len(),
+            self.shape[0],
+            "Scaling vector has wrong length. {} != {}",
+            scale.len(),
+            self.shape[0]
+        );
+        for (elem, &index) in self.data.iter_mut().zip(self.indices.iter()) {
+            *elem *= scale[index.to_usize()];
+        }
+        eliminate_zeros(&mut self.indptr, &mut self.indices, &mut self.data);
+    }
+
+    /// Multiply column `j` of the matrix by `scale[j]`.
+    pub fn scale_cols(&mut self, scale: &[Item]) {
+        assert_eq!(
+            scale.len(),
+            self.shape[1],
+            "Scaling vector has wrong length. {} != {}",
+            scale.len(),
+            self.shape[1]
+        );
+        for (ptr, &factor) in scale.iter().enumerate() {
+            self.data[self.indptr[ptr].to_usize()..self.indptr[1 + ptr].to_usize()]
+                .iter_mut()
+                .for_each(|elem| *elem *= factor);
+        }
+        eliminate_zeros(&mut self.indptr, &mut self.indices, &mut self.data);
+    }
+
+    /// Return the submatrix `A[rows, cols]`.
+    ///
+    /// Entry `(i, j)` of the result is entry `(rows[i], cols[j])` of the matrix. Columns may be selected more than once, rows must be distinct.
+    /// For a contiguous range of columns [CscMatrix::col_range] is faster.
+    pub fn submatrix(&self, rows: &[usize], cols: &[usize]) -> Self {
+        let (indptr, indices, data) = extract_compressed(
+            self.shape[0],
+            cols,
+            rows,
+            &self.indptr,
+            &self.indices,
+            &self.data,
+        );
+        Self::new([rows.len(), cols.len()], indices, indptr, data)
+    }
+
+    /// Return the symmetric permutation `P A P^T`.
+    ///
+    /// Entry `(i, j)` of the result is entry `(permutation[i], permutation[j])` of the matrix.
+    pub fn permute(&self, permutation: &[usize]) -> Self {
+        assert_eq!(
+            self.shape[0], self.shape[1],
+            "Matrix must be square. {} != {}",
+            self.shape[0], self.shape[1]
+        );
+        assert_permutation(permutation, self.shape[0]);
+        self.submatrix(permutation, permutation)
+    }
+
+    /// Convert to CSR matrix
+    ///
+    /// Explicitly stored zeros are removed.
+    pub fn into_csr(self) -> CsrMatrix<Item, I> {
+        // The CSR arrays of the matrix are the CSC arrays of its transpose.
+        let (mut indptr, mut indices, mut data) =
+            transpose_compressed(self.shape[0], &self.indptr, &self.indices, &self.data);
+        eliminate_zeros(&mut indptr, &mut indices, &mut data);
+        CsrMatrix::new(self.shape, indices, indptr, data)
+    }
+}
+
+impl<Item: RlstScalar> CscMatrix<Item> {
+    /// Create a CSC matrix from a dense two-dimensional array.
+    ///
+    /// Entries whose absolute value is not larger than `drop_tol` are not stored. With a
+    /// `drop_tol` of zero all nonzero entries are stored. NaN entries are always stored.
+    pub fn from_dense<ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>>(
+        arr: &Array<Item, ArrayImpl, 2>,
+        drop_tol: Item::Real,
+    ) -> Self {
+        let shape = arr.shape();
+        let mut indptr = Vec::<usize>::with_capacity(1 + shape[1]);
+        let mut indices = Vec::<usize>::new();
+        let mut data = Vec::<Item>::new();
+
+        indptr.push(0);
+        for ptr in 0..shape[1] {
+            for index in 0..shape[0] {
+                let elem = unsafe { arr.get_value_unchecked([index, ptr]) };
+                // Written this way so that NaN entries are kept.
+                #[allow(clippy::neg_cmp_op_on_partial_ord)]
+                if !(elem.abs() <= drop_tol) {
+                    indices.push(index);
+                    data.push(elem);
+                }
+            }
+            indptr.push(indices.len());
+        }
+
+        Self::new(shape, indices, indptr, data)
     }
 
     /// Create CSC matrix from rows, columns and data
@@ -106,22 +568,21 @@ impl<Item: RlstScalar> CscMatrix<Item> {
     ) -> RlstResult<Self> {
         let (rows, cols, data) = normalize_aij(rows, cols, data, SparseMatType::Csc);
 
-        let max_col = cols.iter().max().unwrap();
-        let max_row = rows.last().unwrap();
-
-        assert!(
-            *max_col < shape[1],
-            "Maximum column {} must be smaller than `shape.1` {}",
-            max_col,
-            shape[1]
-        );
-
-        assert!(
-            *max_row < shape[0],
-            "Maximum row {} must be smaller than `shape.0` {}",
-            max_row,
-            shape[0]
-        );
+        if let (Some(max_col), Some(max_row)) = (cols.iter().max(), rows.iter().max()) {
+            assert!(
+                *max_col < shape[1],
+                "Maximum column {} must be smaller than `shape.1` {}",
+                max_col,
+                shape[1]
+            );
+
+            assert!(
+                *max_row < shape[0],
+                "Maximum row {} must be smaller than `shape.0` {}",
+                max_row,
+                shape[0]
+            );
+        }
 
         let nelems = data.len();
 
@@ -140,21 +601,62 @@ impl<Item: RlstScalar> CscMatrix<Item> {
     }
 }
 
+impl<'a, Item: RlstScalar, I: SparseIndex> CscColRange<'a, Item, I> {
+    /// Number of elements
+    pub fn nelems(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Row indices of the entries
+    pub fn indices(&self) -> &'a [I] {
+        self.indices
+    }
+
+    /// Indices at which each column starts
+    pub fn indptr(&self) -> &[I] {
+        &self.indptr
+    }
+
+    /// Entries of the columns
+    pub fn data(&self) -> &'a [Item] {
+        self.data
+    }
+
+    /// Return a view of the columns as a CSC matrix.
+    pub fn view(&self) -> CscMatrixView<'_, Item, I> {
+        CscMatrixView::new(self.shape, self.indices, &self.indptr, self.data)
+    }
+
+    /// Copy the columns into an owned CSC matrix.
+    pub fn into_owned(self) -> CscMatrix<Item, I> {
+        CscMatrix::new(
+            self.shape,
+            self.indices.to_vec(),
+            self.indptr,
+            self.data.to_vec(),
+        )
+    }
+}
+
 /// CSC iterator
-pub struct CscAijIterator<'a, Item: RlstScalar> {
-    mat: &'a CscMatrix<Item>,
+pub struct CscAijIterator<'a, Item: RlstScalar, I: SparseIndex = usize> {
+    mat: CscMatrixView<'a, Item, I>,
     col: usize,
     pos: usize,
 }
 
-impl<'a, Item: RlstScalar> CscAijIterator<'a, Item> {
+impl<'a, Item: RlstScalar, I: SparseIndex> CscAijIterator<'a, Item, I> {
     /// Create a new CSC iterator
-    pub fn new(mat: &'a CscMatrix<Item>) -> Self {
+    pub fn new(mat: &'a CscMatrix<Item, I>) -> Self {
+        Self::from_view(mat.view())
+    }
+
+    fn from_view(mat: CscMatrixView<'a, Item, I>) -> Self {
         // We need to move the col pointer to the first col that has at least one element.
 
         let mut col: usize = 0;
 
-        while col < mat.shape()[1] && mat.indptr[col] == mat.indptr[1 + col] {
+        while col < mat.shape[1] && mat.indptr[col] == mat.indptr[1 + col] {
             col += 1;
         }
 
@@ -162,16 +664,16 @@ impl<'a, Item: RlstScalar> CscAijIterator<'a, Item> {
     }
 }
 
-impl<'a, Item: RlstScalar> std::iter::Iterator for CscAijIterator<'a, Item> {
+impl<Item: RlstScalar, I: SparseIndex> std::iter::Iterator for CscAijIterator<'_, Item, I> {
     type Item = (usize, usize, Item);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos == self.mat.data().len() {
+        if self.pos == self.mat.data.len() {
             return None;
         }
 
         let result = Some((
-            self.mat.indices[self.pos],
+            self.mat.indices[self.pos].to_usize(),
             self.col,
             self.mat.data[self.pos],
         ));
@@ -181,7 +683,7 @@ impl<'a, Item: RlstScalar> std::iter::Iterator for CscAijIterator<'a, Item> {
         // The following jumps over all zero cols to the next relevant col
         // It needs a <= comparison since self.pos has already been increased but
         // indptr[1+self.col] may be the old value (in the case we encounter a zero column).
-        while self.col < self.mat.shape()[1] && self.mat.indptr()[1 + self.col] <= self.pos {
+        while self.col < self.mat.shape[1] && self.mat.indptr[1 + self.col].to_usize() <= self.pos {
             self.col += 1;
         }
 
@@ -192,20 +694,61 @@ impl<'a, Item: RlstScalar> std::iter::Iterator for CscAijIterator<'a, Item> {
     where
         Self: Sized,
     {
-        self.mat.data().len()
+        self.mat.data.len()
     }
 }
 
-impl<Item: RlstScalar> AijIterator for CscMatrix<Item> {
+impl<Item: RlstScalar, I: SparseIndex> AijIterator for CscMatrix<Item, I> {
     type Item = Item;
-    type Iter<'a> = CscAijIterator<'a, Item> where Self: 'a;
+    type Iter<'a>
+        = CscAijIterator<'a, Item, I>
+    where
+        Self: 'a;
 
     fn iter_aij(&self) -> Self::Iter<'_> {
         CscAijIterator::new(self)
     }
 }
 
-impl<Item: RlstScalar> crate::dense::traits::Shape<2> for CscMatrix<Item> {
+impl<'a, Item: RlstScalar, I: SparseIndex> AijIterator for CscMatrixView<'a, Item, I> {
+    type Item = Item;
+    type Iter<'b>
+        = CscAijIterator<'b, Item, I>
+    where
+        Self: 'b;
+
+    fn iter_aij(&self) -> Self::Iter<'_> {
+        CscAijIterator::from_view(*self)
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> crate::dense::traits::Shape<2> for CscMatrix<Item, I> {
+    fn shape(&self) -> [usize; 2] {
+        self.shape
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> crate::dense::traits::Shape<2>
+    for CscMatrixView<'_, Item, I>
+{
+    fn shape(&self) -> [usize; 2] {
+        self.shape
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> AijIterator for CscColRange<'_, Item, I> {
+    type Item = Item;
+    type Iter<'b>
+        = CscAijIterator<'b, Item, I>
+    where
+        Self: 'b;
+
+    fn iter_aij(&self) -> Self::Iter<'_> {
+        CscAijIterator::from_view(self.view())
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> crate::dense::traits::Shape<2> for CscColRange<'_, Item, I> {
     fn shape(&self) -> [usize; 2] {
         self.shape
     }
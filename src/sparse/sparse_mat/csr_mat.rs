@@ -2,32 +2,61 @@
 
 use crate::dense::traits::AijIterator;
 use crate::dense::types::RlstResult;
-use crate::sparse::sparse_mat::SparseMatType;
+use crate::sparse::sparse_mat::{SparseIndex, SparseMatType};
 
 use crate::dense::traits::Shape;
 use crate::dense::types::RlstScalar;
-use crate::sparse::sparse_mat::tools::normalize_aij;
+use crate::sparse::sparse_mat::tools::{
+    add_compressed, assert_permutation, compressed_diagonal, compressed_is_symmetric,
+    convert_indices, eliminate_zeros, extract_compressed, normalize_aij, set_compressed_diagonal,
+    transpose_compressed, validate_compressed, SparseStats,
+};
 
 use super::csc_mat::CscMatrix;
+use crate::dense::array::{Array, DynamicArray};
+use crate::dense::traits::{
+    RandomAccessByValue, RandomAccessMut, UnsafeRandomAccessByValue, UnsafeRandomAccessMut,
+};
+use itertools::Itertools;
 
 /// A CSR matrix
+///
+/// The index type `I` of the column indices and row pointers defaults to `usize`.
 #[derive(Clone)]
-pub struct CsrMatrix<T: RlstScalar> {
+pub struct CsrMatrix<T: RlstScalar, I: SparseIndex = usize> {
     mat_type: SparseMatType,
     shape: [usize; 2],
-    indices: Vec<usize>,
-    indptr: Vec<usize>,
+    indices: Vec<I>,
+    indptr: Vec<I>,
     data: Vec<T>,
 }
 
-impl<Item: RlstScalar> CsrMatrix<Item> {
+/// A CSR matrix that borrows its arrays
+///
+/// The view wraps existing slices, e.g. of a memory mapped file, without copying them.
+/// The arrays are not checked on construction, see [CsrMatrixView::validate].
+#[derive(Clone, Copy)]
+pub struct CsrMatrixView<'a, T: RlstScalar, I: SparseIndex = usize> {
+    shape: [usize; 2],
+    indices: &'a [I],
+    indptr: &'a [I],
+    data: &'a [T],
+}
+
+/// Contiguous rows of a CSR matrix, see [CsrMatrix::row_range]
+///
+/// Borrows the column indices and values of the rows. Only the row pointers are
+/// rebased, so that they start at zero.
+pub struct CsrRowRange<'a, T: RlstScalar, I: SparseIndex = usize> {
+    shape: [usize; 2],
+    indices: &'a [I],
+    indptr: Vec<I>,
+    data: &'a [T],
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CsrMatrix<Item, I> {
     /// Create a new CSR matrix
-    pub fn new(
-        shape: [usize; 2],
-        indices: Vec<usize>,
-        indptr: Vec<usize>,
-        data: Vec<Item>,
-    ) -> Self {
+    pub fn new(shape: [usize; 2], indices: Vec<I>, indptr: Vec<I>, data: Vec<Item>) -> Self {
         Self {
             mat_type: SparseMatType::Csr,
             shape,
@@ -48,12 +77,12 @@ impl<Item: RlstScalar> CsrMatrix<Item> {
     }
 
     /// Indices of items
-    pub fn indices(&self) -> &[usize] {
+    pub fn indices(&self) -> &[I] {
         &self.indices
     }
 
     /// Indices at which each row starts
-    pub fn indptr(&self) -> &[usize] {
+    pub fn indptr(&self) -> &[I] {
         &self.indptr
     }
 
@@ -62,39 +91,470 @@ impl<Item: RlstScalar> CsrMatrix<Item> {
         &self.data
     }
 
+    /// Return the arrays `(indices, indptr, data)` of the matrix without copying them.
+    pub fn into_parts(self) -> (Vec<I>, Vec<I>, Vec<Item>) {
+        (self.indices, self.indptr, self.data)
+    }
+
+    /// Return the rows `rows` of the matrix with all columns.
+    ///
+    /// This is faster than [CsrMatrix::submatrix] as the column indices and values of the
+    /// rows are borrowed instead of copied. Only the row pointers are rebased.
+    pub fn row_range(&self, rows: std::ops::Range<usize>) -> CsrRowRange<'_, Item, I> {
+        assert!(
+            rows.start <= rows.end && rows.end <= self.shape[0],
+            "Row range {:?} out of bounds for matrix with {} rows.",
+            rows,
+            self.shape[0]
+        );
+        let start = self.indptr[rows.start].to_usize();
+        let end = self.indptr[rows.end].to_usize();
+        CsrRowRange {
+            shape: [rows.len(), self.shape[1]],
+            indices: &self.indices[start..end],
+            indptr: self.indptr[rows.start..=rows.end]
+                .iter()
+                .map(|ptr| I::from_usize(ptr.to_usize() - start).unwrap())
+                .collect(),
+            data: &self.data[start..end],
+        }
+    }
+
+    /// Return a view of the matrix that borrows its arrays.
+    pub fn view(&self) -> CsrMatrixView<'_, Item, I> {
+        CsrMatrixView::new(self.shape, &self.indices, &self.indptr, &self.data)
+    }
+
+    /// Check that the compressed arrays describe a valid matrix.
+    ///
+    /// Checks that `indptr` has one entry per row plus one, starts at zero, is monotone and
+    /// ends at the number of entries, and that the indices in each row are in bounds,
+    /// sorted and distinct. Returns [RlstError::InvalidSparseMatrix] describing the first
+    /// problem found.
+    pub fn validate(&self) -> RlstResult<()> {
+        self.view().validate()
+    }
+
+    /// Return statistics of the sparsity pattern and the entries of the matrix.
+    ///
+    /// The matrix should be valid, see [CsrMatrix::validate].
+    pub fn stats(&self) -> SparseStats {
+        self.view().stats()
+    }
+
+    /// Return true if the matrix is square and `|a_ij - a_ji| <= tol * max(|a_ij|, |a_ji|)`
+    /// for all `i` and `j`.
+    pub fn is_symmetric(&self, tol: Item::Real) -> bool {
+        self.view().is_symmetric(tol)
+    }
+
+    /// Return true if the matrix is square and
+    /// `|a_ij - conj(a_ji)| <= tol * max(|a_ij|, |a_ji|)` for all `i` and `j`.
+    pub fn is_hermitian(&self, tol: Item::Real) -> bool {
+        self.view().is_hermitian(tol)
+    }
+
     /// Matrix multiplication
     pub fn matmul(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
-        assert_eq!(self.shape()[0], y.len());
-        assert_eq!(self.shape()[1], x.len());
+        self.view().matmul(alpha, x, beta, y);
+    }
+
+    /// Multiplication with the transpose of the matrix.
+    ///
+    /// Computes `y = alpha * A^T x + beta * y` without converting the matrix.
+    pub fn matmul_trans(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        self.view().matmul_trans(alpha, x, beta, y);
+    }
+
+    /// Multiplication with the conjugate transpose of the matrix.
+    ///
+    /// Computes `y = alpha * A^H x + beta * y` without converting the matrix.
+    pub fn matmul_conj_trans(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        self.view().matmul_conj_trans(alpha, x, beta, y);
+    }
+
+    /// Multiplication with a matrix of right-hand sides.
+    ///
+    /// Computes `y = alpha * A x + beta * y` for dense two-dimensional arrays `x` and `y`,
+    /// which may be arbitrary strided views.
+    pub fn matmul_mat<
+        ArrayImplX: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplY: UnsafeRandomAccessByValue<2, Item = Item>
+            + UnsafeRandomAccessMut<2, Item = Item>
+            + Shape<2>,
+    >(
+        &self,
+        alpha: Item,
+        x: &Array<Item, ArrayImplX, 2>,
+        beta: Item,
+        y: &mut Array<Item, ArrayImplY, 2>,
+    ) {
+        self.view().matmul_mat(alpha, x, beta, y);
+    }
+
+    /// Return the diagonal of the matrix.
+    pub fn diagonal(&self) -> Vec<Item> {
+        self.view().diagonal()
+    }
+
+    /// Return the matrix as a dense two-dimensional array.
+    pub fn to_dense(&self) -> DynamicArray<Item, 2> {
+        self.view().to_dense()
+    }
+
+    /// Return a copy of the matrix with index type `J`.
+    ///
+    /// Returns an error if an index or row pointer is not representable in `J`.
+    pub fn to_index_type<J: SparseIndex>(&self) -> RlstResult<CsrMatrix<Item, J>> {
+        Ok(CsrMatrix::new(
+            self.shape,
+            convert_indices(&self.indices)?,
+            convert_indices(&self.indptr)?,
+            self.data.clone(),
+        ))
+    }
+}
+
+impl<'a, Item: RlstScalar, I: SparseIndex> CsrMatrixView<'a, Item, I> {
+    /// Create a new view of the CSR arrays `indices`, `indptr` and `data`
+    pub fn new(shape: [usize; 2], indices: &'a [I], indptr: &'a [I], data: &'a [Item]) -> Self {
+        Self {
+            shape,
+            indices,
+            indptr,
+            data,
+        }
+    }
+
+    /// Number of element
+    pub fn nelems(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Indices of items
+    pub fn indices(&self) -> &'a [I] {
+        self.indices
+    }
+
+    /// Indices at which each row starts
+    pub fn indptr(&self) -> &'a [I] {
+        self.indptr
+    }
+
+    /// Entries of the matrix
+    pub fn data(&self) -> &'a [Item] {
+        self.data
+    }
+
+    /// Check that the compressed arrays describe a valid matrix, see [CsrMatrix::validate].
+    pub fn validate(&self) -> RlstResult<()> {
+        validate_compressed(
+            self.shape,
+            SparseMatType::Csr,
+            self.indptr,
+            self.indices,
+            self.data.len(),
+        )
+    }
+
+    /// Return statistics of the sparsity pattern and the entries of the matrix.
+    pub fn stats(&self) -> SparseStats {
+        SparseStats::from_compressed(
+            self.shape,
+            SparseMatType::Csr,
+            self.indptr,
+            self.indices,
+            self.data,
+        )
+    }
+
+    /// Return true if the matrix is symmetric up to `tol`, see [CsrMatrix::is_symmetric].
+    pub fn is_symmetric(&self, tol: Item::Real) -> bool {
+        self.shape[0] == self.shape[1]
+            && compressed_is_symmetric(self.indptr, self.indices, self.data, tol, false)
+    }
+
+    /// Return true if the matrix is Hermitian up to `tol`, see [CsrMatrix::is_hermitian].
+    pub fn is_hermitian(&self, tol: Item::Real) -> bool {
+        self.shape[0] == self.shape[1]
+            && compressed_is_symmetric(self.indptr, self.indices, self.data, tol, true)
+    }
+
+    /// Matrix multiplication
+    pub fn matmul(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        assert_eq!(self.shape[0], y.len());
+        assert_eq!(self.shape[1], x.len());
         for (row, out) in y.iter_mut().enumerate() {
             *out = beta * *out
                 + alpha * {
-                    let c1 = self.indptr()[row];
-                    let c2 = self.indptr()[1 + row];
+                    let c1 = self.indptr[row].to_usize();
+                    let c2 = self.indptr[1 + row].to_usize();
                     let mut acc = Item::zero();
 
                     for index in c1..c2 {
-                        let col = self.indices()[index];
-                        acc += self.data()[index] * x[col];
+                        let col = self.indices[index].to_usize();
+                        acc += self.data[index] * x[col];
                     }
                     acc
                 }
         }
     }
 
-    /// Convert to CSC matrix
-    pub fn into_csc(self) -> CscMatrix<Item> {
-        let mut rows = Vec::<usize>::with_capacity(self.nelems());
-        let mut cols = Vec::<usize>::with_capacity(self.nelems());
-        let mut data = Vec::<Item>::with_capacity(self.nelems());
+    /// Multiplication with the transpose of the matrix.
+    pub fn matmul_trans(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        self.matmul_trans_impl(alpha, x, beta, y, false);
+    }
+
+    /// Multiplication with the conjugate transpose of the matrix.
+    pub fn matmul_conj_trans(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        self.matmul_trans_impl(alpha, x, beta, y, true);
+    }
+
+    fn matmul_trans_impl(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item], conj: bool) {
+        assert_eq!(self.shape[1], y.len());
+        assert_eq!(self.shape[0], x.len());
+
+        y.iter_mut().for_each(|elem| *elem = beta * *elem);
+
+        for (row, (&row_start, &row_end)) in self.indptr.iter().tuple_windows().enumerate() {
+            let range = row_start.to_usize()..row_end.to_usize();
+            let x_elem = alpha * x[row];
+            for (&col, &elem) in self.indices[range.clone()]
+                .iter()
+                .zip(self.data[range].iter())
+            {
+                let elem = if conj { elem.conj() } else { elem };
+                y[col.to_usize()] += elem * x_elem;
+            }
+        }
+    }
+
+    /// Multiplication with a matrix of right-hand sides, see [CsrMatrix::matmul_mat].
+    pub fn matmul_mat<
+        ArrayImplX: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>,
+        ArrayImplY: UnsafeRandomAccessByValue<2, Item = Item>
+            + UnsafeRandomAccessMut<2, Item = Item>
+            + Shape<2>,
+    >(
+        &self,
+        alpha: Item,
+        x: &Array<Item, ArrayImplX, 2>,
+        beta: Item,
+        y: &mut Array<Item, ArrayImplY, 2>,
+    ) {
+        assert_eq!(self.shape[0], y.shape()[0]);
+        assert_eq!(self.shape[1], x.shape()[0]);
+        assert_eq!(x.shape()[1], y.shape()[1]);
+
+        for rhs in 0..x.shape()[1] {
+            for (row, (&row_start, &row_end)) in self.indptr.iter().tuple_windows().enumerate() {
+                let range = row_start.to_usize()..row_end.to_usize();
+                let mut acc = Item::zero();
+                for (&col, &elem) in self.indices[range.clone()]
+                    .iter()
+                    .zip(self.data[range].iter())
+                {
+                    acc += elem
+                        * x.get_value([col.to_usize(), rhs])
+                            .expect("Column index out of bounds.");
+                }
+                let out = y.get_mut([row, rhs]).expect("Row index out of bounds.");
+                *out = beta * *out + alpha * acc;
+            }
+        }
+    }
 
+    /// Return the diagonal of the matrix.
+    pub fn diagonal(&self) -> Vec<Item> {
+        compressed_diagonal(
+            self.shape[0].min(self.shape[1]),
+            self.indptr,
+            self.indices,
+            self.data,
+        )
+    }
+
+    /// Return the matrix as a dense two-dimensional array.
+    pub fn to_dense(&self) -> DynamicArray<Item, 2> {
+        let mut arr = DynamicArray::<Item, 2>::from_shape(self.shape);
         for (row, col, elem) in self.iter_aij() {
-            rows.push(row);
-            cols.push(col);
-            data.push(elem);
+            arr[[row, col]] += elem;
+        }
+        arr
+    }
+
+    /// Copy the arrays into an owned CSR matrix.
+    pub fn into_owned(self) -> CsrMatrix<Item, I> {
+        CsrMatrix::new(
+            self.shape,
+            self.indices.to_vec(),
+            self.indptr.to_vec(),
+            self.data.to_vec(),
+        )
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CsrMatrix<Item, I> {
+    /// Return `alpha * self + beta * other`.
+    ///
+    /// The sparsity pattern of the result is the union of the patterns of both matrices
+    /// with numerically zero entries removed.
+    pub fn add(&self, other: &Self, alpha: Item, beta: Item) -> Self {
+        assert_eq!(
+            self.shape, other.shape,
+            "Matrices must have the same shape. {:?} != {:?}",
+            self.shape, other.shape
+        );
+        let (indptr, indices, data) = add_compressed(
+            self.shape[1],
+            (&self.indptr, &self.indices, &self.data),
+            alpha,
+            (&other.indptr, &other.indices, &other.data),
+            beta,
+        );
+        Self::new(self.shape, indices, indptr, data)
+    }
+
+    /// Multiply all entries of the matrix by `alpha`.
+    pub fn scale_inplace(&mut self, alpha: Item) {
+        self.data.iter_mut().for_each(|elem| *elem *= alpha);
+        eliminate_zeros(&mut self.indptr, &mut self.indices, &mut self.data);
+    }
+
+    /// Return the transpose of the matrix.
+    pub fn transpose(&self) -> Self {
+        let (indptr, indices, data) =
+            transpose_compressed(self.shape[1], &self.indptr, &self.indices, &self.data);
+        Self::new([self.shape[1], self.shape[0]], indices, indptr, data)
+    }
+
+    /// Return the conjugate transpose of the matrix.
+    pub fn conj_transpose(&self) -> Self {
+        let mut result = self.transpose();
+        result.data.iter_mut().for_each(|elem| *elem = elem.conj());
+        result
+    }
+
+    /// Replace the diagonal of the matrix by `diag`.
+    ///
+    /// Missing diagonal entries are inserted and zero entries of `diag` are removed.
+    pub fn set_diagonal(&mut self, diag: &[Item]) {
+        assert_eq!(
+            diag.len(),
+            self.shape[0].min(self.shape[1]),
+            "Diagonal has wrong length. {} != {}",
+            diag.len(),
+            self.shape[0].min(self.shape[1])
+        );
+        let (indptr, indices, data) =
+            set_compressed_diagonal(diag, &self.indptr, &self.indices, &self.data);
+        self.indptr = indptr;
+        self.indices = indices;
+        self.data = data;
+    }
+
+    /// Multiply row `i` of the matrix by `scale[i]`.
+    pub fn scale_rows(&mut self, scale: &[Item]) {
+        assert_eq!(
+            scale.len(),
+            self.shape[0],
+            "Scaling vector has wrong length. {} != {}",
+            scale.len(),
+            self.shape[0]
+        );
+        for (ptr, &factor) in scale.iter().enumerate() {
+            self.data[self.indptr[ptr].to_usize()..self.indptr[1 + ptr].to_usize()]
+                .iter_mut()
+                .for_each(|elem| *elem *= factor);
+        }
+        eliminate_zeros(&mut self.indptr, &mut self.indices, &mut self.data);
+    }
+
+    /// Multiply column `j` of the matrix by `scale[j]`.
+    pub fn scale_cols(&mut self, scale: &[Item]) {
+        assert_eq!(
+            scale.len(),
+            self.shape[1],
+            "Scaling vector has wrong length. {} != {}",
+            scale.len(),
+            self.shape[1]
+        );
+        for (elem, &index) in self.data.iter_mut().zip(self.indices.iter()) {
+            *elem *= scale[index.to_usize()];
+        }
+        eliminate_zeros(&mut self.indptr, &mut self.indices, &mut self.data);
+    }
+
+    /// Return the submatrix `A[rows, cols]`.
+    ///
+    /// Entry `(i, j)` of the result is entry `(rows[i], cols[j])` of the matrix. Rows may be selected more than once, columns must be distinct.
+    /// For a contiguous range of rows [CsrMatrix::row_range] is faster.
+    pub fn submatrix(&self, rows: &[usize], cols: &[usize]) -> Self {
+        let (indptr, indices, data) = extract_compressed(
+            self.shape[1],
+            rows,
+            cols,
+            &self.indptr,
+            &self.indices,
+            &self.data,
+        );
+        Self::new([rows.len(), cols.len()], indices, indptr, data)
+    }
+
+    /// Return the symmetric permutation `P A P^T`.
+    ///
+    /// Entry `(i, j)` of the result is entry `(permutation[i], permutation[j])` of the matrix.
+    pub fn permute(&self, permutation: &[usize]) -> Self {
+        assert_eq!(
+            self.shape[0], self.shape[1],
+            "Matrix must be square. {} != {}",
+            self.shape[0], self.shape[1]
+        );
+        assert_permutation(permutation, self.shape[0]);
+        self.submatrix(permutation, permutation)
+    }
+
+    /// Convert to CSC matrix
+    ///
+    /// Explicitly stored zeros are removed.
+    pub fn into_csc(self) -> CscMatrix<Item, I> {
+        // The CSC arrays of the matrix are the CSR arrays of its transpose.
+        let (mut indptr, mut indices, mut data) =
+            transpose_compressed(self.shape[1], &self.indptr, &self.indices, &self.data);
+        eliminate_zeros(&mut indptr, &mut indices, &mut data);
+        CscMatrix::new(self.shape, indices, indptr, data)
+    }
+}
+
+impl<Item: RlstScalar> CsrMatrix<Item> {
+    /// Create a CSR matrix from a dense two-dimensional array.
+    ///
+    /// Entries whose absolute value is not larger than `drop_tol` are not stored. With a
+    /// `drop_tol` of zero all nonzero entries are stored. NaN entries are always stored.
+    pub fn from_dense<ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>>(
+        arr: &Array<Item, ArrayImpl, 2>,
+        drop_tol: Item::Real,
+    ) -> Self {
+        let shape = arr.shape();
+        let mut indptr = Vec::<usize>::with_capacity(1 + shape[0]);
+        let mut indices = Vec::<usize>::new();
+        let mut data = Vec::<Item>::new();
+
+        indptr.push(0);
+        for ptr in 0..shape[0] {
+            for index in 0..shape[1] {
+                let elem = unsafe { arr.get_value_unchecked([ptr, index]) };
+                // Written this way so that NaN entries are kept.
+                #[allow(clippy::neg_cmp_op_on_partial_ord)]
+                if !(elem.abs() <= drop_tol) {
+                    indices.push(index);
+                    data.push(elem);
+                }
+            }
+            indptr.push(indices.len());
         }
 
-        CscMatrix::from_aij(self.shape(), &rows, &cols, &data).unwrap()
+        Self::new(shape, indices, indptr, data)
     }
 
     /// Create CSR matrix from rows, columns and data
@@ -106,22 +566,21 @@ impl<Item: RlstScalar> CsrMatrix<Item> {
     ) -> RlstResult<Self> {
         let (rows, cols, data) = normalize_aij(rows, cols, data, SparseMatType::Csr);
 
-        let max_col = cols.iter().max().unwrap();
-        let max_row = rows.last().unwrap();
-
-        assert!(
-            *max_col < shape[1],
-            "Maximum column {} must be smaller than `shape.1` {}",
-            max_col,
-            shape[1]
-        );
-
-        assert!(
-            *max_row < shape[0],
-            "Maximum row {} must be smaller than `shape.0` {}",
-            max_row,
-            shape[0]
-        );
+        if let (Some(max_col), Some(max_row)) = (cols.iter().max(), rows.iter().max()) {
+            assert!(
+                *max_col < shape[1],
+                "Maximum column {} must be smaller than `shape.1` {}",
+                max_col,
+                shape[1]
+            );
+
+            assert!(
+                *max_row < shape[0],
+                "Maximum row {} must be smaller than `shape.0` {}",
+                max_row,
+                shape[0]
+            );
+        }
 
         let nelems = data.len();
 
@@ -140,21 +599,62 @@ impl<Item: RlstScalar> CsrMatrix<Item> {
     }
 }
 
+impl<'a, Item: RlstScalar, I: SparseIndex> CsrRowRange<'a, Item, I> {
+    /// Number of elements
+    pub fn nelems(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Column indices of the entries
+    pub fn indices(&self) -> &'a [I] {
+        self.indices
+    }
+
+    /// Indices at which each row starts
+    pub fn indptr(&self) -> &[I] {
+        &self.indptr
+    }
+
+    /// Entries of the rows
+    pub fn data(&self) -> &'a [Item] {
+        self.data
+    }
+
+    /// Return a view of the rows as a CSR matrix.
+    pub fn view(&self) -> CsrMatrixView<'_, Item, I> {
+        CsrMatrixView::new(self.shape, self.indices, &self.indptr, self.data)
+    }
+
+    /// Copy the rows into an owned CSR matrix.
+    pub fn into_owned(self) -> CsrMatrix<Item, I> {
+        CsrMatrix::new(
+            self.shape,
+            self.indices.to_vec(),
+            self.indptr,
+            self.data.to_vec(),
+        )
+    }
+}
+
 /// CSR iterator
-pub struct CsrAijIterator<'a, Item: RlstScalar> {
-    mat: &'a CsrMatrix<Item>,
+pub struct CsrAijIterator<'a, Item: RlstScalar, I: SparseIndex = usize> {
+    mat: CsrMatrixView<'a, Item, I>,
     row: usize,
     pos: usize,
 }
 
-impl<'a, Item: RlstScalar> CsrAijIterator<'a, Item> {
+impl<'a, Item: RlstScalar, I: SparseIndex> CsrAijIterator<'a, Item, I> {
     /// Create a new iterator
-    pub fn new(mat: &'a CsrMatrix<Item>) -> Self {
+    pub fn new(mat: &'a CsrMatrix<Item, I>) -> Self {
+        Self::from_view(mat.view())
+    }
+
+    fn from_view(mat: CsrMatrixView<'a, Item, I>) -> Self {
         // We need to move the row pointer to the first row that has at least one element.
 
         let mut row: usize = 0;
 
-        while row < mat.shape()[0] && mat.indptr[row] == mat.indptr[1 + row] {
+        while row < mat.shape[0] && mat.indptr[row] == mat.indptr[1 + row] {
             row += 1;
         }
 
@@ -162,24 +662,24 @@ impl<'a, Item: RlstScalar> CsrAijIterator<'a, Item> {
     }
 }
 
-impl<'a, Item: RlstScalar> std::iter::Iterator for CsrAijIterator<'a, Item> {
+impl<Item: RlstScalar, I: SparseIndex> std::iter::Iterator for CsrAijIterator<'_, Item, I> {
     type Item = (usize, usize, Item);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos == self.mat.data().len() {
+        if self.pos == self.mat.data.len() {
             return None;
         }
 
         let result = Some((
             self.row,
-            *self.mat.indices().get(self.pos).unwrap(),
-            *self.mat.data().get(self.pos).unwrap(),
+            self.mat.indices[self.pos].to_usize(),
+            self.mat.data[self.pos],
         ));
 
         self.pos += 1;
 
         // The following jumps over all zero rows to the next relevant row
-        while self.row < self.mat.shape()[0] && self.mat.indptr()[1 + self.row] <= self.pos {
+        while self.row < self.mat.shape[0] && self.mat.indptr[1 + self.row].to_usize() <= self.pos {
             self.row += 1;
         }
 
@@ -190,20 +690,59 @@ impl<'a, Item: RlstScalar> std::iter::Iterator for CsrAijIterator<'a, Item> {
     where
         Self: Sized,
     {
-        self.mat.data().len()
+        self.mat.data.len()
     }
 }
 
-impl<Item: RlstScalar> AijIterator for CsrMatrix<Item> {
+impl<Item: RlstScalar, I: SparseIndex> AijIterator for CsrMatrix<Item, I> {
     type Item = Item;
-    type Iter<'a> = CsrAijIterator<'a, Item> where Self: 'a;
+    type Iter<'a>
+        = CsrAijIterator<'a, Item, I>
+    where
+        Self: 'a;
 
     fn iter_aij(&self) -> Self::Iter<'_> {
         CsrAijIterator::new(self)
     }
 }
 
-impl<Item: RlstScalar> Shape<2> for CsrMatrix<Item> {
+impl<'a, Item: RlstScalar, I: SparseIndex> AijIterator for CsrMatrixView<'a, Item, I> {
+    type Item = Item;
+    type Iter<'b>
+        = CsrAijIterator<'b, Item, I>
+    where
+        Self: 'b;
+
+    fn iter_aij(&self) -> Self::Iter<'_> {
+        CsrAijIterator::from_view(*self)
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> Shape<2> for CsrMatrix<Item, I> {
+    fn shape(&self) -> [usize; 2] {
+        self.shape
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> Shape<2> for CsrMatrixView<'_, Item, I> {
+    fn shape(&self) -> [usize; 2] {
+        self.shape
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> AijIterator for CsrRowRange<'_, Item, I> {
+    type Item = Item;
+    type Iter<'b>
+        = CsrAijIterator<'b, Item, I>
+    where
+        Self: 'b;
+
+    fn iter_aij(&self) -> Self::Iter<'_> {
+        CsrAijIterator::from_view(self.view())
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> Shape<2> for CsrRowRange<'_, Item, I> {
     fn shape(&self) -> [usize; 2] {
         self.shape
     }
@@ -9,19 +9,25 @@ use crate::sparse::sparse_mat::SparseMatType;
 use crate::sparse::traits::index_layout::IndexLayout;
 
 use crate::sparse::distributed_vector::DistributedVector;
-use mpi::traits::{Communicator, Equivalence, Root};
+use mpi::collective::SystemOperation;
+use mpi::datatype::{Partition, PartitionMut};
+use mpi::traits::{Communicator, CommunicatorCollectives, Equivalence, Root};
 
 use crate::dense::traits::Shape;
 use crate::dense::traits::{RawAccess, RawAccessMut};
-use crate::dense::types::RlstScalar;
+use crate::dense::types::{RlstError, RlstResult, RlstScalar};
 
 /// Distributed CSR matrix
+///
+/// Each process stores its rows split into two blocks. The diagonal block contains the columns
+/// owned by the process in the domain layout. The off-diagonal block contains the remaining
+/// columns, indexed by their position in the received ghost values. This allows to multiply
+/// with the diagonal block while the ghost values are still being communicated.
 pub struct MpiCsrMatrix<'a, T: RlstScalar + Equivalence, C: Communicator> {
     mat_type: SparseMatType,
     shape: [usize; 2],
-    local_matrix: CsrMatrix<T>,
-    global_indices: Vec<usize>,
-    local_dof_count: usize,
+    diagonal_block: CsrMatrix<T>,
+    off_diagonal_block: CsrMatrix<T>,
     domain_layout: &'a DefaultMpiIndexLayout<'a, C>,
     range_layout: &'a DefaultMpiIndexLayout<'a, C>,
     domain_ghosts: crate::sparse::ghost_communicator::GhostCommunicator,
@@ -40,52 +46,73 @@ impl<'a, T: RlstScalar + Equivalence, C: Communicator> MpiCsrMatrix<'a, T, C> {
     ) -> Self {
         let my_rank = comm.rank() as usize;
 
-        let domain_ghost_dofs: Vec<usize> = indices
+        // A ghost column may appear in several local rows. We only want to receive it once.
+        let mut domain_ghost_dofs: Vec<usize> = indices
             .iter()
             .copied()
             .filter(|&dof| domain_layout.rank_from_index(dof).unwrap() != my_rank)
             .collect();
+        domain_ghost_dofs.sort_unstable();
+        domain_ghost_dofs.dedup();
 
         let domain_ghosts = GhostCommunicator::new(&domain_ghost_dofs, domain_layout, comm);
-        let local_dof_count =
-            domain_layout.number_of_local_indices() + domain_ghosts.total_receive_count;
-        let mut index_mapper = HashMap::new();
-
-        // We need to transform the indices vector of the CSR matrix from global indexing to
-        // local indexing. For this we assume that the input vector has the format
-        // [local_indices..., ghost_indices]. So we map global indices to fit this format.
-        // To do this we create a hash map that takes the global indices and maps to the new
-        // local indexing.
-
-        let mut count: usize = 0;
-        for index in domain_layout.local_range().0..domain_layout.local_range().1 {
-            index_mapper.insert(index, count);
-            count += 1;
-        }
-        for index in &domain_ghosts.global_receive_indices {
-            index_mapper.insert(*index, count);
-            count += 1;
-        }
 
-        // The hash map is created. We now iterate through the indices vector of the sparse matrix
-        // to change the indexing.
+        // We need to transform the column indices from global indexing to local indexing.
+        // Owned columns are mapped into the diagonal block and ghost columns are mapped to
+        // their position in the received ghost values, which are the columns of the
+        // off-diagonal block.
 
-        let mapped_indices = indices
+        let (first_dof, last_dof) = domain_layout.local_range();
+        let ghost_mapper = domain_ghosts
+            .global_receive_indices
             .iter()
-            .map(|elem| *index_mapper.get(elem).unwrap())
-            .collect::<Vec<_>>();
+            .enumerate()
+            .map(|(position, &index)| (index, position))
+            .collect::<HashMap<_, _>>();
+
+        let nrows = indptr.len() - 1;
+
+        let mut diagonal_indptr = Vec::<usize>::with_capacity(1 + nrows);
+        let mut diagonal_indices = Vec::<usize>::new();
+        let mut diagonal_data = Vec::<T>::new();
+
+        let mut off_diagonal_indptr = Vec::<usize>::with_capacity(1 + nrows);
+        let mut off_diagonal_indices = Vec::<usize>::new();
+        let mut off_diagonal_data = Vec::<T>::new();
+
+        diagonal_indptr.push(0);
+        off_diagonal_indptr.push(0);
+
+        for row in 0..nrows {
+            for index in indptr[row]..indptr[1 + row] {
+                let col = indices[index];
+                if (first_dof..last_dof).contains(&col) {
+                    diagonal_indices.push(col - first_dof);
+                    diagonal_data.push(data[index]);
+                } else {
+                    off_diagonal_indices.push(*ghost_mapper.get(&col).unwrap());
+                    off_diagonal_data.push(data[index]);
+                }
+            }
+            diagonal_indptr.push(diagonal_indices.len());
+            off_diagonal_indptr.push(off_diagonal_indices.len());
+        }
 
         Self {
             mat_type: SparseMatType::Csr,
             shape,
-            local_matrix: CsrMatrix::new(
-                [indptr.len() - 1, shape[1]],
-                mapped_indices,
-                indptr,
-                data,
+            diagonal_block: CsrMatrix::new(
+                [nrows, last_dof - first_dof],
+                diagonal_indices,
+                diagonal_indptr,
+                diagonal_data,
+            ),
+            off_diagonal_block: CsrMatrix::new(
+                [nrows, domain_ghosts.total_receive_count],
+                off_diagonal_indices,
+                off_diagonal_indptr,
+                off_diagonal_data,
             ),
-            global_indices: indices,
-            local_dof_count,
             domain_layout,
             range_layout,
             domain_ghosts,
@@ -99,22 +126,57 @@ impl<'a, T: RlstScalar + Equivalence, C: Communicator> MpiCsrMatrix<'a, T, C> {
 
     /// Local shape
     pub fn local_shape(&self) -> [usize; 2] {
-        self.local_matrix.shape()
+        [self.diagonal_block.shape()[0], self.shape[1]]
     }
 
-    /// Column indices
-    pub fn indices(&self) -> &[usize] {
-        &self.global_indices
+    /// Local rows with global column indices, assembled from the diagonal and off-diagonal blocks
+    ///
+    /// The column indices in each row are sorted.
+    pub fn to_local_csr(&self) -> CsrMatrix<T> {
+        let first_dof = self.domain_layout.local_range().0;
+        let diagonal = &self.diagonal_block;
+        let off_diagonal = &self.off_diagonal_block;
+        let nrows = diagonal.shape()[0];
+
+        let mut indptr = Vec::<usize>::with_capacity(1 + nrows);
+        let mut indices = Vec::<usize>::with_capacity(diagonal.nelems() + off_diagonal.nelems());
+        let mut data = Vec::<T>::with_capacity(diagonal.nelems() + off_diagonal.nelems());
+        let mut row_entries = Vec::<(usize, T)>::new();
+
+        indptr.push(0);
+        for row in 0..nrows {
+            row_entries.clear();
+            for index in diagonal.indptr()[row]..diagonal.indptr()[1 + row] {
+                row_entries.push((
+                    first_dof + diagonal.indices()[index],
+                    diagonal.data()[index],
+                ));
+            }
+            for index in off_diagonal.indptr()[row]..off_diagonal.indptr()[1 + row] {
+                row_entries.push((
+                    self.domain_ghosts.global_receive_indices[off_diagonal.indices()[index]],
+                    off_diagonal.data()[index],
+                ));
+            }
+            row_entries.sort_unstable_by_key(|&(col, _)| col);
+            for &(col, value) in &row_entries {
+                indices.push(col);
+                data.push(value);
+            }
+            indptr.push(indices.len());
+        }
+
+        CsrMatrix::new(self.local_shape(), indices, indptr, data)
     }
 
-    /// Indices at which each row starts
-    pub fn indptr(&self) -> &[usize] {
-        self.local_matrix.indptr()
+    /// Block of the local rows with the columns owned by this process
+    pub fn diagonal_block(&self) -> &CsrMatrix<T> {
+        &self.diagonal_block
     }
 
-    /// Matrix entries
-    pub fn data(&self) -> &[T] {
-        self.local_matrix.data()
+    /// Block of the local rows with the ghost columns
+    pub fn off_diagonal_block(&self) -> &CsrMatrix<T> {
+        &self.off_diagonal_block
     }
 
     /// Domain layout
@@ -269,6 +331,180 @@ impl<'a, T: RlstScalar + Equivalence, C: Communicator> MpiCsrMatrix<'a, T, C> {
         )
     }
 
+    /// Create a distributed matrix from `(row, column, value)` triplets.
+    ///
+    /// Every process may contribute entries to any row of the matrix. Entries are sent to the
+    /// process that owns their row in `range_layout` and duplicate entries are summed up. This
+    /// is a collective operation. If any process passes invalid triplets all processes return
+    /// an error.
+    pub fn from_aij(
+        shape: [usize; 2],
+        rows: &[usize],
+        cols: &[usize],
+        data: &[T],
+        domain_layout: &'a DefaultMpiIndexLayout<'a, C>,
+        range_layout: &'a DefaultMpiIndexLayout<'a, C>,
+        comm: &'a C,
+    ) -> RlstResult<Self> {
+        let size = comm.size() as usize;
+
+        // Check the local triplets and find the owning process of each row.
+
+        let mut owners = Vec::<usize>::with_capacity(rows.len());
+        let mut local_error = None;
+
+        if rows.len() != cols.len() || rows.len() != data.len() {
+            local_error = Some(RlstError::GeneralError(format!(
+                "rows, cols and data have lengths {}, {} and {}.",
+                rows.len(),
+                cols.len(),
+                data.len()
+            )));
+        } else if shape[0] != range_layout.number_of_global_indices()
+            || shape[1] != domain_layout.number_of_global_indices()
+        {
+            local_error = Some(RlstError::IndexLayoutError(format!(
+                "Matrix shape {:?} does not match the layouts with {} rows and {} columns.",
+                shape,
+                range_layout.number_of_global_indices(),
+                domain_layout.number_of_global_indices()
+            )));
+        } else {
+            for (&row, &col) in rows.iter().zip(cols.iter()) {
+                if col >= shape[1] {
+                    local_error = Some(RlstError::IndexLayoutError(format!(
+                        "Column index {} is out of bounds for {} columns.",
+                        col, shape[1]
+                    )));
+                    break;
+                }
+                if let Some(rank) = range_layout.rank_from_index(row) {
+                    owners.push(rank);
+                } else {
+                    local_error = Some(RlstError::IndexLayoutError(format!(
+                        "Row index {} is not owned by any process.",
+                        row
+                    )));
+                    break;
+                }
+            }
+        }
+
+        // All processes need to agree on whether to continue. Otherwise the processes
+        // with valid data would wait forever in the exchange below.
+
+        let local_flag = i32::from(local_error.is_some());
+        let mut global_flag: i32 = 0;
+        comm.all_reduce_into(&local_flag, &mut global_flag, SystemOperation::max());
+
+        if let Some(error) = local_error {
+            return Err(error);
+        }
+        if global_flag != 0 {
+            return Err(RlstError::GeneralError(
+                "Invalid triplets on another process.".to_string(),
+            ));
+        }
+
+        // Sort the triplets by the owning process and count how many go to each process.
+
+        let mut send_counts = vec![0_i32; size];
+        for &rank in &owners {
+            send_counts[rank] += 1;
+        }
+
+        let mut send_displacements = Vec::<i32>::with_capacity(size);
+        let mut count = 0;
+        for &n in &send_counts {
+            send_displacements.push(count);
+            count += n;
+        }
+
+        let mut send_rows = vec![0; rows.len()];
+        let mut send_cols = vec![0; rows.len()];
+        let mut send_data = vec![T::zero(); rows.len()];
+        let mut position = send_displacements
+            .iter()
+            .map(|&d| d as usize)
+            .collect::<Vec<_>>();
+
+        for (index, &rank) in owners.iter().enumerate() {
+            let pos = position[rank];
+            send_rows[pos] = rows[index];
+            send_cols[pos] = cols[index];
+            send_data[pos] = data[index];
+            position[rank] += 1;
+        }
+
+        // Tell every process how many triplets it receives from us.
+
+        let mut receive_counts = vec![0_i32; size];
+        comm.all_to_all_into(&send_counts, &mut receive_counts);
+
+        let mut receive_displacements = Vec::<i32>::with_capacity(size);
+        let mut count = 0;
+        for &n in &receive_counts {
+            receive_displacements.push(count);
+            count += n;
+        }
+        let receive_total = count as usize;
+
+        // Now send the triplets to their owners.
+
+        let mut receive_rows = vec![0; receive_total];
+        let mut receive_cols = vec![0; receive_total];
+        let mut receive_data = vec![T::zero(); receive_total];
+
+        comm.all_to_all_varcount_into(
+            &Partition::new(&send_rows[..], &send_counts[..], &send_displacements[..]),
+            &mut PartitionMut::new(
+                &mut receive_rows[..],
+                &receive_counts[..],
+                &receive_displacements[..],
+            ),
+        );
+        comm.all_to_all_varcount_into(
+            &Partition::new(&send_cols[..], &send_counts[..], &send_displacements[..]),
+            &mut PartitionMut::new(
+                &mut receive_cols[..],
+                &receive_counts[..],
+                &receive_displacements[..],
+            ),
+        );
+        comm.all_to_all_varcount_into(
+            &Partition::new(&send_data[..], &send_counts[..], &send_displacements[..]),
+            &mut PartitionMut::new(
+                &mut receive_data[..],
+                &receive_counts[..],
+                &receive_displacements[..],
+            ),
+        );
+
+        // All received rows are owned by this process. Shift them to local row indices and
+        // assemble the local rows. This sums up duplicate entries.
+
+        let (first_row, last_row) = range_layout.local_range();
+        receive_rows.iter_mut().for_each(|row| *row -= first_row);
+
+        let local_matrix = CsrMatrix::from_aij(
+            [last_row - first_row, shape[1]],
+            &receive_rows,
+            &receive_cols,
+            &receive_data,
+        )?;
+        let (indices, indptr, data) = local_matrix.into_parts();
+
+        Ok(Self::new(
+            shape,
+            indices,
+            indptr,
+            data,
+            domain_layout,
+            range_layout,
+            comm,
+        ))
+    }
+
     /// Matrix multiplication
     pub fn matmul<'b>(
         &self,
@@ -277,21 +513,42 @@ impl<'a, T: RlstScalar + Equivalence, C: Communicator> MpiCsrMatrix<'a, T, C> {
         beta: T,
         y: &mut DistributedVector<'b, T, C>,
     ) {
-        // Create a vector that combines local dofs and ghosts
+        let x_local = x.local();
+        let mut y_local = y.local_mut();
+        let mut ghost_data = vec![T::zero(); self.domain_ghosts.total_receive_count];
+
+        // Multiply with the diagonal block while the ghosts are in flight, then add the
+        // contribution of the ghosts.
+
+        self.domain_ghosts
+            .forward_send_ghosts_overlapped(x_local.data(), &mut ghost_data, || {
+                self.diagonal_block
+                    .matmul(alpha, x_local.data(), beta, y_local.data_mut())
+            });
 
-        let mut local_vec = Vec::<T>::with_capacity(self.local_dof_count);
+        self.off_diagonal_block
+            .matmul(alpha, &ghost_data, T::one(), y_local.data_mut());
+    }
+
+    /// Matrix multiplication that receives all ghosts before multiplying with the local matrix
+    pub fn matmul_blocking<'b>(
+        &self,
+        alpha: T,
+        x: &DistributedVector<'b, T, C>,
+        beta: T,
+        y: &mut DistributedVector<'b, T, C>,
+    ) {
         let x_local = x.local();
-        let x_data = x_local.data();
+        let mut y_local = y.local_mut();
         let mut ghost_data = vec![T::zero(); self.domain_ghosts.total_receive_count];
 
-        local_vec.extend(x_data.iter().copied());
         self.domain_ghosts
-            .forward_send_ghosts(x_data, &mut ghost_data);
-        local_vec.extend(ghost_data.iter());
+            .forward_send_ghosts(x_local.data(), &mut ghost_data);
 
-        // Compute result
-        self.local_matrix
-            .matmul(alpha, local_vec.as_slice(), beta, y.local_mut().data_mut());
+        self.diagonal_block
+            .matmul(alpha, x_local.data(), beta, y_local.data_mut());
+        self.off_diagonal_block
+            .matmul(alpha, &ghost_data, T::one(), y_local.data_mut());
     }
 }
 
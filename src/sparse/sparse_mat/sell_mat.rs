@@ -0,0 +1,279 @@
+//! Definition of SELL-C-σ matrices.
+//!
+//! A sliced ELLPACK (SELL-C-σ) matrix groups `C` consecutive rows into a slice. Each
+//! slice is padded to its longest row and stored column by column, such that one column
+//! of a slice fills one SIMD register. The slice height `C` is the SIMD width of the
+//! current architecture as used by [RlstSimd].
+//!
+//! To reduce the padding, rows are sorted by decreasing length within windows of `σ`
+//! consecutive rows before they are grouped into slices.
+
+use crate::dense::simd::{RlstSimd, SimdFor};
+use crate::dense::traits::Shape;
+use crate::dense::types::RlstScalar;
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::SparseIndex;
+
+/// Return the number of entries of type `Item` in a SIMD register of the current architecture.
+pub fn simd_slice_height<Item: RlstSimd>() -> usize {
+    struct Impl<Item> {
+        _marker: std::marker::PhantomData<Item>,
+    }
+
+    impl<Item: RlstSimd> pulp::WithSimd for Impl<Item> {
+        type Output = usize;
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            SimdFor::<Item, S>::new(simd).simd_vector_width()
+        }
+    }
+
+    pulp::Arch::new().dispatch(Impl::<Item> {
+        _marker: std::marker::PhantomData,
+    })
+}
+
+/// A SELL-C-σ matrix
+#[derive(Clone)]
+pub struct SellMatrix<Item: RlstScalar + RlstSimd> {
+    shape: [usize; 2],
+    slice_height: usize,
+    sigma: usize,
+    permutation: Vec<usize>,
+    slice_ptr: Vec<usize>,
+    indices: Vec<usize>,
+    data: Vec<Item>,
+}
+
+impl<Item: RlstScalar + RlstSimd> SellMatrix<Item> {
+    /// Create a SELL-C-σ matrix from a CSR matrix.
+    ///
+    /// Rows are sorted by decreasing length within windows of `sigma` rows. For `sigma = 1`
+    /// the row order is kept.
+    pub fn from_csr<I: SparseIndex>(csr: &CsrMatrix<Item, I>, sigma: usize) -> Self {
+        assert!(sigma > 0, "The sorting window sigma must be positive.");
+
+        let shape = csr.shape();
+        let slice_height = simd_slice_height::<Item>();
+        let nslices = shape[0].div_ceil(slice_height);
+        let row_length =
+            |row: usize| csr.indptr()[1 + row].to_usize() - csr.indptr()[row].to_usize();
+
+        let mut permutation = (0..shape[0]).collect::<Vec<_>>();
+        for window in permutation.chunks_mut(sigma) {
+            window.sort_by_key(|&row| std::cmp::Reverse(row_length(row)));
+        }
+
+        let mut slice_ptr = Vec::<usize>::with_capacity(1 + nslices);
+        slice_ptr.push(0);
+        for rows in permutation.chunks(slice_height) {
+            let width = rows.iter().map(|&row| row_length(row)).max().unwrap();
+            slice_ptr.push(slice_ptr.last().unwrap() + width * slice_height);
+        }
+
+        // Padding entries are zeros in column 0.
+        let mut indices = vec![0; *slice_ptr.last().unwrap()];
+        let mut data = vec![Item::zero(); indices.len()];
+
+        for (slice, rows) in permutation.chunks(slice_height).enumerate() {
+            for (lane, &row) in rows.iter().enumerate() {
+                for (k, pos) in
+                    (csr.indptr()[row].to_usize()..csr.indptr()[1 + row].to_usize()).enumerate()
+                {
+                    let index = slice_ptr[slice] + k * slice_height + lane;
+                    indices[index] = csr.indices()[pos].to_usize();
+                    data[index] = csr.data()[pos];
+                }
+            }
+        }
+
+        Self {
+            shape,
+            slice_height,
+            sigma,
+            permutation,
+            slice_ptr,
+            indices,
+            data,
+        }
+    }
+
+    /// Number of stored elements, including padding
+    pub fn nelems(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Number of slices
+    pub fn nslices(&self) -> usize {
+        self.slice_ptr.len() - 1
+    }
+
+    /// Number of rows in each slice
+    pub fn slice_height(&self) -> usize {
+        self.slice_height
+    }
+
+    /// Size of the window in which rows are sorted by length
+    pub fn sigma(&self) -> usize {
+        self.sigma
+    }
+
+    /// Original row index of each stored row
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// Indices at which each slice starts
+    pub fn slice_ptr(&self) -> &[usize] {
+        &self.slice_ptr
+    }
+
+    /// Column indices of the stored entries
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Stored entries
+    pub fn data(&self) -> &[Item] {
+        &self.data
+    }
+
+    /// Matrix multiplication
+    ///
+    /// Each slice is multiplied with SIMD instructions, processing one row per SIMD lane.
+    pub fn matmul(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        assert_eq!(self.shape()[0], y.len());
+        assert_eq!(self.shape()[1], x.len());
+
+        pulp::Arch::new().dispatch(SellMatmul {
+            mat: self,
+            alpha,
+            x,
+            beta,
+            y,
+        });
+    }
+
+    /// Multiply the slices without SIMD instructions.
+    fn matmul_scalar(&self, alpha: Item, x: &[Item], beta: Item, y: &mut [Item]) {
+        let slice_height = self.slice_height;
+        for (slice, rows) in self.permutation.chunks(slice_height).enumerate() {
+            let start = self.slice_ptr[slice];
+            let width = (self.slice_ptr[1 + slice] - start) / slice_height;
+            for (lane, &row) in rows.iter().enumerate() {
+                let mut acc = Item::zero();
+                for k in 0..width {
+                    let index = start + k * slice_height + lane;
+                    acc += self.data[index] * x[self.indices[index]];
+                }
+                y[row] = beta * y[row] + alpha * acc;
+            }
+        }
+    }
+
+    /// Convert to a CSR matrix
+    ///
+    /// Zero entries, including the padding, are not stored in the CSR matrix.
+    pub fn to_csr(&self) -> CsrMatrix<Item> {
+        let slice_height = self.slice_height;
+
+        // `position[row]` is the stored position of the original row `row`.
+        let mut position = vec![0; self.shape[0]];
+        for (pos, &row) in self.permutation.iter().enumerate() {
+            position[row] = pos;
+        }
+
+        let mut indptr = Vec::<usize>::with_capacity(1 + self.shape[0]);
+        let mut indices = Vec::<usize>::new();
+        let mut data = Vec::<Item>::new();
+
+        indptr.push(0);
+        for &pos in &position {
+            let slice = pos / slice_height;
+            let lane = pos % slice_height;
+            for index in
+                (self.slice_ptr[slice] + lane..self.slice_ptr[1 + slice]).step_by(slice_height)
+            {
+                if self.data[index] != Item::zero() {
+                    indices.push(self.indices[index]);
+                    data.push(self.data[index]);
+                }
+            }
+            indptr.push(indices.len());
+        }
+
+        CsrMatrix::new(self.shape, indices, indptr, data)
+    }
+}
+
+/// SIMD kernel for the product of a SELL-C-σ matrix with a vector.
+struct SellMatmul<'a, Item: RlstScalar + RlstSimd> {
+    mat: &'a SellMatrix<Item>,
+    alpha: Item,
+    x: &'a [Item],
+    beta: Item,
+    y: &'a mut [Item],
+}
+
+impl<Item: RlstScalar + RlstSimd> pulp::WithSimd for SellMatmul<'_, Item> {
+    type Output = ();
+
+    #[inline(always)]
+    fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+        let Self {
+            mat,
+            alpha,
+            x,
+            beta,
+            y,
+        } = self;
+
+        let simd = SimdFor::<Item, S>::new(simd);
+        let slice_height = mat.slice_height;
+
+        // The matrix may have been created for a different SIMD width.
+        if simd.simd_vector_width() != slice_height {
+            mat.matmul_scalar(alpha, x, beta, y);
+            return;
+        }
+
+        let mut gathered = vec![Item::zero(); slice_height];
+        let mut result = vec![Item::zero(); slice_height];
+
+        for (slice, rows) in mat.permutation.chunks(slice_height).enumerate() {
+            let start = mat.slice_ptr[slice];
+            let end = mat.slice_ptr[1 + slice];
+            let (values, _) = Item::as_simd_slice::<S>(&mat.data[start..end]);
+
+            let mut acc = simd.splat(Item::zero());
+            for (value, cols) in values
+                .iter()
+                .zip(mat.indices[start..end].chunks_exact(slice_height))
+            {
+                for (elem, &col) in gathered.iter_mut().zip(cols) {
+                    *elem = x[col];
+                }
+                acc = simd.mul_add(*value, Item::as_simd_slice::<S>(&gathered).0[0], acc);
+            }
+            Item::as_simd_slice_mut::<S>(&mut result).0[0] = acc;
+
+            for (&row, &value) in rows.iter().zip(result.iter()) {
+                y[row] = beta * y[row] + alpha * value;
+            }
+        }
+    }
+}
+
+impl<Item: RlstScalar + RlstSimd> Shape<2> for SellMatrix<Item> {
+    fn shape(&self) -> [usize; 2] {
+        self.shape
+    }
+}
+
+impl<Item: RlstScalar + RlstSimd, I: SparseIndex> CsrMatrix<Item, I> {
+    /// Convert to a SELL-C-σ matrix with sorting window `sigma`.
+    pub fn to_sell(&self, sigma: usize) -> SellMatrix<Item> {
+        SellMatrix::from_csr(self, sigma)
+    }
+}
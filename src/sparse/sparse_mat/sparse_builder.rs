@@ -0,0 +1,274 @@
+//! Incremental assembly of sparse matrices.
+//!
+//! A [SparseBuilder] collects entries one at a time or in dense blocks, as needed
+//! for example in finite element assembly loops, and then creates a CSR or CSC matrix.
+//!
+//! A builder either stores triplets (created with [SparseBuilder::new]) or adds values
+//! into a fixed sparsity pattern (created with [SparseBuilder::from_csr_pattern] or
+//! [SparseBuilder::from_csc_pattern]). The second mode never allocates during assembly.
+
+use crate::dense::array::Array;
+use crate::dense::traits::{Shape, UnsafeRandomAccessByValue};
+use crate::dense::types::RlstScalar;
+use crate::sparse::sparse_mat::csc_mat::CscMatrix;
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::tools::transpose_compressed;
+use crate::sparse::sparse_mat::SparseMatType;
+
+/// Storage of the entries of a [SparseBuilder].
+#[derive(Clone)]
+enum BuilderStorage<Item: RlstScalar> {
+    Triplets {
+        rows: Vec<usize>,
+        cols: Vec<usize>,
+        data: Vec<Item>,
+    },
+    Pattern {
+        mat_type: SparseMatType,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<Item>,
+    },
+}
+
+/// Builder for sparse matrices.
+#[derive(Clone)]
+pub struct SparseBuilder<Item: RlstScalar> {
+    shape: [usize; 2],
+    storage: BuilderStorage<Item>,
+}
+
+impl<Item: RlstScalar> SparseBuilder<Item> {
+    /// Create a new builder that collects triplets.
+    ///
+    /// Duplicate entries are summed up when the matrix is created.
+    pub fn new(shape: [usize; 2]) -> Self {
+        Self::with_capacity(shape, 0)
+    }
+
+    /// Create a new builder that collects triplets with space for `capacity` entries.
+    pub fn with_capacity(shape: [usize; 2], capacity: usize) -> Self {
+        Self {
+            shape,
+            storage: BuilderStorage::Triplets {
+                rows: Vec::with_capacity(capacity),
+                cols: Vec::with_capacity(capacity),
+                data: Vec::with_capacity(capacity),
+            },
+        }
+    }
+
+    /// Create a builder that adds values into the sparsity pattern of a CSR matrix.
+    ///
+    /// All values are initialised to zero. Adding an entry outside of the pattern panics.
+    pub fn from_csr_pattern(mat: &CsrMatrix<Item>) -> Self {
+        Self::from_pattern(mat.shape(), SparseMatType::Csr, mat.indptr(), mat.indices())
+    }
+
+    /// Create a builder that adds values into the sparsity pattern of a CSC matrix.
+    ///
+    /// All values are initialised to zero. Adding an entry outside of the pattern panics.
+    pub fn from_csc_pattern(mat: &CscMatrix<Item>) -> Self {
+        Self::from_pattern(mat.shape(), SparseMatType::Csc, mat.indptr(), mat.indices())
+    }
+
+    fn from_pattern(
+        shape: [usize; 2],
+        mat_type: SparseMatType,
+        indptr: &[usize],
+        indices: &[usize],
+    ) -> Self {
+        let mut indices = indices.to_vec();
+
+        // Sorted indices allow a binary search for each entry.
+        for (&start, &end) in indptr.iter().zip(indptr.iter().skip(1)) {
+            indices[start..end].sort_unstable();
+            assert!(
+                indices[start..end]
+                    .windows(2)
+                    .all(|pair| pair[0] != pair[1]),
+                "Sparsity pattern contains duplicate entries."
+            );
+        }
+
+        Self {
+            shape,
+            storage: BuilderStorage::Pattern {
+                mat_type,
+                indptr: indptr.to_vec(),
+                data: vec![Item::zero(); indices.len()],
+                indices,
+            },
+        }
+    }
+
+    /// Return true if the builder adds values into a fixed sparsity pattern.
+    pub fn has_pattern(&self) -> bool {
+        matches!(self.storage, BuilderStorage::Pattern { .. })
+    }
+
+    /// Number of stored entries.
+    ///
+    /// For a triplet builder this counts duplicate entries separately.
+    pub fn nelems(&self) -> usize {
+        match &self.storage {
+            BuilderStorage::Triplets { data, .. } => data.len(),
+            BuilderStorage::Pattern { data, .. } => data.len(),
+        }
+    }
+
+    /// Add `value` to the entry `(row, col)`.
+    pub fn push(&mut self, row: usize, col: usize, value: Item) {
+        assert!(
+            row < self.shape[0] && col < self.shape[1],
+            "Entry ({}, {}) out of bounds for matrix of shape {:?}",
+            row,
+            col,
+            self.shape
+        );
+
+        match &mut self.storage {
+            BuilderStorage::Triplets { rows, cols, data } => {
+                rows.push(row);
+                cols.push(col);
+                data.push(value);
+            }
+            BuilderStorage::Pattern {
+                mat_type,
+                indptr,
+                indices,
+                data,
+            } => {
+                let (ptr, index) = match mat_type {
+                    SparseMatType::Csr => (row, col),
+                    SparseMatType::Csc => (col, row),
+                };
+                let start = indptr[ptr];
+                let pos = indices[start..indptr[1 + ptr]]
+                    .binary_search(&index)
+                    .unwrap_or_else(|_| {
+                        panic!("Entry ({}, {}) is not in the sparsity pattern.", row, col)
+                    });
+                data[start + pos] += value;
+            }
+        }
+    }
+
+    /// Add a dense block to the entries `(rows[i], cols[j])`.
+    ///
+    /// The block must have shape `[rows.len(), cols.len()]`.
+    pub fn add_block<ArrayImpl: UnsafeRandomAccessByValue<2, Item = Item> + Shape<2>>(
+        &mut self,
+        rows: &[usize],
+        cols: &[usize],
+        block: &Array<Item, ArrayImpl, 2>,
+    ) {
+        assert_eq!(
+            block.shape(),
+            [rows.len(), cols.len()],
+            "Block has shape {:?} but expected {:?}",
+            block.shape(),
+            [rows.len(), cols.len()]
+        );
+
+        if let BuilderStorage::Triplets {
+            rows: all_rows,
+            cols: all_cols,
+            data,
+        } = &mut self.storage
+        {
+            let nelems = rows.len() * cols.len();
+            all_rows.reserve(nelems);
+            all_cols.reserve(nelems);
+            data.reserve(nelems);
+        }
+
+        for (col_index, &col) in cols.iter().enumerate() {
+            for (row_index, &row) in rows.iter().enumerate() {
+                self.push(row, col, unsafe {
+                    block.get_value_unchecked([row_index, col_index])
+                });
+            }
+        }
+    }
+
+    /// Remove all entries while keeping the allocated memory.
+    ///
+    /// For a builder with a fixed sparsity pattern all values are set to zero.
+    pub fn reset(&mut self) {
+        match &mut self.storage {
+            BuilderStorage::Triplets { rows, cols, data } => {
+                rows.clear();
+                cols.clear();
+                data.clear();
+            }
+            BuilderStorage::Pattern { data, .. } => {
+                data.iter_mut().for_each(|elem| *elem = Item::zero());
+            }
+        }
+    }
+
+    /// Create a CSR matrix from the assembled entries.
+    ///
+    /// For a triplet builder the entries are normalised with
+    /// [normalize_aij](crate::sparse::sparse_mat::tools::normalize_aij). For a builder with
+    /// a fixed sparsity pattern the pattern is kept, including entries that are zero.
+    pub fn finish_csr(&self) -> CsrMatrix<Item> {
+        match &self.storage {
+            BuilderStorage::Triplets { rows, cols, data } => {
+                CsrMatrix::from_aij(self.shape, rows, cols, data).unwrap()
+            }
+            BuilderStorage::Pattern {
+                mat_type: SparseMatType::Csr,
+                indptr,
+                indices,
+                data,
+            } => CsrMatrix::new(self.shape, indices.clone(), indptr.clone(), data.clone()),
+            BuilderStorage::Pattern {
+                mat_type: SparseMatType::Csc,
+                indptr,
+                indices,
+                data,
+            } => {
+                let (indptr, indices, data) =
+                    transpose_compressed(self.shape[0], indptr, indices, data);
+                CsrMatrix::new(self.shape, indices, indptr, data)
+            }
+        }
+    }
+
+    /// Create a CSC matrix from the assembled entries.
+    ///
+    /// For a triplet builder the entries are normalised with
+    /// [normalize_aij](crate::sparse::sparse_mat::tools::normalize_aij). For a builder with
+    /// a fixed sparsity pattern the pattern is kept, including entries that are zero.
+    pub fn finish_csc(&self) -> CscMatrix<Item> {
+        match &self.storage {
+            BuilderStorage::Triplets { rows, cols, data } => {
+                CscMatrix::from_aij(self.shape, rows, cols, data).unwrap()
+            }
+            BuilderStorage::Pattern {
+                mat_type: SparseMatType::Csc,
+                indptr,
+                indices,
+                data,
+            } => CscMatrix::new(self.shape, indices.clone(), indptr.clone(), data.clone()),
+            BuilderStorage::Pattern {
+                mat_type: SparseMatType::Csr,
+                indptr,
+                indices,
+                data,
+            } => {
+                let (indptr, indices, data) =
+                    transpose_compressed(self.shape[1], indptr, indices, data);
+                CscMatrix::new(self.shape, indices, indptr, data)
+            }
+        }
+    }
+}
+
+impl<Item: RlstScalar> Shape<2> for SparseBuilder<Item> {
+    fn shape(&self) -> [usize; 2] {
+        self.shape
+    }
+}
@@ -0,0 +1,290 @@
+//! Sparse matrix-matrix products.
+//!
+//! The product is computed in two phases. The symbolic phase [SpGemmSymbolic::new_csr]
+//! (or [SpGemmSymbolic::new_csc]) determines the sparsity pattern of the product. The
+//! numeric phase computes the values for this pattern. The symbolic phase only depends on
+//! the sparsity patterns of the factors and can be reused for factors with the same
+//! patterns but different values.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::dense::traits::Shape;
+use crate::dense::types::{RlstError, RlstResult, RlstScalar};
+use crate::sparse::sparse_mat::csc_mat::CscMatrix;
+use crate::sparse::sparse_mat::csr_mat::CsrMatrix;
+use crate::sparse::sparse_mat::tools::{to_index, transpose_compressed};
+use crate::sparse::sparse_mat::{SparseIndex, SparseMatType};
+
+/// Sparsity pattern of a sparse matrix-matrix product `A B`.
+///
+/// The pattern contains every entry that is structurally nonzero. Entries that are
+/// numerically zero due to cancellation are stored explicitly.
+#[derive(Clone)]
+pub struct SpGemmSymbolic<I: SparseIndex = usize> {
+    mat_type: SparseMatType,
+    shape: [usize; 2],
+    first_pattern: u64,
+    second_pattern: u64,
+    indptr: Vec<I>,
+    indices: Vec<I>,
+}
+
+impl<I: SparseIndex> SpGemmSymbolic<I> {
+    /// Symbolic phase of the product of two CSR matrices.
+    pub fn new_csr<Item: RlstScalar>(a: &CsrMatrix<Item, I>, b: &CsrMatrix<Item, I>) -> Self {
+        assert_eq!(
+            a.shape()[1],
+            b.shape()[0],
+            "Wrong dimension. {} != {}",
+            a.shape()[1],
+            b.shape()[0]
+        );
+        let (indptr, indices) = symbolic(
+            b.shape()[1],
+            a.indptr(),
+            a.indices(),
+            b.indptr(),
+            b.indices(),
+        );
+        Self {
+            mat_type: SparseMatType::Csr,
+            shape: [a.shape()[0], b.shape()[1]],
+            first_pattern: pattern_checksum(a.indptr(), a.indices()),
+            second_pattern: pattern_checksum(b.indptr(), b.indices()),
+            indptr,
+            indices,
+        }
+    }
+
+    /// Symbolic phase of the product of two CSC matrices.
+    pub fn new_csc<Item: RlstScalar>(a: &CscMatrix<Item, I>, b: &CscMatrix<Item, I>) -> Self {
+        assert_eq!(
+            a.shape()[1],
+            b.shape()[0],
+            "Wrong dimension. {} != {}",
+            a.shape()[1],
+            b.shape()[0]
+        );
+        // The CSC arrays of `A B` are the CSR arrays of `B^T A^T`.
+        let (indptr, indices) = symbolic(
+            a.shape()[0],
+            b.indptr(),
+            b.indices(),
+            a.indptr(),
+            a.indices(),
+        );
+        Self {
+            mat_type: SparseMatType::Csc,
+            shape: [a.shape()[0], b.shape()[1]],
+            first_pattern: pattern_checksum(a.indptr(), a.indices()),
+            second_pattern: pattern_checksum(b.indptr(), b.indices()),
+            indptr,
+            indices,
+        }
+    }
+
+    /// Shape of the product.
+    pub fn shape(&self) -> [usize; 2] {
+        self.shape
+    }
+
+    /// Number of stored entries of the product.
+    pub fn nelems(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Numeric phase of the product of two CSR matrices.
+    ///
+    /// `a` and `b` must have the same sparsity patterns as the matrices passed to
+    /// [SpGemmSymbolic::new_csr]. Otherwise [RlstError::InvalidSparseMatrix] is returned.
+    pub fn numeric_csr<Item: RlstScalar>(
+        &self,
+        a: &CsrMatrix<Item, I>,
+        b: &CsrMatrix<Item, I>,
+    ) -> RlstResult<CsrMatrix<Item, I>> {
+        assert!(
+            matches!(self.mat_type, SparseMatType::Csr),
+            "Symbolic phase was computed for CSC matrices."
+        );
+        self.check_compatible(
+            a.shape(),
+            (a.indptr(), a.indices()),
+            b.shape(),
+            (b.indptr(), b.indices()),
+        )?;
+        Ok(self.numeric_csr_unchecked(a, b))
+    }
+
+    /// Numeric phase of the product of two CSC matrices.
+    ///
+    /// `a` and `b` must have the same sparsity patterns as the matrices passed to
+    /// [SpGemmSymbolic::new_csc]. Otherwise [RlstError::InvalidSparseMatrix] is returned.
+    /// The result is converted to CSR format.
+    pub fn numeric_csc<Item: RlstScalar>(
+        &self,
+        a: &CscMatrix<Item, I>,
+        b: &CscMatrix<Item, I>,
+    ) -> RlstResult<CsrMatrix<Item, I>> {
+        assert!(
+            matches!(self.mat_type, SparseMatType::Csc),
+            "Symbolic phase was computed for CSR matrices."
+        );
+        self.check_compatible(
+            a.shape(),
+            (a.indptr(), a.indices()),
+            b.shape(),
+            (b.indptr(), b.indices()),
+        )?;
+        Ok(self.numeric_csc_unchecked(a, b))
+    }
+
+    fn numeric_csr_unchecked<Item: RlstScalar>(
+        &self,
+        a: &CsrMatrix<Item, I>,
+        b: &CsrMatrix<Item, I>,
+    ) -> CsrMatrix<Item, I> {
+        let data = numeric(
+            self.shape[1],
+            &self.indptr,
+            &self.indices,
+            (a.indptr(), a.indices(), a.data()),
+            (b.indptr(), b.indices(), b.data()),
+        );
+        CsrMatrix::new(self.shape, self.indices.clone(), self.indptr.clone(), data)
+    }
+
+    fn numeric_csc_unchecked<Item: RlstScalar>(
+        &self,
+        a: &CscMatrix<Item, I>,
+        b: &CscMatrix<Item, I>,
+    ) -> CsrMatrix<Item, I> {
+        let data = numeric(
+            self.shape[0],
+            &self.indptr,
+            &self.indices,
+            (b.indptr(), b.indices(), b.data()),
+            (a.indptr(), a.indices(), a.data()),
+        );
+        let (indptr, indices, data) =
+            transpose_compressed(self.shape[0], &self.indptr, &self.indices, &data);
+        CsrMatrix::new(self.shape, indices, indptr, data)
+    }
+
+    fn check_compatible(
+        &self,
+        first_shape: [usize; 2],
+        first_pattern: (&[I], &[I]),
+        second_shape: [usize; 2],
+        second_pattern: (&[I], &[I]),
+    ) -> RlstResult<()> {
+        if first_shape[0] == self.shape[0]
+            && second_shape[1] == self.shape[1]
+            && pattern_checksum(first_pattern.0, first_pattern.1) == self.first_pattern
+            && pattern_checksum(second_pattern.0, second_pattern.1) == self.second_pattern
+        {
+            Ok(())
+        } else {
+            Err(RlstError::InvalidSparseMatrix(
+                "Matrices do not match the sparsity pattern of the symbolic phase.".to_string(),
+            ))
+        }
+    }
+}
+
+/// Checksum of the sparsity pattern of a compressed matrix.
+fn pattern_checksum<I: SparseIndex>(indptr: &[I], indices: &[I]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    indptr.len().hash(&mut hasher);
+    for &index in indptr.iter().chain(indices) {
+        index.to_usize().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Compute the pattern of the product of two compressed row matrices.
+///
+/// Returns `(indptr, indices)` with sorted indices in each row.
+fn symbolic<I: SparseIndex>(
+    ncols: usize,
+    a_indptr: &[I],
+    a_indices: &[I],
+    b_indptr: &[I],
+    b_indices: &[I],
+) -> (Vec<I>, Vec<I>) {
+    let nrows = a_indptr.len() - 1;
+
+    let mut indptr = Vec::<I>::with_capacity(1 + nrows);
+    let mut indices = Vec::<I>::new();
+
+    // `marker[col] == row` if `col` already is in the pattern of `row`.
+    let mut marker = vec![usize::MAX; ncols];
+
+    indptr.push(I::default());
+    for row in 0..nrows {
+        let row_start = indices.len();
+        for &k in &a_indices[a_indptr[row].to_usize()..a_indptr[1 + row].to_usize()] {
+            let k = k.to_usize();
+            for &col in &b_indices[b_indptr[k].to_usize()..b_indptr[1 + k].to_usize()] {
+                if marker[col.to_usize()] != row {
+                    marker[col.to_usize()] = row;
+                    indices.push(col);
+                }
+            }
+        }
+        indices[row_start..].sort_unstable();
+        indptr.push(to_index(indices.len()));
+    }
+
+    (indptr, indices)
+}
+
+/// Compute the values of the product of two compressed row matrices for a given pattern.
+fn numeric<Item: RlstScalar, I: SparseIndex>(
+    ncols: usize,
+    indptr: &[I],
+    indices: &[I],
+    a: (&[I], &[I], &[Item]),
+    b: (&[I], &[I], &[Item]),
+) -> Vec<Item> {
+    let (a_indptr, a_indices, a_data) = a;
+    let (b_indptr, b_indices, b_data) = b;
+
+    let mut data = vec![Item::zero(); indices.len()];
+
+    // Position of each column in the current row of the product.
+    let mut position = vec![0; ncols];
+
+    for (row, (&row_start, &row_end)) in indptr.iter().zip(indptr.iter().skip(1)).enumerate() {
+        let row_start = row_start.to_usize();
+        for (pos, &col) in indices[row_start..row_end.to_usize()].iter().enumerate() {
+            position[col.to_usize()] = row_start + pos;
+        }
+        for a_pos in a_indptr[row].to_usize()..a_indptr[1 + row].to_usize() {
+            let k = a_indices[a_pos].to_usize();
+            let a_value = a_data[a_pos];
+            for b_pos in b_indptr[k].to_usize()..b_indptr[1 + k].to_usize() {
+                data[position[b_indices[b_pos].to_usize()]] += a_value * b_data[b_pos];
+            }
+        }
+    }
+
+    data
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CsrMatrix<Item, I> {
+    /// Compute the sparse matrix product `self * other`.
+    ///
+    /// To reuse the sparsity pattern for several products use [SpGemmSymbolic].
+    pub fn matmul_sparse(&self, other: &CsrMatrix<Item, I>) -> CsrMatrix<Item, I> {
+        SpGemmSymbolic::new_csr(self, other).numeric_csr_unchecked(self, other)
+    }
+}
+
+impl<Item: RlstScalar, I: SparseIndex> CscMatrix<Item, I> {
+    /// Compute the sparse matrix product `self * other` as a CSR matrix.
+    ///
+    /// To reuse the sparsity pattern for several products use [SpGemmSymbolic].
+    pub fn matmul_sparse(&self, other: &CscMatrix<Item, I>) -> CsrMatrix<Item, I> {
+        SpGemmSymbolic::new_csc(self, other).numeric_csc_unchecked(self, other)
+    }
+}
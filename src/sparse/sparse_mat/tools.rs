@@ -1,6 +1,6 @@
 //! Tools for sparse matrix handling
-use crate::dense::types::RlstScalar;
-use crate::sparse::sparse_mat::SparseMatType;
+use crate::dense::types::{RlstError, RlstResult, RlstScalar};
+use crate::sparse::sparse_mat::{SparseIndex, SparseMatType};
 
 /// Normalize an Aij matrix.
 ///
@@ -80,3 +80,608 @@ pub fn normalize_aij<T: RlstScalar>(
 
     (new_rows, new_cols, new_data)
 }
+
+/// Convert indices to another index type.
+pub(crate) fn convert_indices<I: SparseIndex, J: SparseIndex>(indices: &[I]) -> RlstResult<Vec<J>> {
+    indices
+        .iter()
+        .map(|&index| {
+            J::from_usize(index.to_usize()).ok_or_else(|| {
+                RlstError::GeneralError(format!(
+                    "Index {} is not representable in the target index type.",
+                    index
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Convert `value` to the index type `I`.
+///
+/// Panics if `value` is not representable in `I`.
+pub(crate) fn to_index<I: SparseIndex>(value: usize) -> I {
+    I::from_usize(value)
+        .unwrap_or_else(|| panic!("Index {} is not representable in the index type.", value))
+}
+
+/// Transpose a matrix in compressed row or column format.
+///
+/// Takes the `indptr`, `indices` and `data` of a compressed matrix with `nptr` rows
+/// (CSR) or columns (CSC) and `nindices` columns (CSR) or rows (CSC). Returns the
+/// compressed arrays of the transpose, which are also the arrays of the same matrix
+/// in the other compressed format. Indices in each row or column of the result are
+/// sorted and explicitly stored zeros are kept.
+pub(crate) fn transpose_compressed<T: Copy, I: SparseIndex>(
+    nindices: usize,
+    indptr: &[I],
+    indices: &[I],
+    data: &[T],
+) -> (Vec<I>, Vec<I>, Vec<T>) {
+    let nelems = data.len();
+
+    let mut new_indptr = vec![0; 1 + nindices];
+    for &index in &indices[..nelems] {
+        new_indptr[1 + index.to_usize()] += 1;
+    }
+    for index in 0..nindices {
+        new_indptr[1 + index] += new_indptr[index];
+    }
+
+    let mut next = new_indptr[..nindices].to_vec();
+    let mut new_indices = vec![I::default(); nelems];
+    let mut new_data = Vec::<T>::with_capacity(nelems);
+    let mut positions = vec![0; nelems];
+
+    for (ptr, (&start, &end)) in indptr.iter().zip(indptr.iter().skip(1)).enumerate() {
+        for (pos, &index) in indices
+            .iter()
+            .enumerate()
+            .take(end.to_usize())
+            .skip(start.to_usize())
+        {
+            let index = index.to_usize();
+            new_indices[next[index]] = to_index(ptr);
+            positions[next[index]] = pos;
+            next[index] += 1;
+        }
+    }
+    new_data.extend(positions.iter().map(|&pos| data[pos]));
+
+    (
+        new_indptr.into_iter().map(to_index).collect(),
+        new_indices,
+        new_data,
+    )
+}
+
+/// Compute `alpha A + beta B` for two matrices in the same compressed format.
+///
+/// `nindices` is the number of columns (CSR) or rows (CSC). The result has sorted
+/// indices in each row or column and no explicit zeros.
+pub(crate) fn add_compressed<T: RlstScalar, I: SparseIndex>(
+    nindices: usize,
+    first: (&[I], &[I], &[T]),
+    alpha: T,
+    second: (&[I], &[I], &[T]),
+    beta: T,
+) -> (Vec<I>, Vec<I>, Vec<T>) {
+    let (first_indptr, first_indices, first_data) = first;
+    let (second_indptr, second_indices, second_data) = second;
+    let nptr = first_indptr.len() - 1;
+
+    let mut indptr = Vec::<I>::with_capacity(1 + nptr);
+    let mut indices = Vec::<I>::with_capacity(first_data.len() + second_data.len());
+    let mut data = Vec::<T>::with_capacity(first_data.len() + second_data.len());
+
+    // `marker[index] == ptr` if `index` has already been seen in row or column `ptr`.
+    let mut marker = vec![usize::MAX; nindices];
+    let mut values = vec![T::zero(); nindices];
+    let mut current = Vec::<usize>::new();
+
+    indptr.push(I::default());
+    for ptr in 0..nptr {
+        current.clear();
+        let first_range = first_indptr[ptr].to_usize()..first_indptr[1 + ptr].to_usize();
+        let second_range = second_indptr[ptr].to_usize()..second_indptr[1 + ptr].to_usize();
+        for (&index, &value, factor) in itertools::chain(
+            itertools::izip!(
+                &first_indices[first_range.clone()],
+                &first_data[first_range],
+                std::iter::repeat(alpha)
+            ),
+            itertools::izip!(
+                &second_indices[second_range.clone()],
+                &second_data[second_range],
+                std::iter::repeat(beta)
+            ),
+        ) {
+            let index = index.to_usize();
+            if marker[index] != ptr {
+                marker[index] = ptr;
+                values[index] = T::zero();
+                current.push(index);
+            }
+            values[index] += factor * value;
+        }
+        current.sort_unstable();
+        for &index in &current {
+            if values[index] != T::zero() {
+                indices.push(to_index(index));
+                data.push(values[index]);
+            }
+        }
+        indptr.push(to_index(indices.len()));
+    }
+
+    (indptr, indices, data)
+}
+
+/// Remove explicitly stored zeros from a matrix in compressed format.
+pub(crate) fn eliminate_zeros<T: RlstScalar, I: SparseIndex>(
+    indptr: &mut [I],
+    indices: &mut Vec<I>,
+    data: &mut Vec<T>,
+) {
+    let mut count = 0;
+    let mut start = indptr[0].to_usize();
+    for ptr in 0..indptr.len() - 1 {
+        let end = indptr[1 + ptr].to_usize();
+        for pos in start..end {
+            if data[pos] != T::zero() {
+                indices[count] = indices[pos];
+                data[count] = data[pos];
+                count += 1;
+            }
+        }
+        start = end;
+        indptr[1 + ptr] = to_index(count);
+    }
+    indices.truncate(count);
+    data.truncate(count);
+}
+
+/// Return the diagonal of a matrix in compressed format.
+///
+/// `ndiag` is the length of the diagonal, i.e. the minimum of the number of rows and columns.
+pub(crate) fn compressed_diagonal<T: RlstScalar, I: SparseIndex>(
+    ndiag: usize,
+    indptr: &[I],
+    indices: &[I],
+    data: &[T],
+) -> Vec<T> {
+    (0..ndiag)
+        .map(|ptr| {
+            (indptr[ptr].to_usize()..indptr[1 + ptr].to_usize())
+                .filter(|&pos| indices[pos].to_usize() == ptr)
+                .fold(T::zero(), |acc, pos| acc + data[pos])
+        })
+        .collect()
+}
+
+/// Replace the diagonal of a matrix in compressed format.
+///
+/// Returns the new compressed arrays with sorted indices and no explicit zeros.
+pub(crate) fn set_compressed_diagonal<T: RlstScalar, I: SparseIndex>(
+    diag: &[T],
+    indptr: &[I],
+    indices: &[I],
+    data: &[T],
+) -> (Vec<I>, Vec<I>, Vec<T>) {
+    let nptr = indptr.len() - 1;
+
+    let mut new_indptr = Vec::<I>::with_capacity(1 + nptr);
+    let mut new_indices = Vec::<I>::with_capacity(data.len() + diag.len());
+    let mut new_data = Vec::<T>::with_capacity(data.len() + diag.len());
+    let mut row = Vec::<(usize, T)>::new();
+
+    new_indptr.push(I::default());
+    for ptr in 0..nptr {
+        row.clear();
+        row.extend(
+            (indptr[ptr].to_usize()..indptr[1 + ptr].to_usize())
+                .map(|pos| (indices[pos].to_usize(), data[pos]))
+                .filter(|&(index, _)| ptr >= diag.len() || index != ptr),
+        );
+        if ptr < diag.len() {
+            row.push((ptr, diag[ptr]));
+        }
+        row.sort_by_key(|&(index, _)| index);
+
+        // Sum duplicates in case the input was not normalized.
+        for (pos, &(index, value)) in row.iter().enumerate() {
+            if pos > 0 && row[pos - 1].0 == index {
+                *new_data.last_mut().unwrap() += value;
+            } else {
+                new_indices.push(to_index(index));
+                new_data.push(value);
+            }
+        }
+        new_indptr.push(to_index(new_indices.len()));
+    }
+
+    eliminate_zeros(&mut new_indptr, &mut new_indices, &mut new_data);
+    (new_indptr, new_indices, new_data)
+}
+
+/// Extract a submatrix of a matrix in compressed format.
+///
+/// Row (CSR) or column (CSC) `i` of the result is `outer[i]` of the input, restricted
+/// to the indices in `inner`, which become the indices `0..inner.len()` in the given
+/// order. `nindices` is the number of columns (CSR) or rows (CSC) of the input. Entries
+/// of `outer` may repeat, entries of `inner` must be distinct. Returns the compressed
+/// arrays with sorted indices, summed duplicates and no explicit zeros.
+pub(crate) fn extract_compressed<T: RlstScalar, I: SparseIndex>(
+    nindices: usize,
+    outer: &[usize],
+    inner: &[usize],
+    indptr: &[I],
+    indices: &[I],
+    data: &[T],
+) -> (Vec<I>, Vec<I>, Vec<T>) {
+    let nptr = indptr.len() - 1;
+
+    // `new_index[index]` is the position of `index` in `inner` or `usize::MAX`.
+    let mut new_index = vec![usize::MAX; nindices];
+    for (pos, &index) in inner.iter().enumerate() {
+        assert!(
+            index < nindices,
+            "Index {} out of bounds for dimension {}.",
+            index,
+            nindices
+        );
+        assert_eq!(
+            new_index[index],
+            usize::MAX,
+            "Index {} is selected more than once.",
+            index
+        );
+        new_index[index] = pos;
+    }
+
+    let mut new_indptr = Vec::<I>::with_capacity(1 + outer.len());
+    let mut new_indices = Vec::<I>::new();
+    let mut new_data = Vec::<T>::new();
+    let mut row = Vec::<(usize, T)>::new();
+
+    new_indptr.push(I::default());
+    for &ptr in outer {
+        assert!(
+            ptr < nptr,
+            "Index {} out of bounds for dimension {}.",
+            ptr,
+            nptr
+        );
+        row.clear();
+        row.extend(
+            (indptr[ptr].to_usize()..indptr[1 + ptr].to_usize())
+                .map(|pos| (new_index[indices[pos].to_usize()], data[pos]))
+                .filter(|&(index, _)| index != usize::MAX),
+        );
+        row.sort_by_key(|&(index, _)| index);
+
+        // Sum duplicates in case the input was not normalized.
+        for (pos, &(index, value)) in row.iter().enumerate() {
+            if pos > 0 && row[pos - 1].0 == index {
+                *new_data.last_mut().unwrap() += value;
+            } else {
+                new_indices.push(to_index(index));
+                new_data.push(value);
+            }
+        }
+        new_indptr.push(to_index(new_indices.len()));
+    }
+
+    eliminate_zeros(&mut new_indptr, &mut new_indices, &mut new_data);
+    (new_indptr, new_indices, new_data)
+}
+
+/// Check that `permutation` is a permutation of `0..n`.
+pub(crate) fn assert_permutation(permutation: &[usize], n: usize) {
+    assert_eq!(
+        permutation.len(),
+        n,
+        "Permutation has wrong length. {} != {}",
+        permutation.len(),
+        n
+    );
+    let mut seen = vec![false; n];
+    for &index in permutation {
+        assert!(
+            index < n && !seen[index],
+            "Entry {} of the permutation is out of bounds or repeated.",
+            index
+        );
+        seen[index] = true;
+    }
+}
+
+/// Return the entries of row (CSR) or column (CSC) `ptr` of a matrix in compressed format
+/// sorted by index with duplicates summed.
+pub(crate) fn sorted_compressed_row<T: RlstScalar, I: SparseIndex>(
+    indptr: &[I],
+    indices: &[I],
+    data: &[T],
+    ptr: usize,
+) -> Vec<(usize, T)> {
+    let range = indptr[ptr].to_usize()..indptr[1 + ptr].to_usize();
+    let mut entries = indices[range.clone()]
+        .iter()
+        .map(|index| index.to_usize())
+        .zip(data[range].iter().copied())
+        .collect::<Vec<_>>();
+    entries.sort_unstable_by_key(|&(index, _)| index);
+    entries.dedup_by(|next, previous| {
+        let duplicate = next.0 == previous.0;
+        if duplicate {
+            previous.1 += next.1;
+        }
+        duplicate
+    });
+    entries
+}
+
+/// Check the compressed arrays of a matrix of the given shape.
+///
+/// `indptr` must start at zero, be monotone and end at the number of entries, and the
+/// indices in each row (CSR) or column (CSC) must be in bounds, sorted and distinct.
+pub(crate) fn validate_compressed<I: SparseIndex>(
+    shape: [usize; 2],
+    mat_type: SparseMatType,
+    indptr: &[I],
+    indices: &[I],
+    ndata: usize,
+) -> RlstResult<()> {
+    let (nptr, nindices, outer, inner) = match mat_type {
+        SparseMatType::Csr => (shape[0], shape[1], "row", "column"),
+        SparseMatType::Csc => (shape[1], shape[0], "column", "row"),
+    };
+    let invalid = |message: String| Err(RlstError::InvalidSparseMatrix(message));
+    // Entry `(index, ptr)` in the order (row, column).
+    let entry = |ptr: usize, index: usize| match mat_type {
+        SparseMatType::Csr => (ptr, index),
+        SparseMatType::Csc => (index, ptr),
+    };
+
+    if indptr.len() != 1 + nptr {
+        return invalid(format!(
+            "indptr has length {} but the matrix has {} {}s.",
+            indptr.len(),
+            nptr,
+            outer
+        ));
+    }
+    if indptr[0] != I::default() {
+        return invalid(format!("indptr starts at {} instead of 0.", indptr[0]));
+    }
+    if let Some(ptr) = (0..nptr).find(|&ptr| indptr[ptr] > indptr[1 + ptr]) {
+        return invalid(format!(
+            "indptr is not monotone. indptr[{}] = {} > indptr[{}] = {}.",
+            ptr,
+            indptr[ptr],
+            1 + ptr,
+            indptr[1 + ptr]
+        ));
+    }
+    if indptr[nptr].to_usize() != indices.len() {
+        return invalid(format!(
+            "indptr ends at {} but there are {} indices.",
+            indptr[nptr],
+            indices.len()
+        ));
+    }
+    if ndata != indices.len() {
+        return invalid(format!(
+            "There are {} indices but {} data entries.",
+            indices.len(),
+            ndata
+        ));
+    }
+
+    for ptr in 0..nptr {
+        let row = &indices[indptr[ptr].to_usize()..indptr[1 + ptr].to_usize()];
+        if let Some(&index) = row.iter().find(|&&index| index.to_usize() >= nindices) {
+            return invalid(format!(
+                "{} index {} in {} {} is out of bounds for {} {}s.",
+                capitalise(inner),
+                index,
+                outer,
+                ptr,
+                nindices,
+                inner
+            ));
+        }
+        for pair in row.windows(2) {
+            if pair[0] == pair[1] {
+                return invalid(format!(
+                    "Duplicate entry {:?}.",
+                    entry(ptr, pair[0].to_usize())
+                ));
+            }
+            if pair[0] > pair[1] {
+                return invalid(format!(
+                    "{} indices in {} {} are not sorted. {} follows {}.",
+                    capitalise(inner),
+                    outer,
+                    ptr,
+                    pair[1],
+                    pair[0]
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Return `word` with an upper case first letter.
+fn capitalise(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Compare a square matrix in compressed format with its transpose.
+///
+/// `compare(a_ij, a_ji)` is called for every pair of entries `(i, j)` and `(j, i)` of
+/// which at least one is stored, with `None` for an entry that is not stored. Returns
+/// true if `compare` returns true for all pairs.
+pub(crate) fn compare_compressed_transpose<T: RlstScalar, I: SparseIndex>(
+    indptr: &[I],
+    indices: &[I],
+    data: &[T],
+    compare: impl Fn(Option<T>, Option<T>) -> bool,
+) -> bool {
+    let n = indptr.len() - 1;
+    let (t_indptr, t_indices, t_data) = transpose_compressed(n, indptr, indices, data);
+
+    (0..n).all(|ptr| {
+        let row = sorted_compressed_row(indptr, indices, data, ptr);
+        let t_row = sorted_compressed_row(&t_indptr, &t_indices, &t_data, ptr);
+        let mut entries = row.iter().peekable();
+        let mut t_entries = t_row.iter().peekable();
+        loop {
+            let pair = match (entries.peek(), t_entries.peek()) {
+                (None, None) => return true,
+                (Some(&&(index, value)), Some(&&(t_index, t_value))) if index == t_index => {
+                    entries.next();
+                    t_entries.next();
+                    (Some(value), Some(t_value))
+                }
+                (Some(&&(index, value)), Some(&&(t_index, _))) if index < t_index => {
+                    entries.next();
+                    (Some(value), None)
+                }
+                (Some(&&(_, value)), None) => {
+                    entries.next();
+                    (Some(value), None)
+                }
+                (_, Some(&&(_, t_value))) => {
+                    t_entries.next();
+                    (None, Some(t_value))
+                }
+            };
+            if !compare(pair.0, pair.1) {
+                return false;
+            }
+        }
+    })
+}
+
+/// Check whether a square matrix in compressed format equals its transpose, or its
+/// conjugate transpose if `conjugate` is true, up to the relative tolerance `tol`.
+///
+/// Entries are compared as `|a_ij - a_ji| <= tol * max(|a_ij|, |a_ji|)`, where the
+/// transposed entry is conjugated if `conjugate` is true.
+pub(crate) fn compressed_is_symmetric<T: RlstScalar, I: SparseIndex>(
+    indptr: &[I],
+    indices: &[I],
+    data: &[T],
+    tol: T::Real,
+    conjugate: bool,
+) -> bool {
+    compare_compressed_transpose(indptr, indices, data, |value, t_value| {
+        let value = value.unwrap_or(T::zero());
+        let t_value = t_value.unwrap_or(T::zero());
+        let t_value = if conjugate { t_value.conj() } else { t_value };
+        let scale = if value.abs() > t_value.abs() {
+            value.abs()
+        } else {
+            t_value.abs()
+        };
+        (value - t_value).abs() <= tol * scale
+    })
+}
+
+/// Statistics of a sparse matrix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseStats {
+    /// Shape of the matrix
+    pub shape: [usize; 2],
+    /// Number of stored entries
+    pub nnz: usize,
+    /// Minimum number of stored entries in a row
+    pub min_row_nnz: usize,
+    /// Maximum number of stored entries in a row
+    pub max_row_nnz: usize,
+    /// Mean number of stored entries in a row
+    pub mean_row_nnz: f64,
+    /// Number of rows without stored entries
+    pub empty_rows: usize,
+    /// Largest `i - j` of a stored entry `(i, j)` below the diagonal
+    pub lower_bandwidth: usize,
+    /// Largest `j - i` of a stored entry `(i, j)` above the diagonal
+    pub upper_bandwidth: usize,
+    /// True if the matrix is square and `(j, i)` is stored whenever `(i, j)` is stored
+    pub structurally_symmetric: bool,
+    /// True if the matrix is square and `|a_ii| >= sum_{j != i} |a_ij|` for every row `i`
+    pub diagonally_dominant: bool,
+    /// True if the matrix is square and `|a_ii| > sum_{j != i} |a_ij|` for every row `i`
+    pub strictly_diagonally_dominant: bool,
+}
+
+impl SparseStats {
+    /// Compute the statistics of a matrix in compressed format.
+    pub(crate) fn from_compressed<T: RlstScalar, I: SparseIndex>(
+        shape: [usize; 2],
+        mat_type: SparseMatType,
+        indptr: &[I],
+        indices: &[I],
+        data: &[T],
+    ) -> Self {
+        let nptr = indptr.len() - 1;
+        let square = shape[0] == shape[1];
+
+        let mut row_nnz = vec![0; shape[0]];
+        let mut diagonal = vec![<T::Real as num::Zero>::zero(); shape[0]];
+        let mut off_diagonal = vec![<T::Real as num::Zero>::zero(); shape[0]];
+        let mut lower_bandwidth = 0;
+        let mut upper_bandwidth = 0;
+
+        for ptr in 0..nptr {
+            for pos in indptr[ptr].to_usize()..indptr[1 + ptr].to_usize() {
+                let (row, col) = match mat_type {
+                    SparseMatType::Csr => (ptr, indices[pos].to_usize()),
+                    SparseMatType::Csc => (indices[pos].to_usize(), ptr),
+                };
+                row_nnz[row] += 1;
+                if row == col {
+                    diagonal[row] += data[pos].abs();
+                } else {
+                    off_diagonal[row] += data[pos].abs();
+                }
+                lower_bandwidth = lower_bandwidth.max(row.saturating_sub(col));
+                upper_bandwidth = upper_bandwidth.max(col.saturating_sub(row));
+            }
+        }
+
+        let structurally_symmetric = square
+            && compare_compressed_transpose(indptr, indices, data, |value, t_value| {
+                value.is_some() && t_value.is_some()
+            });
+
+        Self {
+            shape,
+            nnz: data.len(),
+            min_row_nnz: row_nnz.iter().copied().min().unwrap_or(0),
+            max_row_nnz: row_nnz.iter().copied().max().unwrap_or(0),
+            mean_row_nnz: if shape[0] == 0 {
+                0.0
+            } else {
+                data.len() as f64 / shape[0] as f64
+            },
+            empty_rows: row_nnz.iter().filter(|&&count| count == 0).count(),
+            lower_bandwidth,
+            upper_bandwidth,
+            structurally_symmetric,
+            diagonally_dominant: square
+                && diagonal
+                    .iter()
+                    .zip(off_diagonal.iter())
+                    .all(|(d, o)| d >= o),
+            strictly_diagonally_dominant: square
+                && diagonal.iter().zip(off_diagonal.iter()).all(|(d, o)| d > o),
+        }
+    }
+}
@@ -1,196 +1,585 @@
 //! Implementation of Umfpack for CSC Matrices
+//!
+//! The factorisation is split into a symbolic phase [UmfpackSymbolic], which only depends on
+//! the sparsity pattern, and a numeric phase that creates an [UmfpackLu]. The symbolic object
+//! is kept by the LU decomposition so that a matrix with the same pattern but new values can
+//! be factorised with [UmfpackLu::refactor].
 
+use std::borrow::Cow;
 use std::ffi::c_void;
 
 use crate::dense::array::Array;
 use crate::dense::traits::{RawAccess, RawAccessMut, Shape, Stride};
-use crate::dense::types::c64;
 use crate::dense::types::RlstScalar;
 use crate::dense::types::TransMode;
+use crate::dense::types::{c32, c64};
 use crate::dense::types::{RlstError, RlstResult};
 use crate::external::umfpack;
 use crate::sparse::sparse_mat::csc_mat::CscMatrix;
 
 use super::csr_mat::CsrMatrix;
+use super::tools::convert_indices;
+use super::SparseIndex;
+
+/// Fill-reducing ordering used by the symbolic analysis of Umfpack.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UmfpackOrdering {
+    /// Cholmod ordering (AMD/COLAMD, then METIS if the fill-in is high).
+    Cholmod,
+    /// AMD for symmetric and COLAMD for unsymmetric matrices.
+    Amd,
+    /// METIS nested dissection.
+    Metis,
+    /// Try several orderings and use the best.
+    Best,
+    /// No fill-reducing ordering.
+    None,
+}
 
-/// Holds the Umfpack data structures.
-pub struct UmfpackLu<T: RlstScalar> {
-    shape: [usize; 2],
-    indices: Vec<i32>,
-    indptr: Vec<i32>,
-    data: Vec<T>,
-    numeric: *mut c_void,
+impl UmfpackOrdering {
+    fn value(self) -> u32 {
+        match self {
+            UmfpackOrdering::Cholmod => umfpack::raw::UMFPACK_ORDERING_CHOLMOD,
+            UmfpackOrdering::Amd => umfpack::raw::UMFPACK_ORDERING_AMD,
+            UmfpackOrdering::Metis => umfpack::raw::UMFPACK_ORDERING_METIS,
+            UmfpackOrdering::Best => umfpack::raw::UMFPACK_ORDERING_BEST,
+            UmfpackOrdering::None => umfpack::raw::UMFPACK_ORDERING_NONE,
+        }
+    }
 }
 
-impl<T: RlstScalar> Drop for UmfpackLu<T> {
-    fn drop(&mut self) {
-        unsafe { umfpack::raw::umfpack_di_free_numeric(&mut self.numeric) };
+/// Control parameters of Umfpack.
+#[derive(Clone, Debug)]
+pub struct UmfpackControl {
+    control: Vec<f64>,
+}
+
+impl Default for UmfpackControl {
+    fn default() -> Self {
+        let mut control = vec![0.0; umfpack::raw::UMFPACK_CONTROL as usize];
+        unsafe { umfpack::raw::umfpack_di_defaults(control.as_mut_ptr()) };
+        Self { control }
     }
 }
 
-impl UmfpackLu<f64> {
-    /// Solve for a given right-hand side.
-    pub fn solve<
-        ArrayImplX: crate::dense::traits::RandomAccessByValue<1, Item = f64>
-            + Shape<1>
-            + RawAccessMut<Item = f64>
-            + Stride<1>,
-        ArrayImplRhs: crate::dense::traits::RandomAccessByValue<1, Item = f64>
-            + Shape<1>
-            + RawAccess<Item = f64>
-            + Stride<1>,
-    >(
-        &self,
-        rhs: Array<f64, ArrayImplRhs, 1>,
-        mut x: Array<f64, ArrayImplX, 1>,
-        trans: TransMode,
-    ) -> crate::dense::types::RlstResult<()> {
-        assert_eq!(rhs.stride()[0], 1);
-        assert_eq!(x.stride()[0], 1);
+impl UmfpackControl {
+    /// Create the default control parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-        let sys = match trans {
-            TransMode::NoTrans => umfpack::raw::UMFPACK_A,
-            TransMode::Trans => umfpack::raw::UMFPACK_Aat,
-            TransMode::ConjTrans => umfpack::raw::UMFPACK_At,
-            _ => panic!("Transpose mode not supported for Umfpack"),
-        };
+    /// Relative pivot tolerance for threshold partial pivoting.
+    pub fn pivot_tolerance(&self) -> f64 {
+        self.control[umfpack::raw::UMFPACK_PIVOT_TOLERANCE as usize]
+    }
 
-        let n = self.shape[0];
-        assert_eq!(x.shape()[0], n);
-        assert_eq!(rhs.shape()[0], n);
+    /// Set the relative pivot tolerance.
+    pub fn set_pivot_tolerance(&mut self, tolerance: f64) {
+        self.control[umfpack::raw::UMFPACK_PIVOT_TOLERANCE as usize] = tolerance;
+    }
 
-        let info = unsafe {
-            umfpack::raw::umfpack_di_solve(
-                sys as i32,
-                self.indptr.as_ptr(),
-                self.indices.as_ptr(),
-                self.data.as_ptr(),
-                x.data_mut().as_mut_ptr(),
-                rhs.data().as_ptr(),
-                self.numeric,
-                std::ptr::null(),
-                std::ptr::null_mut(),
-            )
-        };
+    /// Set the fill-reducing ordering.
+    pub fn set_ordering(&mut self, ordering: UmfpackOrdering) {
+        self.control[umfpack::raw::UMFPACK_ORDERING as usize] = ordering.value() as f64;
+    }
 
-        if info != 0 {
-            return Err(RlstError::UmfpackError(info));
+    /// The raw control array. Entries are indexed by the `UMFPACK_*` control constants.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.control
+    }
+
+    /// The mutable raw control array.
+    pub fn as_mut_slice(&mut self) -> &mut [f64] {
+        &mut self.control
+    }
+}
+
+/// Statistics returned by Umfpack.
+#[derive(Clone, Debug)]
+pub struct UmfpackInfo {
+    info: Vec<f64>,
+}
+
+impl Default for UmfpackInfo {
+    fn default() -> Self {
+        Self {
+            info: vec![0.0; umfpack::raw::UMFPACK_INFO as usize],
         }
+    }
+}
 
-        Ok(())
+impl UmfpackInfo {
+    /// Status code of the last call.
+    pub fn status(&self) -> i32 {
+        self.info[umfpack::raw::UMFPACK_STATUS as usize] as i32
+    }
+
+    /// Estimate of the reciprocal condition number from the numeric factorisation.
+    pub fn rcond(&self) -> f64 {
+        self.info[umfpack::raw::UMFPACK_RCOND as usize]
+    }
+
+    /// Number of floating point operations of the numeric factorisation.
+    pub fn flops(&self) -> f64 {
+        self.info[umfpack::raw::UMFPACK_FLOPS as usize]
+    }
+
+    /// The raw info array. Entries are indexed by the `UMFPACK_*` info constants.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.info
     }
 }
 
-impl UmfpackLu<c64> {
-    /// Solve for a given right-hand side.
-    pub fn solve<
-        ArrayImplX: crate::dense::traits::RandomAccessByValue<1, Item = c64>
-            + Shape<1>
-            + RawAccessMut<Item = c64>
-            + Stride<1>,
-        ArrayImplRhs: crate::dense::traits::RandomAccessByValue<1, Item = c64>
-            + Shape<1>
-            + RawAccess<Item = c64>
-            + Stride<1>,
-    >(
-        &self,
-        rhs: Array<c64, ArrayImplRhs, 1>,
-        mut x: Array<c64, ArrayImplX, 1>,
-        trans: TransMode,
-    ) -> crate::dense::types::RlstResult<()> {
-        assert_eq!(rhs.stride()[0], 1);
-        assert_eq!(x.stride()[0], 1);
+/// Scalar types that can be factorised with Umfpack.
+///
+/// Umfpack only works in double precision. Single precision matrices and vectors are
+/// promoted to [UmfpackScalar::Double] and the results are rounded back.
+pub trait UmfpackScalar: RlstScalar {
+    /// The double precision type used by Umfpack.
+    type Double: UmfpackDouble;
 
-        let sys = match trans {
-            TransMode::NoTrans => umfpack::raw::UMFPACK_A,
-            TransMode::Trans => umfpack::raw::UMFPACK_Aat,
-            TransMode::ConjTrans => umfpack::raw::UMFPACK_At,
-            _ => panic!("Transpose mode not supported for Umfpack"),
-        };
+    /// Convert to double precision.
+    fn to_double(self) -> Self::Double;
 
-        let n = self.shape[0];
-        assert_eq!(x.shape()[0], n);
-        assert_eq!(rhs.shape()[0], n);
+    /// Convert from double precision.
+    fn from_double(value: Self::Double) -> Self;
+}
 
-        let info = unsafe {
-            umfpack::raw::umfpack_zi_solve(
-                sys as i32,
-                self.indptr.as_ptr(),
-                self.indices.as_ptr(),
-                self.data.as_ptr() as *const f64,
-                std::ptr::null(),
-                x.data_mut().as_mut_ptr() as *mut f64,
-                std::ptr::null_mut(),
-                rhs.data().as_ptr() as *mut f64,
-                std::ptr::null(),
-                self.numeric,
-                std::ptr::null(),
-                std::ptr::null_mut(),
-            )
-        };
+macro_rules! impl_umfpack_scalar {
+    ($scalar:ty, $double:ty, $to:expr, $from:expr) => {
+        impl UmfpackScalar for $scalar {
+            type Double = $double;
 
-        if info != 0 {
-            return Err(RlstError::UmfpackError(info));
+            fn to_double(self) -> Self::Double {
+                $to(self)
+            }
+
+            fn from_double(value: Self::Double) -> Self {
+                $from(value)
+            }
         }
+    };
+}
 
-        Ok(())
+impl_umfpack_scalar!(f32, f64, |value: f32| value as f64, |value: f64| value
+    as f32);
+impl_umfpack_scalar!(f64, f64, |value| value, |value| value);
+impl_umfpack_scalar!(
+    c32,
+    c64,
+    |value: c32| c64::new(value.re as f64, value.im as f64),
+    |value: c64| c32::new(value.re as f32, value.im as f32)
+);
+impl_umfpack_scalar!(c64, c64, |value| value, |value| value);
+
+/// Double precision scalar types with a native Umfpack interface.
+pub trait UmfpackDouble: UmfpackScalar<Double = Self> {
+    /// Call the symbolic analysis of Umfpack.
+    ///
+    /// # Safety
+    /// `indptr`, `indices` and `data` must describe a CSC matrix of the given shape.
+    /// `control` and `info` must have the lengths `UMFPACK_CONTROL` and `UMFPACK_INFO`.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn umfpack_symbolic(
+        shape: [usize; 2],
+        indptr: &[i32],
+        indices: &[i32],
+        data: &[Self],
+        symbolic: &mut *mut c_void,
+        control: &[f64],
+        info: &mut [f64],
+    ) -> i32;
+
+    /// Call the numeric factorisation of Umfpack.
+    ///
+    /// # Safety
+    /// `symbolic` must be the result of [UmfpackDouble::umfpack_symbolic] for the same
+    /// sparsity pattern.
+    unsafe fn umfpack_numeric(
+        indptr: &[i32],
+        indices: &[i32],
+        data: &[Self],
+        symbolic: *mut c_void,
+        numeric: &mut *mut c_void,
+        control: &[f64],
+        info: &mut [f64],
+    ) -> i32;
+
+    /// Solve a linear system with a numeric factorisation.
+    ///
+    /// # Safety
+    /// `numeric` must be the result of [UmfpackDouble::umfpack_numeric] for the matrix
+    /// given by `indptr`, `indices` and `data`.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn umfpack_solve(
+        sys: i32,
+        indptr: &[i32],
+        indices: &[i32],
+        data: &[Self],
+        x: &mut [Self],
+        b: &[Self],
+        numeric: *mut c_void,
+        control: &[f64],
+    ) -> i32;
+
+    /// Free a symbolic object.
+    ///
+    /// # Safety
+    /// `symbolic` must be null or created by [UmfpackDouble::umfpack_symbolic].
+    unsafe fn umfpack_free_symbolic(symbolic: &mut *mut c_void);
+
+    /// Free a numeric object.
+    ///
+    /// # Safety
+    /// `numeric` must be null or created by [UmfpackDouble::umfpack_numeric].
+    unsafe fn umfpack_free_numeric(numeric: &mut *mut c_void);
+
+    /// Return the number of nonzeros of the factors and the shape as
+    /// `[lnz, unz, n_row, n_col, nz_udiag]`.
+    ///
+    /// # Safety
+    /// `numeric` must be created by [UmfpackDouble::umfpack_numeric].
+    unsafe fn umfpack_get_lunz(numeric: *mut c_void, lunz: &mut [i32; 5]) -> i32;
+
+    /// Copy the factors out of a numeric object.
+    ///
+    /// `L` is returned in compressed row form and `U` in compressed column form.
+    ///
+    /// # Safety
+    /// `numeric` must be created by [UmfpackDouble::umfpack_numeric] and the arrays must
+    /// have the sizes given by [UmfpackDouble::umfpack_get_lunz].
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn umfpack_get_numeric(
+        lp: &mut [i32],
+        lj: &mut [i32],
+        lx: &mut [Self],
+        up: &mut [i32],
+        ui: &mut [i32],
+        ux: &mut [Self],
+        p: &mut [i32],
+        q: &mut [i32],
+        do_recip: &mut i32,
+        rs: &mut [f64],
+        numeric: *mut c_void,
+    ) -> i32;
+}
+
+impl UmfpackDouble for f64 {
+    unsafe fn umfpack_symbolic(
+        shape: [usize; 2],
+        indptr: &[i32],
+        indices: &[i32],
+        data: &[Self],
+        symbolic: &mut *mut c_void,
+        control: &[f64],
+        info: &mut [f64],
+    ) -> i32 {
+        umfpack::raw::umfpack_di_symbolic(
+            shape[0] as i32,
+            shape[1] as i32,
+            indptr.as_ptr(),
+            indices.as_ptr(),
+            data.as_ptr(),
+            symbolic,
+            control.as_ptr(),
+            info.as_mut_ptr(),
+        )
+    }
+
+    unsafe fn umfpack_numeric(
+        indptr: &[i32],
+        indices: &[i32],
+        data: &[Self],
+        symbolic: *mut c_void,
+        numeric: &mut *mut c_void,
+        control: &[f64],
+        info: &mut [f64],
+    ) -> i32 {
+        umfpack::raw::umfpack_di_numeric(
+            indptr.as_ptr(),
+            indices.as_ptr(),
+            data.as_ptr(),
+            symbolic,
+            numeric,
+            control.as_ptr(),
+            info.as_mut_ptr(),
+        )
+    }
+
+    unsafe fn umfpack_solve(
+        sys: i32,
+        indptr: &[i32],
+        indices: &[i32],
+        data: &[Self],
+        x: &mut [Self],
+        b: &[Self],
+        numeric: *mut c_void,
+        control: &[f64],
+    ) -> i32 {
+        umfpack::raw::umfpack_di_solve(
+            sys,
+            indptr.as_ptr(),
+            indices.as_ptr(),
+            data.as_ptr(),
+            x.as_mut_ptr(),
+            b.as_ptr(),
+            numeric,
+            control.as_ptr(),
+            std::ptr::null_mut(),
+        )
+    }
+
+    unsafe fn umfpack_free_symbolic(symbolic: &mut *mut c_void) {
+        umfpack::raw::umfpack_di_free_symbolic(symbolic)
+    }
+
+    unsafe fn umfpack_free_numeric(numeric: &mut *mut c_void) {
+        umfpack::raw::umfpack_di_free_numeric(numeric)
+    }
+
+    unsafe fn umfpack_get_lunz(numeric: *mut c_void, lunz: &mut [i32; 5]) -> i32 {
+        let [lnz, unz, n_row, n_col, nz_udiag] = lunz;
+        umfpack::raw::umfpack_di_get_lunz(lnz, unz, n_row, n_col, nz_udiag, numeric)
+    }
+
+    unsafe fn umfpack_get_numeric(
+        lp: &mut [i32],
+        lj: &mut [i32],
+        lx: &mut [Self],
+        up: &mut [i32],
+        ui: &mut [i32],
+        ux: &mut [Self],
+        p: &mut [i32],
+        q: &mut [i32],
+        do_recip: &mut i32,
+        rs: &mut [f64],
+        numeric: *mut c_void,
+    ) -> i32 {
+        umfpack::raw::umfpack_di_get_numeric(
+            lp.as_mut_ptr(),
+            lj.as_mut_ptr(),
+            lx.as_mut_ptr(),
+            up.as_mut_ptr(),
+            ui.as_mut_ptr(),
+            ux.as_mut_ptr(),
+            p.as_mut_ptr(),
+            q.as_mut_ptr(),
+            std::ptr::null_mut(),
+            do_recip,
+            rs.as_mut_ptr(),
+            numeric,
+        )
     }
 }
 
-impl CsrMatrix<f64> {
-    /// Compute the sparse LU decomposition of the matrix.
-    ///
-    /// Note that the sparse matrix is first converted to
-    /// CSC format before computing the LU.
-    pub fn into_lu(self) -> RlstResult<UmfpackLu<f64>> {
-        self.into_csc().into_lu()
+impl UmfpackDouble for c64 {
+    unsafe fn umfpack_symbolic(
+        shape: [usize; 2],
+        indptr: &[i32],
+        indices: &[i32],
+        data: &[Self],
+        symbolic: &mut *mut c_void,
+        control: &[f64],
+        info: &mut [f64],
+    ) -> i32 {
+        umfpack::raw::umfpack_zi_symbolic(
+            shape[0] as i32,
+            shape[1] as i32,
+            indptr.as_ptr(),
+            indices.as_ptr(),
+            data.as_ptr() as *const f64,
+            std::ptr::null(),
+            symbolic,
+            control.as_ptr(),
+            info.as_mut_ptr(),
+        )
+    }
+
+    unsafe fn umfpack_numeric(
+        indptr: &[i32],
+        indices: &[i32],
+        data: &[Self],
+        symbolic: *mut c_void,
+        numeric: &mut *mut c_void,
+        control: &[f64],
+        info: &mut [f64],
+    ) -> i32 {
+        umfpack::raw::umfpack_zi_numeric(
+            indptr.as_ptr(),
+            indices.as_ptr(),
+            data.as_ptr() as *const f64,
+            std::ptr::null(),
+            symbolic,
+            numeric,
+            control.as_ptr(),
+            info.as_mut_ptr(),
+        )
+    }
+
+    unsafe fn umfpack_solve(
+        sys: i32,
+        indptr: &[i32],
+        indices: &[i32],
+        data: &[Self],
+        x: &mut [Self],
+        b: &[Self],
+        numeric: *mut c_void,
+        control: &[f64],
+    ) -> i32 {
+        umfpack::raw::umfpack_zi_solve(
+            sys,
+            indptr.as_ptr(),
+            indices.as_ptr(),
+            data.as_ptr() as *const f64,
+            std::ptr::null(),
+            x.as_mut_ptr() as *mut f64,
+            std::ptr::null_mut(),
+            b.as_ptr() as *const f64,
+            std::ptr::null(),
+            numeric,
+            control.as_ptr(),
+            std::ptr::null_mut(),
+        )
+    }
+
+    unsafe fn umfpack_free_symbolic(symbolic: &mut *mut c_void) {
+        umfpack::raw::umfpack_zi_free_symbolic(symbolic)
+    }
+
+    unsafe fn umfpack_free_numeric(numeric: &mut *mut c_void) {
+        umfpack::raw::umfpack_zi_free_numeric(numeric)
+    }
+
+    unsafe fn umfpack_get_lunz(numeric: *mut c_void, lunz: &mut [i32; 5]) -> i32 {
+        let [lnz, unz, n_row, n_col, nz_udiag] = lunz;
+        umfpack::raw::umfpack_zi_get_lunz(lnz, unz, n_row, n_col, nz_udiag, numeric)
+    }
+
+    unsafe fn umfpack_get_numeric(
+        lp: &mut [i32],
+        lj: &mut [i32],
+        lx: &mut [Self],
+        up: &mut [i32],
+        ui: &mut [i32],
+        ux: &mut [Self],
+        p: &mut [i32],
+        q: &mut [i32],
+        do_recip: &mut i32,
+        rs: &mut [f64],
+        numeric: *mut c_void,
+    ) -> i32 {
+        umfpack::raw::umfpack_zi_get_numeric(
+            lp.as_mut_ptr(),
+            lj.as_mut_ptr(),
+            lx.as_mut_ptr() as *mut f64,
+            std::ptr::null_mut(),
+            up.as_mut_ptr(),
+            ui.as_mut_ptr(),
+            ux.as_mut_ptr() as *mut f64,
+            std::ptr::null_mut(),
+            p.as_mut_ptr(),
+            q.as_mut_ptr(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            do_recip,
+            rs.as_mut_ptr(),
+            numeric,
+        )
     }
 }
 
-impl CsrMatrix<c64> {
-    /// Compute the sparse LU decomposition of the matrix.
-    ///
-    /// Note that the sparse matrix is first converted to
-    /// CSC format before computing the LU.
-    pub fn into_lu(self) -> RlstResult<UmfpackLu<c64>> {
-        self.into_csc().into_lu()
+/// Return the indices as `i32`, which is the index type of UMFPACK.
+///
+/// Indices of type `i32` are borrowed, other index types are converted.
+fn umfpack_indices<I: SparseIndex>(indices: &[I]) -> RlstResult<Cow<'_, [i32]>> {
+    match I::as_i32_slice(indices) {
+        Some(indices) => Ok(Cow::Borrowed(indices)),
+        None => convert_indices(indices).map(Cow::Owned),
     }
 }
 
-impl CscMatrix<f64> {
-    /// Compute the sparse LU decomposition of the matrix.
-    pub fn into_lu(self) -> RlstResult<UmfpackLu<f64>> {
-        let shape = self.shape();
+/// Return the indices as `i32`. Indices of type `i32` are moved, other index types are
+/// converted.
+fn umfpack_indices_owned<I: SparseIndex>(indices: Vec<I>) -> RlstResult<Cow<'static, [i32]>> {
+    match I::into_i32_vec(indices) {
+        Ok(indices) => Ok(Cow::Owned(indices)),
+        Err(indices) => convert_indices(&indices).map(Cow::Owned),
+    }
+}
 
-        if shape[0] != shape[1] {
-            return Err(RlstError::GeneralError(format!(
-                "Matrix is not square. rows: {}, cols: {}",
-                shape[0], shape[1],
-            )));
-        }
+/// Holds the result of the symbolic analysis of Umfpack.
+///
+/// The lifetime `'a` is the lifetime of the matrix if its indices are borrowed.
+pub struct UmfpackSymbolic<'a, T: UmfpackScalar> {
+    shape: [usize; 2],
+    indices: Cow<'a, [i32]>,
+    indptr: Cow<'a, [i32]>,
+    symbolic: *mut c_void,
+    control: UmfpackControl,
+    info: UmfpackInfo,
+    _marker: std::marker::PhantomData<T>,
+}
 
-        let n = shape[0] as i32;
+impl<T: UmfpackScalar> Drop for UmfpackSymbolic<'_, T> {
+    fn drop(&mut self) {
+        unsafe { T::Double::umfpack_free_symbolic(&mut self.symbolic) };
+    }
+}
+
+impl<'a, T: UmfpackScalar> UmfpackSymbolic<'a, T> {
+    /// Compute the symbolic analysis of a square matrix.
+    ///
+    /// UMFPACK needs the indices for the numeric factorisation. Indices of type `i32` are
+    /// borrowed from `mat`, other index types are converted to `i32`.
+    pub fn new<I: SparseIndex>(
+        mat: &'a CscMatrix<T, I>,
+        control: UmfpackControl,
+    ) -> RlstResult<Self> {
+        Self::from_indices(
+            mat.shape(),
+            umfpack_indices(mat.indptr())?,
+            umfpack_indices(mat.indices())?,
+            mat.data(),
+            control,
+        )
+    }
 
-        let mut symbolic = std::ptr::null_mut::<c_void>();
+    /// Compute the symbolic analysis from the CSC arrays of a matrix.
+    fn from_indices(
+        shape: [usize; 2],
+        indptr: Cow<'a, [i32]>,
+        indices: Cow<'a, [i32]>,
+        data: &[T],
+        control: UmfpackControl,
+    ) -> RlstResult<Self> {
+        if shape[0] != shape[1] {
+            return Err(RlstError::MatrixNotSquare(shape[0], shape[1]));
+        }
 
-        let mut umfpack_lu = UmfpackLu::<f64> {
+        let mut symbolic = Self {
             shape,
-            indices: self.indices().iter().map(|&item| item as i32).collect(),
-            indptr: self.indptr().iter().map(|&item| item as i32).collect(),
-            data: self.data().to_vec(),
-            numeric: std::ptr::null_mut::<c_void>(),
+            indices,
+            indptr,
+            symbolic: std::ptr::null_mut::<c_void>(),
+            control,
+            info: UmfpackInfo::default(),
+            _marker: std::marker::PhantomData,
         };
 
+        let data = data
+            .iter()
+            .map(|&value| value.to_double())
+            .collect::<Vec<_>>();
+
         let info = unsafe {
-            umfpack::raw::umfpack_di_symbolic(
-                n,
-                n,
-                umfpack_lu.indptr.as_ptr(),
-                umfpack_lu.indices.as_ptr(),
-                umfpack_lu.data.as_ptr(),
-                &mut symbolic,
-                std::ptr::null(),
-                std::ptr::null_mut(),
+            T::Double::umfpack_symbolic(
+                shape,
+                &symbolic.indptr,
+                &symbolic.indices,
+                &data,
+                &mut symbolic.symbolic,
+                &symbolic.control.control,
+                &mut symbolic.info.info,
             )
         };
 
@@ -198,15 +587,74 @@ impl CscMatrix<f64> {
             return Err(RlstError::UmfpackError(info));
         }
 
+        Ok(symbolic)
+    }
+
+    /// Statistics of the symbolic analysis.
+    pub fn info(&self) -> &UmfpackInfo {
+        &self.info
+    }
+
+    /// Control parameters.
+    pub fn control(&self) -> &UmfpackControl {
+        &self.control
+    }
+
+    /// Compute the numeric factorisation for the matrix values `data`.
+    ///
+    /// `data` contains the values of a matrix with the same sparsity pattern as the matrix
+    /// passed to [UmfpackSymbolic::new].
+    pub fn factorize(self, data: &[T]) -> RlstResult<UmfpackLu<'a, T>> {
+        let mut lu = UmfpackLu {
+            symbolic: self,
+            data: Vec::new(),
+            numeric: std::ptr::null_mut::<c_void>(),
+            info: UmfpackInfo::default(),
+        };
+        lu.refactor(data)?;
+        Ok(lu)
+    }
+}
+
+/// Holds the Umfpack data structures.
+pub struct UmfpackLu<'a, T: UmfpackScalar> {
+    symbolic: UmfpackSymbolic<'a, T>,
+    data: Vec<T::Double>,
+    numeric: *mut c_void,
+    info: UmfpackInfo,
+}
+
+impl<T: UmfpackScalar> Drop for UmfpackLu<'_, T> {
+    fn drop(&mut self) {
+        unsafe { T::Double::umfpack_free_numeric(&mut self.numeric) };
+    }
+}
+
+impl<'a, T: UmfpackScalar> UmfpackLu<'a, T> {
+    /// Recompute the numeric factorisation for new matrix values.
+    ///
+    /// The symbolic analysis is reused. `data` contains the values of a matrix with the
+    /// same sparsity pattern as the original matrix.
+    pub fn refactor(&mut self, data: &[T]) -> RlstResult<()> {
+        if data.len() != self.symbolic.indices.len() {
+            return Err(RlstError::SingleDimensionError {
+                expected: self.symbolic.indices.len(),
+                actual: data.len(),
+            });
+        }
+
+        unsafe { T::Double::umfpack_free_numeric(&mut self.numeric) };
+        self.data = data.iter().map(|&value| value.to_double()).collect();
+
         let info = unsafe {
-            umfpack::raw::umfpack_di_numeric(
-                umfpack_lu.indptr.as_ptr(),
-                umfpack_lu.indices.as_ptr(),
-                umfpack_lu.data.as_ptr(),
-                symbolic,
-                &mut umfpack_lu.numeric,
-                std::ptr::null(),
-                std::ptr::null_mut(),
+            T::Double::umfpack_numeric(
+                &self.symbolic.indptr,
+                &self.symbolic.indices,
+                &self.data,
+                self.symbolic.symbolic,
+                &mut self.numeric,
+                &self.symbolic.control.control,
+                &mut self.info.info,
             )
         };
 
@@ -214,64 +662,125 @@ impl CscMatrix<f64> {
             return Err(RlstError::UmfpackError(info));
         }
 
-        unsafe { umfpack::raw::umfpack_di_free_symbolic(&mut symbolic) };
+        Ok(())
+    }
 
-        Ok(umfpack_lu)
+    /// The symbolic analysis.
+    pub fn symbolic(&self) -> &UmfpackSymbolic<'a, T> {
+        &self.symbolic
     }
-}
 
-impl CscMatrix<c64> {
-    /// Compute the sparse LU decomposition of the matrix.
-    pub fn into_lu(self) -> RlstResult<UmfpackLu<c64>> {
-        let shape = self.shape();
+    /// Statistics of the numeric factorisation.
+    pub fn info(&self) -> &UmfpackInfo {
+        &self.info
+    }
 
-        if shape[0] != shape[1] {
-            return Err(RlstError::GeneralError(format!(
-                "Matrix is not square. rows: {}, cols: {}",
-                shape[0], shape[1],
-            )));
-        }
+    /// Estimate of the reciprocal condition number.
+    pub fn rcond(&self) -> f64 {
+        self.info.rcond()
+    }
+
+    /// Number of floating point operations of the numeric factorisation.
+    pub fn flops(&self) -> f64 {
+        self.info.flops()
+    }
 
-        let n = shape[0] as i32;
+    /// Extract the factors of the LU decomposition.
+    ///
+    /// See [UmfpackFactors] for the relation between the factors and the matrix.
+    pub fn get_factors(&self) -> RlstResult<UmfpackFactors<T>> {
+        let mut lunz = [0; 5];
+        let info = unsafe { T::Double::umfpack_get_lunz(self.numeric, &mut lunz) };
+        if info != 0 {
+            return Err(RlstError::UmfpackError(info));
+        }
 
-        let mut symbolic = std::ptr::null_mut::<c_void>();
+        let [lnz, unz, n_row, n_col, _] = lunz.map(|value| value as usize);
+        let n_inner = std::cmp::min(n_row, n_col);
 
-        let mut umfpack_lu = UmfpackLu::<c64> {
-            shape,
-            indices: self.indices().iter().map(|&item| item as i32).collect(),
-            indptr: self.indptr().iter().map(|&item| item as i32).collect(),
-            data: self.data().to_vec(),
-            numeric: std::ptr::null_mut::<c_void>(),
-        };
+        let mut lp = vec![0; 1 + n_row];
+        let mut lj = vec![0; lnz];
+        let mut lx = vec![T::zero().to_double(); lnz];
+        let mut up = vec![0; 1 + n_col];
+        let mut ui = vec![0; unz];
+        let mut ux = vec![T::zero().to_double(); unz];
+        let mut p = vec![0; n_row];
+        let mut q = vec![0; n_col];
+        let mut do_recip = 0;
+        let mut rs = vec![0.0; n_row];
 
         let info = unsafe {
-            umfpack::raw::umfpack_zi_symbolic(
-                n,
-                n,
-                umfpack_lu.indptr.as_ptr(),
-                umfpack_lu.indices.as_ptr(),
-                umfpack_lu.data.as_ptr() as *const f64,
-                std::ptr::null(),
-                &mut symbolic,
-                std::ptr::null(),
-                std::ptr::null_mut(),
+            T::Double::umfpack_get_numeric(
+                &mut lp,
+                &mut lj,
+                &mut lx,
+                &mut up,
+                &mut ui,
+                &mut ux,
+                &mut p,
+                &mut q,
+                &mut do_recip,
+                &mut rs,
+                self.numeric,
             )
         };
-
         if info != 0 {
             return Err(RlstError::UmfpackError(info));
         }
 
+        let to_usize = |values: Vec<i32>| values.into_iter().map(|value| value as usize).collect();
+        let to_single = |values: Vec<T::Double>| {
+            values
+                .into_iter()
+                .map(|value| T::from_double(value))
+                .collect()
+        };
+
+        // Umfpack returns L by rows.
+        let l =
+            CsrMatrix::new([n_row, n_inner], to_usize(lj), to_usize(lp), to_single(lx)).into_csc();
+        let u = CscMatrix::new([n_inner, n_col], to_usize(ui), to_usize(up), to_single(ux));
+
+        // Return the scale factors such that rows are always multiplied.
+        let row_scaling = rs
+            .into_iter()
+            .map(|value| T::real(if do_recip != 0 { value } else { 1.0 / value }))
+            .collect();
+
+        Ok(UmfpackFactors {
+            l,
+            u,
+            row_permutation: to_usize(p),
+            col_permutation: to_usize(q),
+            row_scaling,
+        })
+    }
+
+    /// Solve with a contiguous right-hand side.
+    fn solve_slice(&self, rhs: &[T], x: &mut [T], trans: TransMode) -> RlstResult<()> {
+        let sys = match trans {
+            TransMode::NoTrans => umfpack::raw::UMFPACK_A,
+            TransMode::Trans => umfpack::raw::UMFPACK_Aat,
+            TransMode::ConjTrans => umfpack::raw::UMFPACK_At,
+            _ => panic!("Transpose mode not supported for Umfpack"),
+        };
+
+        let rhs_double = rhs
+            .iter()
+            .map(|&value| value.to_double())
+            .collect::<Vec<_>>();
+        let mut x_double = vec![T::zero().to_double(); x.len()];
+
         let info = unsafe {
-            umfpack::raw::umfpack_zi_numeric(
-                umfpack_lu.indptr.as_ptr(),
-                umfpack_lu.indices.as_ptr(),
-                umfpack_lu.data.as_ptr() as *const f64,
-                std::ptr::null(),
-                symbolic,
-                &mut umfpack_lu.numeric,
-                std::ptr::null(),
-                std::ptr::null_mut(),
+            T::Double::umfpack_solve(
+                sys as i32,
+                &self.symbolic.indptr,
+                &self.symbolic.indices,
+                &self.data,
+                &mut x_double,
+                &rhs_double,
+                self.numeric,
+                &self.symbolic.control.control,
             )
         };
 
@@ -279,8 +788,151 @@ impl CscMatrix<c64> {
             return Err(RlstError::UmfpackError(info));
         }
 
-        unsafe { umfpack::raw::umfpack_di_free_symbolic(&mut symbolic) };
+        for (elem, &value) in x.iter_mut().zip(x_double.iter()) {
+            *elem = T::from_double(value);
+        }
+
+        Ok(())
+    }
+
+    /// Solve for a given right-hand side.
+    pub fn solve<
+        ArrayImplX: crate::dense::traits::RandomAccessByValue<1, Item = T>
+            + Shape<1>
+            + RawAccessMut<Item = T>
+            + Stride<1>,
+        ArrayImplRhs: crate::dense::traits::RandomAccessByValue<1, Item = T>
+            + Shape<1>
+            + RawAccess<Item = T>
+            + Stride<1>,
+    >(
+        &self,
+        rhs: Array<T, ArrayImplRhs, 1>,
+        mut x: Array<T, ArrayImplX, 1>,
+        trans: TransMode,
+    ) -> crate::dense::types::RlstResult<()> {
+        assert_eq!(rhs.stride()[0], 1);
+        assert_eq!(x.stride()[0], 1);
+
+        let n = self.symbolic.shape[0];
+        assert_eq!(x.shape()[0], n);
+        assert_eq!(rhs.shape()[0], n);
+
+        self.solve_slice(rhs.data(), x.data_mut(), trans)
+    }
+
+    /// Solve for several right-hand sides given as the columns of `rhs`.
+    ///
+    /// The columns of `rhs` and `x` must be contiguous in memory.
+    pub fn solve_mat<
+        ArrayImplX: crate::dense::traits::RandomAccessByValue<2, Item = T>
+            + Shape<2>
+            + RawAccessMut<Item = T>
+            + Stride<2>,
+        ArrayImplRhs: crate::dense::traits::RandomAccessByValue<2, Item = T>
+            + Shape<2>
+            + RawAccess<Item = T>
+            + Stride<2>,
+    >(
+        &self,
+        rhs: Array<T, ArrayImplRhs, 2>,
+        mut x: Array<T, ArrayImplX, 2>,
+        trans: TransMode,
+    ) -> crate::dense::types::RlstResult<()> {
+        assert_eq!(rhs.stride()[0], 1);
+        assert_eq!(x.stride()[0], 1);
+
+        let n = self.symbolic.shape[0];
+        assert_eq!(rhs.shape()[0], n);
+        assert_eq!(x.shape(), rhs.shape());
+
+        let rhs_stride = rhs.stride()[1];
+        let x_stride = x.stride()[1];
+        for col in 0..rhs.shape()[1] {
+            self.solve_slice(
+                &rhs.data()[col * rhs_stride..col * rhs_stride + n],
+                &mut x.data_mut()[col * x_stride..col * x_stride + n],
+                trans,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The factors of an Umfpack LU decomposition.
+///
+/// The factors satisfy `P R A Q = L U`, where `R` is the diagonal matrix of the row scaling
+/// factors, `P` is the permutation with `(P A)[k, :] = A[row_permutation[k], :]` and `Q` is
+/// the permutation with `(A Q)[:, k] = A[:, col_permutation[k]]`. `L` is unit lower
+/// triangular and `U` is upper triangular.
+pub struct UmfpackFactors<T: UmfpackScalar> {
+    l: CscMatrix<T>,
+    u: CscMatrix<T>,
+    row_permutation: Vec<usize>,
+    col_permutation: Vec<usize>,
+    row_scaling: Vec<T::Real>,
+}
+
+impl<T: UmfpackScalar> UmfpackFactors<T> {
+    /// The unit lower triangular factor `L`.
+    pub fn l(&self) -> &CscMatrix<T> {
+        &self.l
+    }
+
+    /// The upper triangular factor `U`.
+    pub fn u(&self) -> &CscMatrix<T> {
+        &self.u
+    }
+
+    /// The row permutation. Entry `k` is the row of `A` that is the `k`-th pivot row.
+    pub fn row_permutation(&self) -> &[usize] {
+        &self.row_permutation
+    }
+
+    /// The column permutation. Entry `k` is the column of `A` that is the `k`-th pivot column.
+    pub fn col_permutation(&self) -> &[usize] {
+        &self.col_permutation
+    }
+
+    /// The factors by which the rows of `A` are multiplied.
+    pub fn row_scaling(&self) -> &[T::Real] {
+        &self.row_scaling
+    }
+}
 
-        Ok(umfpack_lu)
+impl<T: UmfpackScalar, I: SparseIndex> CsrMatrix<T, I> {
+    /// Compute the sparse LU decomposition of the matrix.
+    ///
+    /// Note that the sparse matrix is first converted to
+    /// CSC format before computing the LU.
+    pub fn into_lu(self) -> RlstResult<UmfpackLu<'static, T>> {
+        self.into_csc().into_lu()
+    }
+}
+
+impl<T: UmfpackScalar, I: SparseIndex> CscMatrix<T, I> {
+    /// Compute the sparse LU decomposition of the matrix.
+    ///
+    /// Indices of type `i32` are moved into the decomposition without copying.
+    pub fn into_lu(self) -> RlstResult<UmfpackLu<'static, T>> {
+        let shape = self.shape();
+        let (indices, indptr, data) = self.into_parts();
+        UmfpackSymbolic::from_indices(
+            shape,
+            umfpack_indices_owned(indptr)?,
+            umfpack_indices_owned(indices)?,
+            &data,
+            UmfpackControl::default(),
+        )?
+        .factorize(&data)
+    }
+
+    /// Compute the sparse LU decomposition of the matrix with the given control parameters.
+    ///
+    /// To factorise several matrices with the same sparsity pattern use [UmfpackSymbolic]
+    /// and [UmfpackLu::refactor].
+    pub fn lu(&self, control: UmfpackControl) -> RlstResult<UmfpackLu<'_, T>> {
+        UmfpackSymbolic::new(self, control)?.factorize(self.data())
     }
 }
@@ -1,4 +1,16 @@
-//! Control BLAS threading if possible
+//! Control threading of RLST kernels and BLAS
+//!
+//! The number of threads set with [set_num_threads] is used by the parallel array
+//! kernels (feature `rayon`) and, with feature `internal_blis`, by Blis. The number of
+//! threads of other BLAS libraries is not changed, see [blas_num_threads_applied].
+//!
+//! Array kernels with fewer elements than [get_parallel_threshold] are always evaluated
+//! serially since the overhead of distributing the work outweighs the benefit.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+#[cfg(feature = "rayon")]
+use std::sync::{Arc, RwLock};
 
 #[cfg(feature = "internal_blis")]
 extern "C" {
@@ -6,30 +18,137 @@ extern "C" {
     fn bli_thread_get_num_threads() -> i64;
 }
 
-#[cfg(feature = "internal_blis")]
-/// Get the current number of threads used by Blis.
-pub fn get_num_threads() -> usize {
-    let threads = unsafe { bli_thread_get_num_threads() };
+/// Default number of elements below which array kernels are evaluated serially.
+pub const DEFAULT_PARALLEL_THRESHOLD: usize = 1 << 15;
+
+// A value of zero means that the number of threads has not been set.
+static NUM_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+static BLAS_NUM_THREADS_APPLIED: AtomicBool = AtomicBool::new(false);
 
-    threads as usize
+static PARALLEL_THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_PARALLEL_THRESHOLD);
+
+#[cfg(feature = "rayon")]
+static THREAD_POOL: RwLock<Option<Arc<rayon::ThreadPool>>> = RwLock::new(None);
+
+/// Get the current number of threads.
+///
+/// If no number of threads was set, this is the default of the Rayon thread pool
+/// with feature `rayon`, the number of Blis threads with feature `internal_blis` and
+/// one otherwise.
+pub fn get_num_threads() -> usize {
+    match NUM_THREADS.load(Ordering::Relaxed) {
+        0 => default_num_threads(),
+        nthreads => nthreads,
+    }
 }
 
-#[cfg(feature = "internal_blis")]
-/// Set threads to a given number of threads.
+/// Set the number of threads used by RLST kernels and BLAS.
 pub fn set_num_threads(nthreads: usize) {
-    unsafe { bli_thread_set_num_threads(nthreads as i64) };
+    assert!(nthreads > 0, "Number of threads must be positive.");
+
+    #[cfg(feature = "rayon")]
+    {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(nthreads)
+            .build()
+            .expect("Could not create thread pool.");
+        *THREAD_POOL.write().unwrap() = Some(Arc::new(pool));
+    }
+
+    NUM_THREADS.store(nthreads, Ordering::Relaxed);
+
+    BLAS_NUM_THREADS_APPLIED.store(set_blas_num_threads(nthreads), Ordering::Relaxed);
+}
+
+/// Return true if the last call to [set_num_threads] also set the number of BLAS threads.
+pub fn blas_num_threads_applied() -> bool {
+    BLAS_NUM_THREADS_APPLIED.load(Ordering::Relaxed)
 }
 
-#[cfg(feature = "internal_blis")]
 /// Set threads to the number of logical cpus.
 pub fn enable_threading() {
-    let num_cpus = num_cpus::get();
-
-    unsafe { bli_thread_set_num_threads(num_cpus as i64) };
+    set_num_threads(
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+    )
 }
 
-#[cfg(feature = "internal_blis")]
 /// Set number of threads to 1.
 pub fn disable_threading() {
-    unsafe { bli_thread_set_num_threads(1) };
+    set_num_threads(1)
+}
+
+#[cfg(feature = "internal_blis")]
+fn set_blas_num_threads(nthreads: usize) -> bool {
+    unsafe { bli_thread_set_num_threads(nthreads as i64) };
+    true
+}
+
+#[cfg(not(feature = "internal_blis"))]
+fn set_blas_num_threads(_nthreads: usize) -> bool {
+    false
+}
+
+/// Get the number of elements below which array kernels are evaluated serially.
+pub fn get_parallel_threshold() -> usize {
+    PARALLEL_THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// Set the number of elements below which array kernels are evaluated serially.
+pub fn set_parallel_threshold(threshold: usize) {
+    PARALLEL_THRESHOLD.store(threshold, Ordering::Relaxed);
+}
+
+/// Bound on arrays that parallel kernels read from several threads.
+///
+/// This is `Sync` with feature `rayon` and is implemented for all types otherwise.
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// Bound on arrays that parallel kernels read from several threads.
+///
+/// This is `Sync` with feature `rayon` and is implemented for all types otherwise.
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// Return true if a kernel operating on `nelements` elements should run in parallel.
+#[cfg(feature = "rayon")]
+pub(crate) fn use_parallel(nelements: usize) -> bool {
+    nelements >= get_parallel_threshold() && get_num_threads() > 1
+}
+
+/// Run `op` within the RLST thread pool.
+///
+/// Parallel iterators inside `op` use the number of threads set with [set_num_threads],
+/// or the global Rayon thread pool if no number of threads was set.
+#[cfg(feature = "rayon")]
+pub(crate) fn install<R: Send, F: FnOnce() -> R + Send>(op: F) -> R {
+    let pool = THREAD_POOL.read().unwrap().clone();
+    match pool {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn default_num_threads() -> usize {
+    rayon::current_num_threads()
+}
+
+#[cfg(all(not(feature = "rayon"), feature = "internal_blis"))]
+fn default_num_threads() -> usize {
+    unsafe { bli_thread_get_num_threads() as usize }
+}
+
+#[cfg(all(not(feature = "rayon"), not(feature = "internal_blis")))]
+fn default_num_threads() -> usize {
+    1
 }
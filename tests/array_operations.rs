@@ -564,3 +564,347 @@ mat_mul_test_impl!(f64, 1E-14);
 mat_mul_test_impl!(f32, 1E-5);
 mat_mul_test_impl!(c32, 1E-5);
 mat_mul_test_impl!(c64, 1E-14);
+
+#[test]
+fn test_reshape_view() {
+    let shape = [3, 4, 5];
+    let mut arr = rlst_dynamic_array3!(f64, shape);
+    arr.fill_from_seed_equally_distributed(0);
+
+    let reshaped = arr.reshape([12, 5]);
+
+    // A contiguous array can always be reshaped without copying.
+    assert!(reshaped.is_view());
+    assert_eq!(reshaped.shape(), [12, 5]);
+    assert_eq!(reshaped.stride(), [1, 12]);
+
+    for (actual, expected) in reshaped.iter().zip(arr.iter()) {
+        assert_eq!(actual, expected);
+    }
+
+    assert_eq!(reshaped[[7, 3]], arr[[1, 2, 3]]);
+}
+
+#[test]
+fn test_reshape_subview() {
+    let shape = [6, 4, 5];
+    let mut arr = rlst_dynamic_array3!(f64, shape);
+    arr.fill_from_seed_equally_distributed(0);
+
+    // Full slabs are contiguous, so merging the last two axes does not require a copy.
+    let subview = arr.view().into_subview([0, 0, 1], [6, 4, 2]);
+    let reshaped = subview.reshape([6, 8]);
+    assert!(reshaped.is_view());
+    assert_eq!(reshaped.stride(), [1, 6]);
+    assert_eq!(reshaped[[2, 5]], arr[[2, 1, 2]]);
+
+    // Partial columns are not contiguous, so merging the first two axes requires a copy.
+    let subview = arr.view().into_subview([1, 1, 1], [3, 2, 3]);
+    let reshaped = subview.reshape([6, 3]);
+    assert!(!reshaped.is_view());
+    assert_eq!(reshaped.stride(), [1, 6]);
+
+    for (actual, expected) in reshaped.iter().zip(subview.iter()) {
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn test_reshape_insert_axes() {
+    let mut arr = rlst_dynamic_array2!(f64, [4, 6]);
+    arr.fill_from_seed_equally_distributed(0);
+
+    let reshaped = arr.reshape([1, 4, 1, 6, 1]);
+    assert!(reshaped.is_view());
+    assert_eq!(reshaped[[0, 3, 0, 5, 0]], arr[[3, 5]]);
+
+    let flat = reshaped.reshape([24]);
+    assert!(flat.is_view());
+    assert_eq!(flat[[23]], arr[[3, 5]]);
+}
+
+#[test]
+fn test_to_contiguous() {
+    let mut arr = rlst_dynamic_array2!(f64, [4, 6]);
+    arr.fill_from_seed_equally_distributed(0);
+
+    let contiguous = arr.view().transpose().to_contiguous();
+
+    assert_eq!(contiguous.shape(), [6, 4]);
+    assert_eq!(contiguous.stride(), [1, 6]);
+
+    for row in 0..4 {
+        for col in 0..6 {
+            assert_eq!(contiguous[[col, row]], arr[[row, col]]);
+        }
+    }
+
+    let subview = arr.view().into_subview([1, 2], [2, 3]);
+    let contiguous = subview.to_contiguous();
+
+    assert_eq!(contiguous.stride(), [1, 2]);
+    assert_array_relative_eq!(contiguous, subview, 1E-14);
+}
+
+#[test]
+fn test_row_major_layout() {
+    let data = (0..6).map(|elem| elem as f64).collect::<Vec<_>>();
+    let arr = SliceArray::<f64, 2>::from_shape_row_major(&data, [2, 3]);
+
+    assert_eq!(arr.stride(), [3, 1]);
+    assert_eq!(arr.memory_layout(), Some(MemoryLayout::RowMajor));
+
+    for row in 0..2 {
+        for col in 0..3 {
+            assert_eq!(arr[[row, col]], data[3 * row + col]);
+        }
+    }
+
+    let mut arr = DynamicArray::<f64, 3>::from_shape_row_major([2, 3, 4]);
+    assert_eq!(arr.stride(), [12, 4, 1]);
+
+    arr.resize_in_place([4, 5, 2]);
+    assert_eq!(arr.stride(), [10, 2, 1]);
+
+    let arr = rlst_dynamic_array2!(f64, [3, 3]);
+    assert_eq!(arr.memory_layout(), Some(MemoryLayout::ColumnMajor));
+    assert_eq!(
+        arr.view().into_subview([0, 0], [2, 2]).memory_layout(),
+        None
+    );
+}
+
+macro_rules! impl_row_major_gemm_tests {
+    ($scalar:ty, $tol:expr) => {
+        paste! {
+            #[test]
+            fn [<test_gemm_row_major_$scalar>]() {
+                let mut a = rlst_dynamic_array2!($scalar, [4, 3]);
+                let mut b = rlst_dynamic_array2!($scalar, [3, 5]);
+                a.fill_from_seed_equally_distributed(0);
+                b.fill_from_seed_equally_distributed(1);
+
+                let mut a_row = DynamicArray::<$scalar, 2>::from_shape_row_major([4, 3]);
+                let mut b_row = DynamicArray::<$scalar, 2>::from_shape_row_major([3, 5]);
+                a_row.fill_from(a.view());
+                b_row.fill_from(b.view());
+
+                let expected = empty_array::<$scalar, 2>().simple_mult_into_resize(a.view(), b.view());
+
+                // Row-major inputs with column-major output.
+                let actual = rlst_dynamic_array2!($scalar, [4, 5])
+                    .simple_mult_into(a_row.view(), b_row.view());
+                assert_array_relative_eq!(actual, expected, $tol);
+
+                // Mixed inputs with row-major output.
+                let actual = DynamicArray::<$scalar, 2>::from_shape_row_major([4, 5])
+                    .simple_mult_into(a.view(), b_row.view());
+                assert_array_relative_eq!(actual, expected, $tol);
+
+                // Conjugate transposes of row-major inputs.
+                let mut at_row = DynamicArray::<$scalar, 2>::from_shape_row_major([3, 4]);
+                let mut bt_row = DynamicArray::<$scalar, 2>::from_shape_row_major([5, 3]);
+                at_row.fill_from(a.view().conj().transpose());
+                bt_row.fill_from(b.view().conj().transpose());
+
+                let actual = DynamicArray::<$scalar, 2>::from_shape_row_major([4, 5]).mult_into(
+                    TransMode::ConjTrans,
+                    TransMode::ConjTrans,
+                    1.0.into(),
+                    at_row.view(),
+                    bt_row.view(),
+                    0.0.into(),
+                );
+                assert_array_relative_eq!(actual, expected, $tol);
+
+                let mut a_conj = rlst_dynamic_array2!($scalar, [4, 3]);
+                a_conj.fill_from(a.view().conj());
+                let actual = rlst_dynamic_array2!($scalar, [4, 5]).mult_into(
+                    TransMode::ConjNoTrans,
+                    TransMode::NoTrans,
+                    1.0.into(),
+                    a_conj.view(),
+                    b_row.view(),
+                    0.0.into(),
+                );
+                assert_array_relative_eq!(actual, expected, $tol);
+            }
+        }
+    };
+}
+
+impl_row_major_gemm_tests!(f32, 1E-5);
+impl_row_major_gemm_tests!(f64, 1E-12);
+impl_row_major_gemm_tests!(c32, 1E-5);
+impl_row_major_gemm_tests!(c64, 1E-12);
+
+#[test]
+fn test_sort_and_argsort() {
+    let mut arr = rlst_dynamic_array1!(f64, [6]);
+    arr.data_mut()
+        .copy_from_slice(&[3.0, f64::NAN, -1.0, 2.0, -1.0, 0.5]);
+
+    let perm = arr.argsort();
+    assert_eq!(perm, vec![2, 4, 5, 3, 0, 1]);
+
+    arr.sort();
+    assert_eq!(&arr.data()[..5], &[-1.0, -1.0, 0.5, 2.0, 3.0]);
+    assert!(arr[[5]].is_nan());
+
+    let mut arr = rlst_dynamic_array1!(f64, [4]);
+    arr.data_mut().copy_from_slice(&[1.0, 4.0, 2.0, 3.0]);
+    arr.sort_by(|first, second| second.partial_cmp(first).unwrap());
+    assert_eq!(arr.data(), &[4.0, 3.0, 2.0, 1.0]);
+}
+
+#[test]
+fn test_sort_subview() {
+    let mut arr = rlst_dynamic_array2!(f64, [4, 3]);
+    arr.fill_from_seed_equally_distributed(0);
+    let mut expected = rlst_dynamic_array2!(f64, [4, 3]);
+    expected.fill_from(arr.view());
+
+    // Sort the second row in columns 1 and 2 only.
+    arr.view_mut()
+        .into_subview([1, 1], [1, 2])
+        .slice::<1>(0, 0)
+        .sort();
+
+    let (small, large) = if expected[[1, 1]] <= expected[[1, 2]] {
+        (expected[[1, 1]], expected[[1, 2]])
+    } else {
+        (expected[[1, 2]], expected[[1, 1]])
+    };
+    assert_eq!(arr[[1, 1]], small);
+    assert_eq!(arr[[1, 2]], large);
+    assert_eq!(arr[[1, 0]], expected[[1, 0]]);
+    assert_eq!(arr[[0, 1]], expected[[0, 1]]);
+}
+
+#[test]
+fn test_sort_axis() {
+    let mut arr = rlst_dynamic_array2!(f64, [5, 4]);
+    arr.fill_from_seed_equally_distributed(0);
+
+    let mut by_cols = rlst_dynamic_array2!(f64, [5, 4]);
+    by_cols.fill_from(arr.view());
+    by_cols.sort_axis(0);
+
+    for col in 0..4 {
+        for row in 1..5 {
+            assert!(by_cols[[row - 1, col]] <= by_cols[[row, col]]);
+        }
+        assert_relative_eq!(
+            by_cols.view().slice::<1>(1, col).sum(),
+            arr.view().slice::<1>(1, col).sum(),
+            epsilon = 1E-14
+        );
+    }
+
+    arr.sort_axis(1);
+    for row in 0..5 {
+        for col in 1..4 {
+            assert!(arr[[row, col - 1]] <= arr[[row, col]]);
+        }
+    }
+}
+
+#[test]
+fn test_unique_and_searchsorted() {
+    let mut arr = rlst_dynamic_array1!(f64, [7]);
+    arr.data_mut()
+        .copy_from_slice(&[2.0, f64::NAN, 1.0, 2.0, 5.0, f64::NAN, 1.0]);
+
+    let unique = arr.unique();
+    assert_eq!(unique.len(), 4);
+    assert_eq!(&unique.data()[..3], &[1.0, 2.0, 5.0]);
+    assert!(unique[[3]].is_nan());
+
+    let mut sorted = rlst_dynamic_array1!(f64, [5]);
+    sorted
+        .data_mut()
+        .copy_from_slice(&[1.0, 2.0, 2.0, 2.0, 5.0]);
+
+    assert_eq!(sorted.searchsorted(2.0, SearchSide::Left), 1);
+    assert_eq!(sorted.searchsorted(2.0, SearchSide::Right), 4);
+    assert_eq!(sorted.searchsorted(0.0, SearchSide::Left), 0);
+    assert_eq!(sorted.searchsorted(6.0, SearchSide::Right), 5);
+    assert_eq!(sorted.searchsorted(3.0, SearchSide::Left), 4);
+
+    let indices = [7_usize, 3, 3, 9, 0];
+    let indices = rlst_array_from_slice1!(&indices, [5]);
+    assert_eq!(indices.unique().data(), &[0, 3, 7, 9]);
+    assert_eq!(indices.argsort(), vec![4, 1, 2, 0, 3]);
+}
+
+#[test]
+fn test_contract() {
+    let mut a = rlst_dynamic_array3!(f64, [3, 4, 5]);
+    let mut b = rlst_dynamic_array2!(f64, [5, 2]);
+    a.fill_from_seed_equally_distributed(0);
+    b.fill_from_seed_equally_distributed(1);
+
+    let mut expected = rlst_dynamic_array3!(f64, [3, 4, 2]);
+    for (i, j, k, l) in itertools::iproduct!(0..3, 0..4, 0..5, 0..2) {
+        expected[[i, j, l]] += a[[i, j, k]] * b[[k, l]];
+    }
+
+    let actual: DynamicArray<f64, 3> = contract(a.view(), &[2], b.view(), &[0]);
+    assert_array_relative_eq!(actual, expected, 1E-12);
+
+    let actual: DynamicArray<f64, 3> = einsum("ijk,kl->ijl", a.view(), b.view()).unwrap();
+    assert_array_relative_eq!(actual, expected, 1E-12);
+
+    // Contract two non-adjacent axes given in a different order in each operand.
+    let mut c = rlst_dynamic_array3!(f64, [5, 2, 3]);
+    c.fill_from_seed_equally_distributed(2);
+
+    let mut expected = rlst_dynamic_array2!(f64, [4, 2]);
+    for (i, j, k, l) in itertools::iproduct!(0..3, 0..4, 0..5, 0..2) {
+        expected[[j, l]] += a[[i, j, k]] * c[[k, l, i]];
+    }
+
+    let actual: DynamicArray<f64, 2> = contract(a.view(), &[0, 2], c.view(), &[2, 0]);
+    assert_array_relative_eq!(actual, expected, 1E-12);
+
+    let actual: DynamicArray<f64, 2> = einsum("ijk,kli->jl", a.view(), c.view()).unwrap();
+    assert_array_relative_eq!(actual, expected, 1E-12);
+}
+
+#[test]
+fn test_einsum_output_order() {
+    let mut a = rlst_dynamic_array3!(c64, [3, 4, 5]);
+    let mut b = rlst_dynamic_array2!(c64, [2, 5]);
+    a.fill_from_seed_equally_distributed(0);
+    b.fill_from_seed_equally_distributed(1);
+
+    let mut expected = rlst_dynamic_array3!(c64, [2, 4, 3]);
+    for (i, j, k, l) in itertools::iproduct!(0..3, 0..4, 0..5, 0..2) {
+        expected[[l, j, i]] += a[[i, j, k]] * b[[l, k]];
+    }
+
+    let actual: DynamicArray<c64, 3> = einsum("ijk,lk->lji", a.view(), b.view()).unwrap();
+    assert_array_relative_eq!(actual, expected, 1E-12);
+
+    // Without an explicit output the free labels are sorted alphabetically.
+    let actual: DynamicArray<c64, 3> = einsum("lk,ijk", b.view(), a.view()).unwrap();
+    let expected = expected.permute_axes([2, 1, 0]).to_contiguous();
+    assert_eq!(actual.shape(), [3, 4, 2]);
+    assert_array_relative_eq!(actual, expected, 1E-12);
+}
+
+#[test]
+fn test_einsum_unsupported() {
+    let a = rlst_dynamic_array2!(f64, [3, 3]);
+    let b = rlst_dynamic_array2!(f64, [3, 3]);
+
+    assert!(einsum::<_, _, _, 2, 2, 1>("ii,ij->j", a.view(), b.view()).is_err());
+    assert!(einsum::<_, _, _, 2, 2, 2>("ij,ij->ij", a.view(), b.view()).is_err());
+    assert!(einsum::<_, _, _, 2, 2, 1>("ij,jk->i", a.view(), b.view()).is_err());
+    assert!(einsum::<_, _, _, 2, 2, 2>("ij,jk->ik", a.view(), b.view()).is_ok());
+    let err = einsum::<_, _, _, 2, 2, 3>("ij,jk->ik", a.view(), b.view()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Output of 'ij,jk->ik' has rank 2 but the result array has rank 3."
+    );
+}
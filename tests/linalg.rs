@@ -447,3 +447,205 @@ impl_tests!(f32, 1E-5);
 impl_tests!(f64, 1E-12);
 impl_tests!(c32, 1E-5);
 impl_tests!(c64, 1E-12);
+
+macro_rules! impl_row_major_tests {
+        ($scalar:ty, $tol:expr) => {
+            paste! {
+
+                #[test]
+                fn [<test_inverse_row_major_$scalar>]() {
+                    let n = 4;
+
+                    let mut a = DynamicArray::<$scalar, 2>::from_shape_row_major([n, n]);
+                    let mut b = rlst_dynamic_array2!($scalar, [n, n]);
+
+                    let mut ident = rlst_dynamic_array2!($scalar, [n, n]);
+                    ident.set_identity();
+
+                    a.fill_from_seed_equally_distributed(0);
+                    b.fill_from(a.view());
+
+                    a.view_mut().into_inverse_alloc().unwrap();
+
+                    let actual = empty_array::<$scalar, 2>().simple_mult_into_resize(a.view(), b.view());
+
+                    assert_array_abs_diff_eq!(actual, ident, $tol);
+                }
+
+                #[test]
+                fn [<test_lu_solve_row_major_$scalar>]() {
+                    let dim = [12, 12];
+                    let mut arr = DynamicArray::<$scalar, 2>::from_shape_row_major(dim);
+                    arr.fill_from_seed_equally_distributed(0);
+                    let mut x_actual = rlst_dynamic_array1!($scalar, [dim[0]]);
+                    x_actual.fill_from_seed_equally_distributed(1);
+
+                    let mut arr_col = rlst_dynamic_array2!($scalar, dim);
+                    arr_col.fill_from(arr.view());
+                    let det_expected = LuDecomposition::<$scalar, _>::new(arr_col.view_mut()).unwrap().det();
+                    arr_col.fill_from(arr.view());
+
+                    let mut rhs = rlst_dynamic_array1!($scalar, [dim[0]]);
+                    let mut rhs_trans = rlst_dynamic_array1!($scalar, [dim[0]]);
+                    let mut rhs_conj_trans = rlst_dynamic_array1!($scalar, [dim[0]]);
+                    rhs.view_mut().simple_mult_into_resize(arr_col.view(), x_actual.view());
+                    rhs_trans.view_mut().mult_into_resize(
+                        TransMode::Trans,
+                        TransMode::NoTrans,
+                        1.0.into(),
+                        arr_col.view(),
+                        x_actual.view(),
+                        0.0.into(),
+                    );
+                    rhs_conj_trans.view_mut().mult_into_resize(
+                        TransMode::ConjTrans,
+                        TransMode::NoTrans,
+                        1.0.into(),
+                        arr_col.view(),
+                        x_actual.view(),
+                        0.0.into(),
+                    );
+
+                    let lu = LuDecomposition::<$scalar, _>::new(arr).unwrap();
+                    lu.solve_vec(TransMode::NoTrans, rhs.view_mut()).unwrap();
+                    lu.solve_vec(TransMode::Trans, rhs_trans.view_mut()).unwrap();
+                    lu.solve_vec(TransMode::ConjTrans, rhs_conj_trans.view_mut()).unwrap();
+
+                    assert_array_relative_eq!(x_actual, rhs, $tol);
+                    assert_array_relative_eq!(x_actual, rhs_trans, $tol);
+                    assert_array_relative_eq!(x_actual, rhs_conj_trans, $tol);
+                    approx::assert_relative_eq!(lu.det(), det_expected, max_relative = $tol);
+                }
+
+                #[test]
+                fn [<test_lu_factors_row_major_$scalar>]() {
+                    for dim in [[12, 12], [8, 20], [20, 8]] {
+                        let mut arr = DynamicArray::<$scalar, 2>::from_shape_row_major(dim);
+                        arr.fill_from_seed_normally_distributed(0);
+                        let mut expected = rlst_dynamic_array2!($scalar, dim);
+                        expected.fill_from(arr.view());
+
+                        let lu = LuDecomposition::<$scalar, _>::new(arr).unwrap();
+
+                        let mut l_mat = empty_array::<$scalar, 2>();
+                        let mut u_mat = empty_array::<$scalar, 2>();
+                        let mut p_mat = empty_array::<$scalar, 2>();
+
+                        lu.get_l_resize(l_mat.view_mut());
+                        lu.get_u_resize(u_mat.view_mut());
+                        lu.get_p_resize(p_mat.view_mut());
+
+                        assert_eq!(p_mat.shape(), [dim[1], dim[1]]);
+
+                        // The columns are pivoted for row-major arrays, so that AP = LU.
+                        let lhs = empty_array::<$scalar, 2>().simple_mult_into_resize(expected.view(), p_mat);
+                        let rhs = empty_array::<$scalar, 2>().simple_mult_into_resize(l_mat, u_mat);
+
+                        assert_array_relative_eq!(lhs, rhs, $tol);
+                    }
+                }
+
+                #[test]
+                fn [<test_qr_row_major_$scalar>]() {
+                    for shape in [[8, 5], [5, 8]] {
+                        let k = std::cmp::min(shape[0], shape[1]);
+
+                        let mut mat = DynamicArray::<$scalar, 2>::from_shape_row_major(shape);
+                        mat.fill_from_seed_equally_distributed(0);
+                        let mut expected = rlst_dynamic_array2!($scalar, shape);
+                        expected.fill_from(mat.view());
+
+                        let mut r_mat = rlst_dynamic_array2!($scalar, [k, shape[1]]);
+                        let mut q_mat = rlst_dynamic_array2!($scalar, [shape[0], k]);
+                        let mut p_mat = rlst_dynamic_array2!($scalar, [shape[1], shape[1]]);
+                        let mut p_trans = rlst_dynamic_array2!($scalar, [shape[1], shape[1]]);
+                        let mut ident = rlst_dynamic_array2!($scalar, [k, k]);
+                        ident.set_identity();
+
+                        let qr = QrDecomposition::<$scalar, _>::new(mat).unwrap();
+
+                        qr.get_r(r_mat.view_mut());
+                        qr.get_q_alloc(q_mat.view_mut()).unwrap();
+                        qr.get_p(p_mat.view_mut());
+
+                        p_trans.fill_from(p_mat.transpose());
+
+                        let actual = empty_array::<$scalar, 2>().simple_mult_into_resize(
+                            empty_array::<$scalar, 2>().simple_mult_into_resize(q_mat.view(), r_mat),
+                            p_trans,
+                        );
+
+                        assert_array_relative_eq!(actual, expected, $tol);
+
+                        let qtq = empty_array::<$scalar, 2>().mult_into_resize(
+                            TransMode::ConjTrans,
+                            TransMode::NoTrans,
+                            1.0.into(),
+                            q_mat.view(),
+                            q_mat.view(),
+                            0.0.into(),
+                        );
+
+                        assert_array_abs_diff_eq!(qtq, ident, $tol);
+                    }
+                }
+
+                #[test]
+                fn [<test_qr_row_major_pivoting_$scalar>]() {
+                    // A rank 3 matrix whose last column has the largest norm.
+                    let mut b = rlst_dynamic_array2!($scalar, [6, 3]);
+                    let mut c = rlst_dynamic_array2!($scalar, [3, 5]);
+                    b.fill_from_seed_equally_distributed(0);
+                    c.fill_from_seed_equally_distributed(1);
+                    for row in 0..3 {
+                        c[[row, 4]] *= <$scalar as RlstScalar>::from_real(10.0);
+                    }
+                    let col_major = empty_array::<$scalar, 2>().simple_mult_into_resize(b, c);
+
+                    let mut row_major = DynamicArray::<$scalar, 2>::from_shape_row_major([6, 5]);
+                    row_major.fill_from(col_major.view());
+
+                    let perm_col_major = QrDecomposition::<$scalar, _>::new(col_major).unwrap().get_perm();
+                    let perm_row_major = QrDecomposition::<$scalar, _>::new(row_major).unwrap().get_perm();
+
+                    assert_eq!(perm_col_major[0], 4);
+                    assert_eq!(perm_row_major, perm_col_major);
+                }
+
+                #[test]
+                fn [<test_svd_row_major_$scalar>]() {
+                    let (m, n) = (8, 5);
+                    let k = std::cmp::min(m, n);
+
+                    let mut mat = DynamicArray::<$scalar, 2>::from_shape_row_major([m, n]);
+                    mat.fill_from_seed_equally_distributed(0);
+                    let mut expected = rlst_dynamic_array2!($scalar, [m, n]);
+                    expected.fill_from(mat.view());
+
+                    let mut u = DynamicArray::<$scalar, 2>::from_shape_row_major([m, k]);
+                    let mut vt = DynamicArray::<$scalar, 2>::from_shape_row_major([k, n]);
+                    let mut sigma = rlst_dynamic_array2!($scalar, [k, k]);
+                    let mut singvals = rlst_dynamic_array1!(<$scalar as RlstScalar>::Real, [k]);
+
+                    mat.into_svd_alloc(u.view_mut(), vt.view_mut(), singvals.data_mut(), SvdMode::Reduced)
+                        .unwrap();
+
+                    for index in 0..k {
+                        sigma[[index, index]] = singvals[[index]].into();
+                    }
+
+                    let actual = empty_array::<$scalar, 2>().simple_mult_into_resize(
+                        empty_array::<$scalar, 2>().simple_mult_into_resize(u, sigma),
+                        vt,
+                    );
+
+                    assert_array_relative_eq!(expected, actual, $tol);
+                }
+            }
+        };
+    }
+
+impl_row_major_tests!(f32, 1E-4);
+impl_row_major_tests!(f64, 1E-10);
+impl_row_major_tests!(c32, 1E-4);
+impl_row_major_tests!(c64, 1E-10);
@@ -104,6 +104,169 @@ fn test_cg() {
     assert!(res < tol);
 }
 
+/// Five-point Laplacian on an n x n grid
+fn laplace_2d(n: usize) -> CsrMatrix<f64> {
+    let dim = n * n;
+    let mut rows = Vec::new();
+    let mut cols = Vec::new();
+    let mut data = Vec::new();
+    for i in 0..n {
+        for j in 0..n {
+            rows.push(i * n + j);
+            cols.push(i * n + j);
+            data.push(4.0);
+            if i > 0 {
+                rows.extend([i * n + j, (i - 1) * n + j]);
+                cols.extend([(i - 1) * n + j, i * n + j]);
+                data.extend([-1.0, -1.0]);
+            }
+            if j > 0 {
+                rows.extend([i * n + j, i * n + j - 1]);
+                cols.extend([i * n + j - 1, i * n + j]);
+                data.extend([-1.0, -1.0]);
+            }
+        }
+    }
+    CsrMatrix::from_aij([dim, dim], &rows, &cols, &data).unwrap()
+}
+
+#[test]
+fn test_preconditioned_cg() {
+    let n = 20;
+    let dim = n * n;
+    let tol = 1E-8;
+
+    let mat = laplace_2d(n);
+
+    let space = ArrayVectorSpace::<f64>::new(dim);
+    let op = CsrMatrixOperator::new(&mat, &space, &space);
+
+    let mut rhs = space.zero();
+    rhs.view_mut().fill_from_seed_equally_distributed(0);
+
+    let ic = mat.ic0().unwrap();
+    let ilu = mat.ilu0().unwrap();
+    let ic_op = IncompleteCholeskyOperator::new(&ic, &space);
+    let ilu_op = IncompleteLuOperator::new(&ilu, &space);
+
+    let mut iterations = [0usize; 3];
+
+    let (_sol, res) = CgIteration::new(&op, &rhs)
+        .set_callable(|_, _| iterations[0] += 1)
+        .set_tol(tol)
+        .run();
+    assert!(res < tol);
+
+    let (_sol, res) = CgIteration::new(&op, &rhs)
+        .set_preconditioner(&ic_op)
+        .set_callable(|_, _| iterations[1] += 1)
+        .set_tol(tol)
+        .run();
+    assert!(res < tol);
+
+    let (sol, res) = CgIteration::new(&op, &rhs)
+        .set_preconditioner(&ilu_op)
+        .set_callable(|_, _| iterations[2] += 1)
+        .set_tol(tol)
+        .run();
+    assert!(res < tol);
+
+    let mut residual = op.apply(&sol);
+    residual.axpy_inplace(-1.0, &rhs);
+    assert!(space.norm(&residual) < 1E-6 * space.norm(&rhs));
+
+    assert!(iterations[1] < iterations[0]);
+    // For symmetric matrices ILU(0) is equivalent to IC(0).
+    assert!(iterations[1].abs_diff(iterations[2]) <= 1);
+}
+
+#[test]
+fn test_amg_preconditioned_cg() {
+    let n = 40;
+    let dim = n * n;
+    let tol = 1E-8;
+
+    let mat = laplace_2d(n);
+    let space = ArrayVectorSpace::<f64>::new(dim);
+    let op = CsrMatrixOperator::new(&mat, &space, &space);
+
+    let mut rhs = space.zero();
+    rhs.view_mut().fill_from_seed_equally_distributed(0);
+
+    let ic = mat.ic0().unwrap();
+    let ic_op = IncompleteCholeskyOperator::new(&ic, &space);
+    let amg = AmgBuilder::new(&mat)
+        .set_max_coarse_size(50)
+        .build()
+        .unwrap();
+    let amg_op = AmgOperator::new(&amg, &space);
+
+    let mut iterations = [0usize; 2];
+
+    let (_sol, res) = CgIteration::new(&op, &rhs)
+        .set_preconditioner(&ic_op)
+        .set_callable(|_, _| iterations[0] += 1)
+        .set_tol(tol)
+        .run();
+    assert!(res < tol);
+
+    let (sol, res) = CgIteration::new(&op, &rhs)
+        .set_preconditioner(&amg_op)
+        .set_callable(|_, _| iterations[1] += 1)
+        .set_tol(tol)
+        .run();
+    assert!(res < tol);
+
+    let mut residual = op.apply(&sol);
+    residual.axpy_inplace(-1.0, &rhs);
+    assert!(space.norm(&residual) < 1E-6 * space.norm(&rhs));
+
+    assert!(iterations[1] < iterations[0] / 2);
+}
+
+#[test]
+fn test_smoother_preconditioned_cg() {
+    let n = 20;
+    let dim = n * n;
+    let tol = 1E-8;
+
+    let mat = laplace_2d(n);
+    let space = ArrayVectorSpace::<f64>::new(dim);
+    let op = CsrMatrixOperator::new(&mat, &space, &space);
+
+    let mut rhs = space.zero();
+    rhs.view_mut().fill_from_seed_equally_distributed(0);
+
+    let ssor = StationaryIteration::new(&mat, StationaryMethod::ssor(1.5)).unwrap();
+    let chebyshev = StationaryIteration::chebyshev_smoother(&mat, 4, 10).unwrap();
+    let ssor_op = StationaryIterationOperator::new(&ssor, 1, &space);
+    let chebyshev_op = StationaryIterationOperator::new(&chebyshev, 1, &space);
+
+    let mut iterations = [0usize; 3];
+
+    let (_sol, res) = CgIteration::new(&op, &rhs)
+        .set_callable(|_, _| iterations[0] += 1)
+        .set_tol(tol)
+        .run();
+    assert!(res < tol);
+
+    for (index, precond) in [&ssor_op, &chebyshev_op].into_iter().enumerate() {
+        let (sol, res) = CgIteration::new(&op, &rhs)
+            .set_preconditioner(precond)
+            .set_callable(|_, _| iterations[1 + index] += 1)
+            .set_tol(tol)
+            .run();
+        assert!(res < tol);
+
+        let mut residual = op.apply(&sol);
+        residual.axpy_inplace(-1.0, &rhs);
+        assert!(space.norm(&residual) < 1E-6 * space.norm(&rhs));
+    }
+
+    assert!(iterations[1] < iterations[0] / 2);
+    assert!(iterations[2] < iterations[0] / 2);
+}
+
 #[test]
 fn test_operator_algebra() {
     let mut mat1 = rlst_dynamic_array2!(f64, [4, 3]);
@@ -0,0 +1,142 @@
+//! Tests of parallel array operations
+#![cfg(feature = "rayon")]
+
+use approx::assert_relative_eq;
+use rlst::assert_array_relative_eq;
+use rlst::prelude::*;
+use rlst::threading::{set_num_threads, set_parallel_threshold};
+
+fn setup() {
+    // Force the parallel code path also for small arrays.
+    set_num_threads(4);
+    set_parallel_threshold(0);
+}
+
+#[test]
+fn test_parallel_fill_from() {
+    setup();
+    let shape = [13, 57, 9];
+    let mut arr1 = rlst_dynamic_array3!(f64, shape);
+    let mut arr2 = rlst_dynamic_array3!(f64, shape);
+    let mut actual = rlst_dynamic_array3!(f64, shape);
+    let mut actual_chunked = rlst_dynamic_array3!(f64, shape);
+
+    arr1.fill_from_seed_equally_distributed(0);
+    arr2.fill_from_seed_equally_distributed(1);
+
+    actual.fill_from(3.0 * arr1.view() + arr2.view());
+    actual_chunked.fill_from_chunked::<_, 16>(3.0 * arr1.view() + arr2.view());
+
+    for ((&value, &value_chunked), (elem1, elem2)) in actual
+        .data()
+        .iter()
+        .zip(actual_chunked.data())
+        .zip(arr1.iter().zip(arr2.iter()))
+    {
+        assert_relative_eq!(value, 3.0 * elem1 + elem2, epsilon = 1E-14);
+        assert_relative_eq!(value_chunked, 3.0 * elem1 + elem2, epsilon = 1E-14);
+    }
+}
+
+#[test]
+fn test_parallel_fill_from_into_subview_and_row_major() {
+    setup();
+    let mut arr = rlst_dynamic_array2!(f64, [40, 30]);
+    let mut other = rlst_dynamic_array2!(f64, [20, 10]);
+    other.fill_from_seed_equally_distributed(0);
+
+    arr.view_mut()
+        .into_subview([5, 7], [20, 10])
+        .fill_from(other.view());
+
+    assert_array_relative_eq!(
+        arr.view().into_subview([5, 7], [20, 10]),
+        other.view(),
+        1E-14
+    );
+    assert_relative_eq!(arr.view().sum(), other.iter().sum::<f64>(), epsilon = 1E-12);
+
+    let mut row_major = DynamicArray::<f64, 2>::from_shape_row_major([20, 10]);
+    row_major.fill_from(other.view());
+    assert_array_relative_eq!(row_major, other, 1E-14);
+}
+
+#[test]
+fn test_parallel_sum_and_cmp_mult_into() {
+    setup();
+    let shape = [100, 33];
+    let mut arr1 = rlst_dynamic_array2!(c64, shape);
+    let mut arr2 = rlst_dynamic_array2!(c64, shape);
+    let mut chunked = rlst_dynamic_array2!(c64, shape);
+
+    arr1.fill_from_seed_equally_distributed(0);
+    arr2.fill_from_seed_equally_distributed(1);
+    let initial = arr1.iter().collect::<Vec<_>>();
+    chunked.fill_from(arr1.view());
+
+    arr1.sum_into(arr2.view());
+    chunked.sum_into_chunked::<_, 16>(arr2.view());
+    for (((actual, actual_chunked), elem1), elem2) in arr1
+        .iter()
+        .zip(chunked.iter())
+        .zip(initial.iter())
+        .zip(arr2.iter())
+    {
+        assert_relative_eq!(actual, elem1 + elem2, epsilon = 1E-14);
+        assert_relative_eq!(actual_chunked, elem1 + elem2, epsilon = 1E-14);
+    }
+
+    arr1.cmp_mult_into(arr2.view());
+    chunked.cmp_mult_into_chunked::<_, 16>(arr2.view());
+    for (((actual, actual_chunked), elem1), elem2) in arr1
+        .iter()
+        .zip(chunked.iter())
+        .zip(initial.iter())
+        .zip(arr2.iter())
+    {
+        assert_relative_eq!(actual, (elem1 + elem2) * elem2, epsilon = 1E-14);
+        assert_relative_eq!(actual_chunked, (elem1 + elem2) * elem2, epsilon = 1E-14);
+    }
+}
+
+#[test]
+fn test_parallel_reductions() {
+    setup();
+    let mut vec1 = rlst_dynamic_array1!(c64, [10_000]);
+    let mut vec2 = rlst_dynamic_array1!(c64, [10_000]);
+    vec1.fill_from_seed_equally_distributed(0);
+    vec2.fill_from_seed_equally_distributed(1);
+
+    assert_relative_eq!(vec1.view().sum(), vec1.iter().sum::<c64>(), epsilon = 1E-10);
+    assert_relative_eq!(
+        vec1.view().norm_1(),
+        vec1.iter().map(|elem| elem.abs()).sum::<f64>(),
+        epsilon = 1E-10
+    );
+    assert_relative_eq!(
+        vec1.view().norm_2(),
+        vec1.iter().map(|elem| elem.square()).sum::<f64>().sqrt(),
+        epsilon = 1E-10
+    );
+    assert_relative_eq!(
+        vec1.view().norm_inf(),
+        vec1.iter().map(|elem| elem.abs()).fold(0.0, f64::max),
+        epsilon = 1E-10
+    );
+    assert_relative_eq!(
+        vec1.view().inner(vec2.view()),
+        vec1.iter()
+            .zip(vec2.iter())
+            .map(|(elem1, elem2)| elem1 * elem2.conj())
+            .sum::<c64>(),
+        epsilon = 1E-10
+    );
+
+    let mut mat = rlst_dynamic_array2!(f64, [123, 45]);
+    mat.fill_from_seed_equally_distributed(2);
+    assert_relative_eq!(
+        mat.view().norm_fro(),
+        mat.iter().map(|elem| elem * elem).sum::<f64>().sqrt(),
+        epsilon = 1E-10
+    );
+}
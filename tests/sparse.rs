@@ -95,6 +95,16 @@ fn test_csr_from_aij() {
     assert_eq!(csr.data()[0], 5.0);
 }
 
+#[test]
+fn test_csr_into_parts() {
+    let csr = CsrMatrix::from_aij([3, 3], &[1, 2, 1], &[0, 2, 0], &[2.0, 0.0, 3.0]).unwrap();
+
+    let (indices, indptr, data) = csr.into_parts();
+    assert_eq!(indices, vec![0]);
+    assert_eq!(indptr, vec![0, 0, 1, 1]);
+    assert_eq!(data, vec![5.0]);
+}
+
 #[test]
 fn test_csr_matmul() {
     // Test the matrix [[1, 2], [3, 4]]
@@ -249,3 +259,1763 @@ fn test_csc_umfpack_c64() {
 
     rlst::assert_array_relative_eq!(x_actual, x_exact, 1E-12);
 }
+
+#[cfg(feature = "suitesparse")]
+macro_rules! impl_umfpack_solve_tests {
+    ($scalar:ty, $tol:expr) => {
+        paste::paste! {
+            #[test]
+            fn [<test_csc_umfpack_ $scalar>]() {
+                let n = 5;
+
+                let mut mat = rlst_dynamic_array2!($scalar, [n, n]);
+                let mut x_exact = rlst_dynamic_array1!($scalar, [n]);
+                let mut x_actual = rlst_dynamic_array1!($scalar, [n]);
+
+                mat.fill_from_seed_equally_distributed(0);
+                x_exact.fill_from_seed_equally_distributed(1);
+
+                let rhs =
+                    empty_array::<$scalar, 1>().simple_mult_into_resize(mat.view(), x_exact.view());
+
+                let mut rows = Vec::<usize>::with_capacity(n * n);
+                let mut cols = Vec::<usize>::with_capacity(n * n);
+                let mut data = Vec::<$scalar>::with_capacity(n * n);
+
+                for col_index in 0..n {
+                    for row_index in 0..n {
+                        rows.push(row_index);
+                        cols.push(col_index);
+                        data.push(mat[[row_index, col_index]]);
+                    }
+                }
+
+                let sparse_mat = CscMatrix::from_aij([n, n], &rows, &cols, &data).unwrap();
+
+                sparse_mat
+                    .into_lu()
+                    .unwrap()
+                    .solve(rhs.view(), x_actual.view_mut(), TransMode::NoTrans)
+                    .unwrap();
+
+                rlst::assert_array_relative_eq!(x_actual, x_exact, $tol);
+            }
+        }
+    };
+}
+
+#[cfg(feature = "suitesparse")]
+macro_rules! impl_umfpack_factor_tests {
+    ($scalar:ty, $tol:expr) => {
+        paste::paste! {
+            #[test]
+            fn [<test_csc_umfpack_factors_ $scalar>]() {
+                let n = 6;
+
+                let mut mat = rlst_dynamic_array2!($scalar, [n, n]);
+                mat.fill_from_seed_equally_distributed(2);
+
+                let mut rows = Vec::<usize>::new();
+                let mut cols = Vec::<usize>::new();
+                let mut data = Vec::<$scalar>::new();
+
+                // Keep a sparse pattern with a nonzero diagonal.
+                for col_index in 0..n {
+                    for row_index in 0..n {
+                        if row_index == col_index || (row_index + 2 * col_index) % 3 == 0 {
+                            rows.push(row_index);
+                            cols.push(col_index);
+                            data.push(mat[[row_index, col_index]]);
+                        } else {
+                            mat[[row_index, col_index]] = <$scalar as RlstScalar>::from_real(0.0);
+                        }
+                    }
+                }
+
+                let sparse_mat = CscMatrix::from_aij([n, n], &rows, &cols, &data).unwrap();
+                let factors = sparse_mat.into_lu().unwrap().get_factors().unwrap();
+
+                let to_dense = |csc: &CscMatrix<$scalar>| {
+                    let mut dense = rlst_dynamic_array2!($scalar, csc.shape());
+                    for (row, col, value) in csc.iter_aij() {
+                        dense[[row, col]] = value;
+                    }
+                    dense
+                };
+
+                let l = to_dense(factors.l());
+                let u = to_dense(factors.u());
+                let lu = empty_array::<$scalar, 2>().simple_mult_into_resize(l.view(), u.view());
+
+                for i in 0..n {
+                    assert_eq!(l[[i, i]], <$scalar as RlstScalar>::from_real(1.0));
+                    for j in 0..n {
+                        if j > i {
+                            assert_eq!(l[[i, j]], <$scalar as RlstScalar>::from_real(0.0));
+                        }
+                        if j < i {
+                            assert_eq!(u[[i, j]], <$scalar as RlstScalar>::from_real(0.0));
+                        }
+                    }
+                }
+
+                let mut praq = rlst_dynamic_array2!($scalar, [n, n]);
+                for i in 0..n {
+                    let row = factors.row_permutation()[i];
+                    for j in 0..n {
+                        praq[[i, j]] = mat[[row, factors.col_permutation()[j]]]
+                            .mul_real(factors.row_scaling()[row]);
+                    }
+                }
+
+                rlst::assert_array_abs_diff_eq!(lu, praq, $tol);
+            }
+        }
+    };
+}
+
+#[cfg(feature = "suitesparse")]
+impl_umfpack_solve_tests!(f32, 1E-4);
+#[cfg(feature = "suitesparse")]
+impl_umfpack_solve_tests!(c32, 1E-4);
+
+#[cfg(feature = "suitesparse")]
+impl_umfpack_factor_tests!(f32, 1E-4);
+#[cfg(feature = "suitesparse")]
+impl_umfpack_factor_tests!(f64, 1E-12);
+#[cfg(feature = "suitesparse")]
+impl_umfpack_factor_tests!(c32, 1E-4);
+#[cfg(feature = "suitesparse")]
+impl_umfpack_factor_tests!(c64, 1E-12);
+
+#[cfg(feature = "suitesparse")]
+#[test]
+fn test_csc_umfpack_refactor() {
+    use rlst::sparse::sparse_mat::umfpack::{UmfpackControl, UmfpackOrdering, UmfpackSymbolic};
+
+    let n = 6;
+    let nrhs = 3;
+
+    let mut mat = rlst_dynamic_array2!(f64, [n, n]);
+    let mut x_exact = rlst_dynamic_array2!(f64, [n, nrhs]);
+    let mut x_actual = rlst_dynamic_array2!(f64, [n, nrhs]);
+
+    mat.fill_from_seed_equally_distributed(0);
+    x_exact.fill_from_seed_equally_distributed(1);
+
+    let mut rows = Vec::<usize>::with_capacity(n * n);
+    let mut cols = Vec::<usize>::with_capacity(n * n);
+    let mut data = Vec::<f64>::with_capacity(n * n);
+
+    for col_index in 0..n {
+        for row_index in 0..n {
+            rows.push(row_index);
+            cols.push(col_index);
+            data.push(mat[[row_index, col_index]]);
+        }
+    }
+
+    let sparse_mat = CscMatrix::from_aij([n, n], &rows, &cols, &data).unwrap();
+
+    let mut control = UmfpackControl::new();
+    control.set_pivot_tolerance(0.5);
+    control.set_ordering(UmfpackOrdering::Amd);
+    assert_eq!(control.pivot_tolerance(), 0.5);
+
+    let symbolic = UmfpackSymbolic::new(&sparse_mat, control).unwrap();
+    let mut lu = symbolic.factorize(sparse_mat.data()).unwrap();
+    assert!(lu.rcond() > 0.0);
+    assert!(lu.flops() > 0.0);
+
+    let rhs = empty_array::<f64, 2>().simple_mult_into_resize(mat.view(), x_exact.view());
+    lu.solve_mat(rhs.view(), x_actual.view_mut(), TransMode::NoTrans)
+        .unwrap();
+    rlst::assert_array_relative_eq!(x_actual, x_exact, 1E-12);
+
+    // Refactorise the matrix 2 * A with the same sparsity pattern.
+    let scaled = sparse_mat
+        .data()
+        .iter()
+        .map(|&value| 2.0 * value)
+        .collect::<Vec<_>>();
+    lu.refactor(&scaled).unwrap();
+    lu.solve_mat(rhs.view(), x_actual.view_mut(), TransMode::NoTrans)
+        .unwrap();
+    x_actual.scale_inplace(2.0);
+    rlst::assert_array_relative_eq!(x_actual, x_exact, 1E-12);
+
+    assert!(lu.refactor(&scaled[1..]).is_err());
+}
+
+/// Random triplets of a sparse matrix with approximately `nelems` entries.
+fn random_aij(shape: [usize; 2], nelems: usize, seed: u64) -> (Vec<usize>, Vec<usize>, Vec<f64>) {
+    use rand::{Rng, SeedableRng};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+
+    let rows = (0..nelems).map(|_| rng.gen_range(0..shape[0])).collect();
+    let cols = (0..nelems).map(|_| rng.gen_range(0..shape[1])).collect();
+    let data = (0..nelems).map(|_| rng.gen_range(-1.0..1.0)).collect();
+    (rows, cols, data)
+}
+
+/// Dense matrix from an iterator of triplets.
+fn dense_from_aij(
+    shape: [usize; 2],
+    aij: impl Iterator<Item = (usize, usize, f64)>,
+) -> DynamicArray<f64, 2> {
+    let mut mat = rlst_dynamic_array2!(f64, shape);
+    for (row, col, value) in aij {
+        mat[[row, col]] += value;
+    }
+    mat
+}
+
+#[test]
+fn test_csr_spgemm() {
+    let (rows, cols, data) = random_aij([20, 15], 50, 0);
+    let a = CsrMatrix::from_aij([20, 15], &rows, &cols, &data).unwrap();
+    let (rows, cols, data) = random_aij([15, 12], 40, 1);
+    let b = CsrMatrix::from_aij([15, 12], &rows, &cols, &data).unwrap();
+
+    let mut expected = rlst_dynamic_array2!(f64, [20, 12]);
+    expected.view_mut().simple_mult_into(
+        dense_from_aij(a.shape(), a.iter_aij()).view(),
+        dense_from_aij(b.shape(), b.iter_aij()).view(),
+    );
+
+    let c = a.matmul_sparse(&b);
+    assert_eq!(c.shape(), [20, 12]);
+    rlst::assert_array_relative_eq!(dense_from_aij(c.shape(), c.iter_aij()), expected, 1E-12);
+
+    // Indices within each row are sorted.
+    for row in 0..20 {
+        let row_indices = &c.indices()[c.indptr()[row]..c.indptr()[1 + row]];
+        assert!(row_indices.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    // Reuse the pattern for matrices with the same sparsity but different values.
+    let symbolic = SpGemmSymbolic::new_csr(&a, &b);
+    assert_eq!(symbolic.nelems(), c.nelems());
+
+    let a2 = CsrMatrix::new(
+        a.shape(),
+        a.indices().to_vec(),
+        a.indptr().to_vec(),
+        a.data().iter().map(|&value| 2.0 * value).collect(),
+    );
+    let c2 = symbolic.numeric_csr(&a2, &b).unwrap();
+    for (&value, &value2) in c.data().iter().zip(c2.data()) {
+        approx::assert_relative_eq!(2.0 * value, value2, epsilon = 1E-12);
+    }
+}
+
+#[test]
+fn test_csc_spgemm() {
+    let (rows, cols, data) = random_aij([20, 15], 50, 2);
+    let a = CscMatrix::from_aij([20, 15], &rows, &cols, &data).unwrap();
+    let (rows, cols, data) = random_aij([15, 12], 40, 3);
+    let b = CscMatrix::from_aij([15, 12], &rows, &cols, &data).unwrap();
+
+    let mut expected = rlst_dynamic_array2!(f64, [20, 12]);
+    expected.view_mut().simple_mult_into(
+        dense_from_aij(a.shape(), a.iter_aij()).view(),
+        dense_from_aij(b.shape(), b.iter_aij()).view(),
+    );
+
+    let symbolic = SpGemmSymbolic::new_csc(&a, &b);
+    let c = symbolic.numeric_csc(&a, &b).unwrap();
+    assert_eq!(c.shape(), [20, 12]);
+    assert_eq!(c.nelems(), symbolic.nelems());
+    rlst::assert_array_relative_eq!(dense_from_aij(c.shape(), c.iter_aij()), expected, 1E-12);
+
+    let c = a.matmul_sparse(&b);
+    rlst::assert_array_relative_eq!(dense_from_aij(c.shape(), c.iter_aij()), expected, 1E-12);
+}
+
+#[test]
+fn test_spgemm_symbolic_pattern_mismatch() {
+    let a = CsrMatrix::new([2, 2], vec![0, 1], vec![0, 1, 2], vec![1.0, 2.0]);
+    let b = CsrMatrix::new([2, 2], vec![0, 1], vec![0, 1, 2], vec![3.0, 4.0]);
+    let symbolic = SpGemmSymbolic::new_csr(&a, &b);
+
+    // Same shape and number of entries, but a different sparsity pattern.
+    let other = CsrMatrix::new([2, 2], vec![1, 0], vec![0, 1, 2], vec![1.0, 2.0]);
+    assert!(symbolic.numeric_csr(&a, &b).is_ok());
+    assert!(matches!(
+        symbolic.numeric_csr(&other, &b),
+        Err(RlstError::InvalidSparseMatrix(_))
+    ));
+    assert!(symbolic.numeric_csr(&a, &other).is_err());
+}
+
+/// Return true if indices are strictly increasing in each row or column and no zeros are stored.
+fn is_normalized(indptr: &[usize], indices: &[usize], data: &[f64]) -> bool {
+    indptr.windows(2).all(|range| {
+        indices[range[0]..range[1]]
+            .windows(2)
+            .all(|pair| pair[0] < pair[1])
+    }) && data.iter().all(|&value| value != 0.0)
+}
+
+#[test]
+fn test_csr_arithmetic() {
+    let shape = [12, 9];
+    let (rows, cols, data) = random_aij(shape, 30, 4);
+    let a = CsrMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+    let (rows, cols, data) = random_aij(shape, 30, 5);
+    let b = CsrMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+
+    let a_dense = dense_from_aij(shape, a.iter_aij());
+    let b_dense = dense_from_aij(shape, b.iter_aij());
+
+    let c = a.add(&b, 2.0, -3.0);
+    assert!(is_normalized(c.indptr(), c.indices(), c.data()));
+    let mut expected = rlst::rlst_dynamic_array2!(f64, shape);
+    expected.fill_from(2.0 * a_dense.view() - 3.0 * b_dense.view());
+    rlst::assert_array_relative_eq!(dense_from_aij(shape, c.iter_aij()), expected, 1E-14);
+
+    // Cancellation removes all entries.
+    assert_eq!(a.add(&a, 1.0, -1.0).nelems(), 0);
+
+    let mut scaled = a.clone();
+    scaled.scale_inplace(0.0);
+    assert_eq!(scaled.nelems(), 0);
+    assert_eq!(scaled.indptr().len(), 1 + shape[0]);
+
+    let transpose = a.transpose();
+    assert_eq!(transpose.shape(), [9, 12]);
+    assert!(is_normalized(
+        transpose.indptr(),
+        transpose.indices(),
+        transpose.data()
+    ));
+    rlst::assert_array_relative_eq!(
+        dense_from_aij([9, 12], transpose.iter_aij()),
+        a_dense.view().transpose(),
+        1E-14
+    );
+
+    let row_scale = (0..shape[0]).map(|i| i as f64).collect::<Vec<_>>();
+    let col_scale = (0..shape[1]).map(|j| 1.0 + j as f64).collect::<Vec<_>>();
+    let mut scaled = a.clone();
+    scaled.scale_rows(&row_scale);
+    scaled.scale_cols(&col_scale);
+    assert!(is_normalized(
+        scaled.indptr(),
+        scaled.indices(),
+        scaled.data()
+    ));
+    let scaled_dense = dense_from_aij(shape, scaled.iter_aij());
+    for (i, j) in itertools::iproduct!(0..shape[0], 0..shape[1]) {
+        assert_eq!(
+            scaled_dense[[i, j]],
+            row_scale[i] * a_dense[[i, j]] * col_scale[j]
+        );
+    }
+}
+
+#[test]
+fn test_csc_arithmetic() {
+    let shape = [9, 12];
+    let (rows, cols, data) = random_aij(shape, 30, 6);
+    let a = CscMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+    let (rows, cols, data) = random_aij(shape, 30, 7);
+    let b = CscMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+
+    let a_dense = dense_from_aij(shape, a.iter_aij());
+    let b_dense = dense_from_aij(shape, b.iter_aij());
+
+    let c = a.add(&b, 2.0, -3.0);
+    assert!(is_normalized(c.indptr(), c.indices(), c.data()));
+    let mut expected = rlst::rlst_dynamic_array2!(f64, shape);
+    expected.fill_from(2.0 * a_dense.view() - 3.0 * b_dense.view());
+    rlst::assert_array_relative_eq!(dense_from_aij(shape, c.iter_aij()), expected, 1E-14);
+
+    let transpose = a.transpose();
+    assert_eq!(transpose.shape(), [12, 9]);
+    rlst::assert_array_relative_eq!(
+        dense_from_aij([12, 9], transpose.iter_aij()),
+        a_dense.view().transpose(),
+        1E-14
+    );
+
+    let row_scale = (0..shape[0]).map(|i| i as f64).collect::<Vec<_>>();
+    let mut scaled = a.clone();
+    scaled.scale_rows(&row_scale);
+    scaled.scale_cols(&vec![2.0; shape[1]]);
+    assert!(is_normalized(
+        scaled.indptr(),
+        scaled.indices(),
+        scaled.data()
+    ));
+    let scaled_dense = dense_from_aij(shape, scaled.iter_aij());
+    for (i, j) in itertools::iproduct!(0..shape[0], 0..shape[1]) {
+        assert_eq!(scaled_dense[[i, j]], 2.0 * row_scale[i] * a_dense[[i, j]]);
+    }
+}
+
+#[test]
+fn test_sparse_diagonal() {
+    // The matrix [[1, 2, 0], [0, 0, 3]] has the diagonal [1, 0].
+    let rows = vec![0, 0, 1];
+    let cols = vec![0, 1, 2];
+    let data = vec![1.0, 2.0, 3.0];
+
+    let mut csr = CsrMatrix::from_aij([2, 3], &rows, &cols, &data).unwrap();
+    let mut csc = CscMatrix::from_aij([2, 3], &rows, &cols, &data).unwrap();
+
+    assert_eq!(csr.diagonal(), vec![1.0, 0.0]);
+    assert_eq!(csc.diagonal(), vec![1.0, 0.0]);
+
+    csr.set_diagonal(&[0.0, 5.0]);
+    csc.set_diagonal(&[0.0, 5.0]);
+
+    assert_eq!(csr.diagonal(), vec![0.0, 5.0]);
+    assert_eq!(csc.diagonal(), vec![0.0, 5.0]);
+
+    assert_eq!(csr.indptr(), &[0, 1, 3]);
+    assert_eq!(csr.indices(), &[1, 1, 2]);
+    assert_eq!(csr.data(), &[2.0, 5.0, 3.0]);
+
+    assert_eq!(csc.indptr(), &[0, 0, 2, 3]);
+    assert_eq!(csc.indices(), &[0, 1, 1]);
+    assert_eq!(csc.data(), &[2.0, 5.0, 3.0]);
+}
+
+#[test]
+fn test_sparse_conj_transpose() {
+    let rows = vec![0, 1, 1];
+    let cols = vec![1, 0, 2];
+    let data = vec![c64::new(1.0, 2.0), c64::new(3.0, -1.0), c64::new(0.0, 1.0)];
+
+    for mat in [
+        CsrMatrix::from_aij([2, 3], &rows, &cols, &data)
+            .unwrap()
+            .conj_transpose()
+            .iter_aij()
+            .collect::<Vec<_>>(),
+        CscMatrix::from_aij([2, 3], &rows, &cols, &data)
+            .unwrap()
+            .conj_transpose()
+            .into_csr()
+            .iter_aij()
+            .collect::<Vec<_>>(),
+    ] {
+        assert_eq!(
+            mat,
+            vec![
+                (0, 1, c64::new(3.0, 1.0)),
+                (1, 0, c64::new(1.0, -2.0)),
+                (2, 1, c64::new(0.0, -1.0)),
+            ]
+        );
+    }
+}
+
+#[test]
+fn test_sparse_matmul_mat() {
+    let shape = [12, 9];
+    let nrhs = 3;
+    let (rows, cols, data) = random_aij(shape, 40, 8);
+    let csr = CsrMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+    let csc = CscMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+    let dense = dense_from_aij(shape, csr.iter_aij());
+
+    // Use strided subviews for the right-hand sides and the results.
+    let mut x_big = rlst_dynamic_array2!(f64, [shape[1] + 2, nrhs + 1]);
+    x_big.fill_from_seed_equally_distributed(0);
+    let x = x_big.view().into_subview([1, 1], [shape[1], nrhs]);
+
+    let mut y_init = rlst_dynamic_array2!(f64, [shape[0] + 3, nrhs + 2]);
+    y_init.fill_from_seed_equally_distributed(1);
+
+    let mut expected = rlst_dynamic_array2!(f64, [shape[0], nrhs]);
+    expected.fill_from(y_init.view().into_subview([2, 1], [shape[0], nrhs]));
+    expected.view_mut().mult_into(
+        TransMode::NoTrans,
+        TransMode::NoTrans,
+        2.0,
+        dense.view(),
+        x.view().to_contiguous(),
+        0.5,
+    );
+
+    let mut y_csr = rlst_dynamic_array2!(f64, [shape[0] + 3, nrhs + 2]);
+    y_csr.fill_from(y_init.view());
+    csr.matmul_mat(
+        2.0,
+        &x,
+        0.5,
+        &mut y_csr.view_mut().into_subview([2, 1], [shape[0], nrhs]),
+    );
+    rlst::assert_array_relative_eq!(
+        y_csr.view().into_subview([2, 1], [shape[0], nrhs]),
+        expected,
+        1E-13
+    );
+    // Entries outside of the subview are not touched.
+    assert_eq!(y_csr[[0, 0]], y_init[[0, 0]]);
+
+    let mut y_csc = rlst_dynamic_array2!(f64, [shape[0] + 3, nrhs + 2]);
+    y_csc.fill_from(y_init.view());
+    csc.matmul_mat(
+        2.0,
+        &x,
+        0.5,
+        &mut y_csc.view_mut().into_subview([2, 1], [shape[0], nrhs]),
+    );
+    rlst::assert_array_relative_eq!(
+        y_csc.view().into_subview([2, 1], [shape[0], nrhs]),
+        expected,
+        1E-13
+    );
+}
+
+#[test]
+#[should_panic(expected = "Column index out of bounds.")]
+fn test_csr_matmul_mat_invalid_index() {
+    // The column index 5 is not validated on construction.
+    let csr = CsrMatrix::new([2, 2], vec![0, 5], vec![0, 1, 2], vec![1.0, 1.0]);
+    let x = rlst_dynamic_array2!(f64, [2, 1]);
+    let mut y = rlst_dynamic_array2!(f64, [2, 1]);
+    csr.matmul_mat(1.0, &x, 0.0, &mut y);
+}
+
+#[test]
+#[should_panic(expected = "Row index out of bounds.")]
+fn test_csc_matmul_mat_invalid_index() {
+    let csc = CscMatrix::new([2, 2], vec![0, 5], vec![0, 1, 2], vec![1.0, 1.0]);
+    let x = rlst_dynamic_array2!(f64, [2, 1]);
+    let mut y = rlst_dynamic_array2!(f64, [2, 1]);
+    csc.matmul_mat(1.0, &x, 0.0, &mut y);
+}
+
+#[test]
+fn test_sparse_matmul_trans() {
+    let rows = vec![0, 1, 1, 2];
+    let cols = vec![1, 0, 1, 0];
+    let data = vec![
+        c64::new(1.0, 2.0),
+        c64::new(3.0, -1.0),
+        c64::new(0.0, 1.0),
+        c64::new(2.0, 0.0),
+    ];
+    let csr = CsrMatrix::from_aij([3, 2], &rows, &cols, &data).unwrap();
+    let csc = CscMatrix::from_aij([3, 2], &rows, &cols, &data).unwrap();
+
+    let x = vec![c64::new(1.0, 0.0), c64::new(0.0, 1.0), c64::new(2.0, -1.0)];
+    let y_init = vec![c64::new(1.0, 1.0), c64::new(-1.0, 0.0)];
+    let alpha = c64::new(0.0, 2.0);
+    let beta = c64::new(0.5, 0.0);
+
+    for conj in [false, true] {
+        let mut expected = y_init.iter().map(|&value| beta * value).collect::<Vec<_>>();
+        for (&row, &col, &value) in itertools::izip!(&rows, &cols, &data) {
+            let value = if conj { value.conj() } else { value };
+            expected[col] += alpha * value * x[row];
+        }
+
+        let mut y_csr = y_init.clone();
+        let mut y_csc = y_init.clone();
+        if conj {
+            csr.matmul_conj_trans(alpha, &x, beta, &mut y_csr);
+            csc.matmul_conj_trans(alpha, &x, beta, &mut y_csc);
+        } else {
+            csr.matmul_trans(alpha, &x, beta, &mut y_csr);
+            csc.matmul_trans(alpha, &x, beta, &mut y_csc);
+        }
+
+        for (&actual_csr, &actual_csc, &expected) in itertools::izip!(&y_csr, &y_csc, &expected) {
+            approx::assert_relative_eq!(actual_csr, expected, epsilon = 1E-14);
+            approx::assert_relative_eq!(actual_csc, expected, epsilon = 1E-14);
+        }
+    }
+}
+
+#[test]
+fn test_sparse_builder() {
+    // Assemble a 1D Laplacian from 2x2 element matrices.
+    let n = 6;
+    let mut element = rlst_dynamic_array2!(f64, [2, 2]);
+    element[[0, 0]] = 1.0;
+    element[[0, 1]] = -1.0;
+    element[[1, 0]] = -1.0;
+    element[[1, 1]] = 1.0;
+
+    let mut builder = SparseBuilder::<f64>::new([n, n]);
+    for cell in 0..n - 1 {
+        builder.add_block(&[cell, cell + 1], &[cell, cell + 1], &element);
+    }
+    builder.push(0, n - 1, 0.0);
+    assert!(!builder.has_pattern());
+    assert_eq!(builder.nelems(), 4 * (n - 1) + 1);
+
+    let csr = builder.finish_csr();
+    let csc = builder.finish_csc();
+    assert_eq!(csr.nelems(), 3 * n - 2);
+    assert_eq!(csr.diagonal(), vec![1.0, 2.0, 2.0, 2.0, 2.0, 1.0]);
+    assert_eq!(
+        csr.iter_aij().collect::<Vec<_>>(),
+        csc.into_csr().iter_aij().collect::<Vec<_>>()
+    );
+
+    // Reassemble twice the matrix into the existing pattern.
+    for mut builder in [
+        SparseBuilder::from_csr_pattern(&csr),
+        SparseBuilder::from_csc_pattern(&csr.clone().into_csc()),
+    ] {
+        assert!(builder.has_pattern());
+        for _ in 0..2 {
+            builder.reset();
+            for cell in 0..n - 1 {
+                builder.add_block(&[cell, cell + 1], &[cell, cell + 1], &element);
+                builder.push(cell, cell, 1.0);
+            }
+        }
+        let mat = builder.finish_csr();
+        assert_eq!(mat.indptr(), csr.indptr());
+        assert_eq!(mat.indices(), csr.indices());
+        assert_eq!(mat.diagonal(), vec![2.0, 3.0, 3.0, 3.0, 3.0, 1.0]);
+
+        let mat = builder.finish_csc();
+        assert_eq!(mat.diagonal(), vec![2.0, 3.0, 3.0, 3.0, 3.0, 1.0]);
+    }
+
+    // An empty builder creates an empty matrix.
+    let empty = SparseBuilder::<f64>::new([3, 4]).finish_csr();
+    assert_eq!(empty.nelems(), 0);
+    assert_eq!(empty.indptr(), &[0, 0, 0, 0]);
+}
+
+#[test]
+#[should_panic]
+fn test_sparse_builder_outside_pattern() {
+    let csr = CsrMatrix::from_aij([2, 2], &[0, 1], &[0, 1], &[1.0, 1.0]).unwrap();
+    let mut builder = SparseBuilder::from_csr_pattern(&csr);
+    builder.push(0, 1, 1.0);
+}
+
+#[test]
+fn test_bsr_matrix() {
+    let shape = [12, 9];
+    let (rows, cols, data) = random_aij(shape, 30, 9);
+    let csr = CsrMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+
+    let bsr = BsrMatrix::from_csr(&csr, [3, 3]);
+    assert_eq!(bsr.shape(), shape);
+    assert_eq!(bsr.nelems(), 9 * bsr.nblocks());
+    rlst::assert_array_relative_eq!(
+        dense_from_aij(shape, bsr.iter_aij()),
+        dense_from_aij(shape, csr.iter_aij()),
+        1E-14
+    );
+
+    // Converting back gives the original matrix.
+    let back = bsr.to_csr();
+    assert_eq!(back.indptr(), csr.indptr());
+    assert_eq!(back.indices(), csr.indices());
+    assert_eq!(back.data(), csr.data());
+
+    let x = (0..shape[1]).map(|i| i as f64).collect::<Vec<_>>();
+    let mut expected = vec![1.0; shape[0]];
+    let mut actual = vec![1.0; shape[0]];
+    csr.matmul(2.0, &x, 0.5, &mut expected);
+    bsr.matmul(2.0, &x, 0.5, &mut actual);
+    for (&actual, &expected) in actual.iter().zip(expected.iter()) {
+        approx::assert_relative_eq!(actual, expected, epsilon = 1E-13);
+    }
+
+    // Rectangular blocks.
+    let bsr = csr.to_bsr([4, 1]);
+    assert_eq!(bsr.block(0).shape(), [4, 1]);
+    rlst::assert_array_relative_eq!(
+        dense_from_aij(shape, bsr.to_csr().iter_aij()),
+        dense_from_aij(shape, csr.iter_aij()),
+        1E-14
+    );
+}
+
+#[test]
+fn test_bsr_matrix_operator() {
+    let shape = [6, 6];
+    let (rows, cols, data) = random_aij(shape, 20, 10);
+    let csr = CsrMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+    let bsr = csr.to_bsr([2, 2]);
+
+    let space = ArrayVectorSpace::<f64>::new(6);
+    let csr_op = CsrMatrixOperator::new(&csr, &space, &space);
+    let bsr_op = BsrMatrixOperator::new(&bsr, &space, &space);
+
+    let mut x = space.zero();
+    x.view_mut().fill_from_seed_equally_distributed(0);
+
+    let mut expected = space.zero();
+    let mut actual = space.zero();
+    csr_op.apply_extended(1.0, &x, 0.0, &mut expected).unwrap();
+    bsr_op.apply_extended(1.0, &x, 0.0, &mut actual).unwrap();
+
+    rlst::assert_array_relative_eq!(actual.view(), expected.view(), 1E-13);
+}
+
+#[test]
+fn test_sell_matrix() {
+    // The number of rows is not a multiple of the slice height and some rows are empty.
+    let shape = [37, 21];
+    let (rows, cols, data) = random_aij(shape, 120, 11);
+    let csr = CsrMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+
+    let x = (0..shape[1]).map(|i| 1.0 + i as f64).collect::<Vec<_>>();
+    let mut expected = (0..shape[0]).map(|i| i as f64).collect::<Vec<_>>();
+    csr.matmul(2.0, &x, 0.5, &mut expected);
+
+    for sigma in [1, 8, shape[0]] {
+        let sell = SellMatrix::from_csr(&csr, sigma);
+        assert_eq!(sell.shape(), shape);
+        assert_eq!(sell.nslices(), shape[0].div_ceil(sell.slice_height()));
+        assert!(sell.nelems() >= csr.nelems());
+
+        let mut actual = (0..shape[0]).map(|i| i as f64).collect::<Vec<_>>();
+        sell.matmul(2.0, &x, 0.5, &mut actual);
+        for (&actual, &expected) in actual.iter().zip(expected.iter()) {
+            approx::assert_relative_eq!(actual, expected, epsilon = 1E-13);
+        }
+
+        // Converting back gives the original matrix.
+        let back = sell.to_csr();
+        assert_eq!(back.indptr(), csr.indptr());
+        assert_eq!(back.indices(), csr.indices());
+        assert_eq!(back.data(), csr.data());
+    }
+
+    // Sorting over all rows gives slices of decreasing width.
+    let sell = csr.to_sell(shape[0]);
+    let widths = sell
+        .slice_ptr()
+        .windows(2)
+        .map(|ptr| ptr[1] - ptr[0])
+        .collect::<Vec<_>>();
+    assert!(widths.windows(2).all(|pair| pair[0] >= pair[1]));
+}
+
+#[test]
+fn test_sell_matrix_f32() {
+    let shape = [19, 19];
+    let (rows, cols, data) = random_aij(shape, 80, 12);
+    let data = data.iter().map(|&value| value as f32).collect::<Vec<_>>();
+    let csr = CsrMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+    let sell = csr.to_sell(4);
+
+    let x = (0..shape[1]).map(|i| i as f32).collect::<Vec<_>>();
+    let mut expected = vec![0.0; shape[0]];
+    let mut actual = vec![0.0; shape[0]];
+    csr.matmul(1.0, &x, 0.0, &mut expected);
+    sell.matmul(1.0, &x, 0.0, &mut actual);
+    for (&actual, &expected) in actual.iter().zip(expected.iter()) {
+        approx::assert_relative_eq!(actual, expected, epsilon = 1E-4);
+    }
+}
+
+#[test]
+fn test_sparse_submatrix() {
+    let shape = [10, 8];
+    let (rows, cols, data) = random_aij(shape, 40, 13);
+    let csr = CsrMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+    let csc = CscMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+    let dense = dense_from_aij(shape, csr.iter_aij());
+
+    let sub_rows = [7, 2, 2, 9, 0];
+    let sub_cols = [5, 1, 0, 7];
+    let mut expected = rlst_dynamic_array2!(f64, [sub_rows.len(), sub_cols.len()]);
+    for (i, &row) in sub_rows.iter().enumerate() {
+        for (j, &col) in sub_cols.iter().enumerate() {
+            expected[[i, j]] = dense[[row, col]];
+        }
+    }
+
+    let sub = csr.submatrix(&sub_rows, &sub_cols);
+    assert_eq!(sub.shape(), [5, 4]);
+    assert!(is_normalized(sub.indptr(), sub.indices(), sub.data()));
+    rlst::assert_array_relative_eq!(dense_from_aij(sub.shape(), sub.iter_aij()), expected, 1E-14);
+
+    // For CSC matrices the rows must be distinct.
+    let sub = csc.submatrix(&sub_rows[2..], &sub_cols);
+    assert_eq!(sub.shape(), [3, 4]);
+    assert!(is_normalized(sub.indptr(), sub.indices(), sub.data()));
+    rlst::assert_array_relative_eq!(
+        dense_from_aij(sub.shape(), sub.iter_aij()),
+        expected.view().into_subview([2, 0], [3, 4]),
+        1E-14
+    );
+
+    // Contiguous ranges.
+    let range = csr.row_range(3..8);
+    assert_eq!(range.shape(), [5, shape[1]]);
+    rlst::assert_array_relative_eq!(
+        dense_from_aij(range.shape(), range.iter_aij()),
+        dense.view().into_subview([3, 0], [5, shape[1]]),
+        1E-14
+    );
+    // The column indices and values are borrowed from the matrix.
+    assert_eq!(
+        range.data().as_ptr(),
+        csr.data()[csr.indptr()[3]..].as_ptr()
+    );
+    assert_eq!(range.indptr()[0], 0);
+    let owned = range.into_owned();
+    assert!(owned.validate().is_ok());
+    assert_eq!(owned.shape(), [5, shape[1]]);
+    let range = csc.col_range(2..6);
+    assert_eq!(range.shape(), [shape[0], 4]);
+    rlst::assert_array_relative_eq!(
+        dense_from_aij(range.shape(), range.iter_aij()),
+        dense.view().into_subview([0, 2], [shape[0], 4]),
+        1E-14
+    );
+    assert_eq!(
+        range.indices().as_ptr(),
+        csc.indices()[csc.indptr()[2]..].as_ptr()
+    );
+    assert_eq!(csr.row_range(4..4).nelems(), 0);
+}
+
+#[test]
+fn test_sparse_permute() {
+    let shape = [9, 9];
+    let (rows, cols, data) = random_aij(shape, 30, 14);
+    let csr = CsrMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+    let csc = CscMatrix::from_aij(shape, &rows, &cols, &data).unwrap();
+    let dense = dense_from_aij(shape, csr.iter_aij());
+
+    let permutation = [4, 8, 0, 3, 1, 7, 2, 6, 5];
+    let mut expected = rlst_dynamic_array2!(f64, shape);
+    for (i, &row) in permutation.iter().enumerate() {
+        for (j, &col) in permutation.iter().enumerate() {
+            expected[[i, j]] = dense[[row, col]];
+        }
+    }
+
+    let permuted = csr.permute(&permutation);
+    assert!(is_normalized(
+        permuted.indptr(),
+        permuted.indices(),
+        permuted.data()
+    ));
+    rlst::assert_array_relative_eq!(dense_from_aij(shape, permuted.iter_aij()), expected, 1E-14);
+
+    let permuted = csc.permute(&permutation);
+    assert!(is_normalized(
+        permuted.indptr(),
+        permuted.indices(),
+        permuted.data()
+    ));
+    rlst::assert_array_relative_eq!(dense_from_aij(shape, permuted.iter_aij()), expected, 1E-14);
+}
+
+#[test]
+#[should_panic]
+fn test_sparse_permute_invalid() {
+    let csr = CsrMatrix::<f64>::from_aij([3, 3], &[0, 1], &[1, 2], &[1.0, 2.0]).unwrap();
+    csr.permute(&[0, 2, 2]);
+}
+
+/// Five-point Laplacian on an `n x n` grid with the grid points numbered by `numbering`.
+fn laplace_2d(n: usize, numbering: &[usize]) -> CsrMatrix<f64> {
+    let mut rows = Vec::new();
+    let mut cols = Vec::new();
+    let mut data = Vec::new();
+    for i in 0..n {
+        for j in 0..n {
+            let index = numbering[i * n + j];
+            rows.push(index);
+            cols.push(index);
+            data.push(4.0);
+            let mut neighbours = Vec::new();
+            if i > 0 {
+                neighbours.push((i - 1) * n + j);
+            }
+            if i + 1 < n {
+                neighbours.push((i + 1) * n + j);
+            }
+            if j > 0 {
+                neighbours.push(i * n + j - 1);
+            }
+            if j + 1 < n {
+                neighbours.push(i * n + j + 1);
+            }
+            for other in neighbours {
+                rows.push(index);
+                cols.push(numbering[other]);
+                data.push(-1.0);
+            }
+        }
+    }
+    CsrMatrix::from_aij([n * n, n * n], &rows, &cols, &data).unwrap()
+}
+
+/// Number of entries in the Cholesky factor of a symmetric sparsity pattern.
+fn cholesky_fill(mat: &CsrMatrix<f64>) -> usize {
+    use std::collections::BTreeSet;
+    let n = mat.shape()[0];
+    let mut adjacency = (0..n)
+        .map(|row| {
+            mat.indices()[mat.indptr()[row]..mat.indptr()[1 + row]]
+                .iter()
+                .copied()
+                .filter(|&col| col > row)
+                .collect::<BTreeSet<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut fill = 0;
+    for row in 0..n {
+        let neighbours = std::mem::take(&mut adjacency[row]);
+        fill += 1 + neighbours.len();
+        for &first in &neighbours {
+            adjacency[first].extend(neighbours.iter().copied().filter(|&other| other > first));
+        }
+    }
+    fill
+}
+
+fn is_permutation(permutation: &[usize]) -> bool {
+    let mut sorted = permutation.to_vec();
+    sorted.sort_unstable();
+    sorted
+        .iter()
+        .enumerate()
+        .all(|(index, &value)| index == value)
+}
+
+#[test]
+fn test_sparse_orderings() {
+    use rand::{seq::SliceRandom, SeedableRng};
+    let n = 12;
+    let mut numbering = (0..n * n).collect::<Vec<_>>();
+    numbering.shuffle(&mut rand::rngs::StdRng::seed_from_u64(15));
+    let mat = laplace_2d(n, &numbering);
+    let stats = BandStats::from_csr(&mat);
+
+    let rcm = reverse_cuthill_mckee(&mat);
+    assert!(is_permutation(rcm.permutation()));
+    assert_eq!(rcm.before(), stats);
+    assert_eq!(
+        rcm.after(),
+        BandStats::from_csr(&mat.permute(rcm.permutation()))
+    );
+    assert!(rcm.after().bandwidth() <= 2 * n);
+    assert!(rcm.after().profile() < stats.profile());
+
+    let inverse = rcm.inverse_permutation();
+    for (index, &old) in rcm.permutation().iter().enumerate() {
+        assert_eq!(inverse[old], index);
+    }
+
+    let fill = cholesky_fill(&mat);
+    let amd = approximate_minimum_degree(&mat);
+    assert!(is_permutation(amd.permutation()));
+    assert!(cholesky_fill(&mat.permute(amd.permutation())) < fill / 2);
+
+    let nd = nested_dissection(&mat, 8);
+    assert!(is_permutation(nd.permutation()));
+    assert!(
+        cholesky_fill(&mat.permute(nd.permutation()))
+            < cholesky_fill(&mat.permute(rcm.permutation()))
+    );
+
+    // The natural ordering of the grid has bandwidth n.
+    let natural = laplace_2d(n, &(0..n * n).collect::<Vec<_>>());
+    assert_eq!(BandStats::from_csr(&natural).bandwidth(), n);
+}
+
+#[test]
+fn test_sparse_orderings_disconnected() {
+    // Two paths and an isolated node.
+    let rows = [0, 3, 2, 4, 6, 7];
+    let cols = [2, 1, 3, 6, 5, 7];
+    let data = [1.0; 6];
+    let mat = CsrMatrix::from_aij([8, 8], &rows, &cols, &data).unwrap();
+
+    assert!(is_permutation(reverse_cuthill_mckee(&mat).permutation()));
+    assert!(is_permutation(
+        approximate_minimum_degree(&mat).permutation()
+    ));
+    assert!(is_permutation(nested_dissection(&mat, 1).permutation()));
+    assert_eq!(reverse_cuthill_mckee(&mat).after().bandwidth(), 1);
+}
+
+/// Hermitian positive definite matrix `B^H B + I` for a random sparse `B`.
+fn random_hpd<T: RlstScalar>(n: usize, seed: u64) -> CscMatrix<T> {
+    use rand::{Rng, SeedableRng};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+
+    let nelems = 3 * n;
+    let rows = (0..nelems).map(|_| rng.gen_range(0..n)).collect::<Vec<_>>();
+    let cols = (0..nelems).map(|_| rng.gen_range(0..n)).collect::<Vec<_>>();
+    let data = (0..nelems).map(|_| T::rand(&mut rng)).collect::<Vec<_>>();
+    let b = CscMatrix::from_aij([n, n], &rows, &cols, &data).unwrap();
+
+    let identity = (0..n).collect::<Vec<_>>();
+    let shift = CscMatrix::from_aij([n, n], &identity, &identity, &vec![T::one(); n]).unwrap();
+    b.conj_transpose()
+        .matmul_sparse(&b)
+        .into_csc()
+        .add(&shift, T::one(), T::one())
+}
+
+macro_rules! impl_sparse_cholesky_tests {
+    ($scalar:ty, $tol:expr) => {
+        paste::paste! {
+            #[test]
+            fn [<test_sparse_cholesky_ $scalar>]() {
+                let n = 40;
+                let mat = random_hpd::<$scalar>(n, 16);
+
+                let x_exact = (0..n)
+                    .map(|i| <$scalar as RlstScalar>::from_real(1.0 + i as <$scalar as RlstScalar>::Real))
+                    .collect::<Vec<_>>();
+                let mut rhs = vec![<$scalar as RlstScalar>::from_real(0.0); n];
+                mat.matmul(<$scalar as RlstScalar>::from_real(1.0), &x_exact, <$scalar as RlstScalar>::from_real(0.0), &mut rhs);
+
+                let symbolic = CholeskySymbolic::new(&mat).unwrap();
+                for factorisation in [symbolic.factorize(&mat).unwrap(), symbolic.factorize_ldl(&mat).unwrap()] {
+                    let mut x = vec![<$scalar as RlstScalar>::from_real(0.0); n];
+                    factorisation.solve(&rhs, &mut x);
+                    for (&actual, &expected) in x.iter().zip(x_exact.iter()) {
+                        assert!((actual - expected).abs() < $tol * expected.abs());
+                    }
+                }
+
+                // The symbolic analysis can be reused for a different matrix with the same pattern.
+                let mut scaled = mat.clone();
+                scaled.scale_inplace(<$scalar as RlstScalar>::from_real(2.0));
+                let mut x = vec![<$scalar as RlstScalar>::from_real(0.0); n];
+                symbolic.factorize(&scaled).unwrap().solve(&rhs, &mut x);
+                for (&actual, &expected) in x.iter().zip(x_exact.iter()) {
+                    assert!((actual + actual - expected).abs() < $tol * expected.abs());
+                }
+
+                // Solve through the operator interface.
+                let factorisation = mat.cholesky().unwrap();
+                let space = ArrayVectorSpace::<$scalar>::new(n);
+                let op = SparseCholeskyOperator::new(&factorisation, &space);
+                let mut rhs_vec = space.zero();
+                rhs_vec.view_mut().data_mut().copy_from_slice(&rhs);
+                let mut x = space.zero();
+                op.apply_extended(<$scalar as RlstScalar>::from_real(1.0), &rhs_vec, <$scalar as RlstScalar>::from_real(0.0), &mut x).unwrap();
+                for (&actual, &expected) in x.view().data().iter().zip(x_exact.iter()) {
+                    assert!((actual - expected).abs() < $tol * expected.abs());
+                }
+            }
+        }
+    };
+}
+
+impl_sparse_cholesky_tests!(f32, 1E-3);
+impl_sparse_cholesky_tests!(f64, 1E-10);
+impl_sparse_cholesky_tests!(c32, 1E-3);
+impl_sparse_cholesky_tests!(c64, 1E-10);
+
+#[test]
+fn test_sparse_cholesky_factors() {
+    let n = 25;
+    let mat = random_hpd::<f64>(n, 17);
+    let dense = dense_from_aij([n, n], mat.iter_aij());
+
+    // Without reordering `L L^T` is the matrix itself.
+    let natural = (0..n).collect::<Vec<_>>();
+    let symbolic = CholeskySymbolic::with_permutation(&mat, &natural).unwrap();
+    let l = symbolic.factorize(&mat).unwrap().l_factor();
+    assert_eq!(l.nelems(), symbolic.factor_nelems());
+    let product = l.matmul_sparse(&l.transpose());
+    rlst::assert_array_relative_eq!(dense_from_aij([n, n], product.iter_aij()), dense, 1E-12);
+
+    // The elimination tree parent of each column is its first off-diagonal entry in `L`.
+    for (col, &parent) in symbolic.elimination_tree().iter().enumerate() {
+        let column = &l.indices()[l.indptr()[col]..l.indptr()[1 + col]];
+        assert_eq!(column.get(1).copied().unwrap_or(usize::MAX), parent);
+    }
+
+    // An indefinite matrix has an LDL but no Cholesky factorisation.
+    let identity = (0..n).collect::<Vec<_>>();
+    let shift = CscMatrix::from_aij([n, n], &identity, &identity, &vec![1.0; n]).unwrap();
+    let indefinite = mat.add(&shift, 1.0, -3.5);
+    assert!(indefinite.cholesky().is_err());
+    let ldl = indefinite.ldl().unwrap();
+    assert!(ldl.d_factor().unwrap().iter().any(|&d| d < 0.0));
+
+    let x_exact = (0..n).map(|i| i as f64).collect::<Vec<_>>();
+    let mut rhs = vec![0.0; n];
+    indefinite.matmul(1.0, &x_exact, 0.0, &mut rhs);
+    let mut x = vec![0.0; n];
+    ldl.solve(&rhs, &mut x);
+    for (&actual, &expected) in x.iter().zip(x_exact.iter()) {
+        approx::assert_abs_diff_eq!(actual, expected, epsilon = 1E-8);
+    }
+
+    assert!(matches!(
+        CscMatrix::<f64>::from_aij([2, 3], &[0], &[0], &[1.0])
+            .unwrap()
+            .cholesky(),
+        Err(RlstError::MatrixNotSquare(2, 3))
+    ));
+}
+
+/// Tridiagonal matrix with diagonal 4 and random off-diagonal entries. If `hermitian` is
+/// true the superdiagonal is the conjugate of the subdiagonal.
+fn random_tridiagonal<T: RlstScalar>(n: usize, hermitian: bool, seed: u64) -> CsrMatrix<T> {
+    use rand::SeedableRng;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+
+    let mut rows = Vec::new();
+    let mut cols = Vec::new();
+    let mut data = Vec::new();
+    for i in 0..n {
+        rows.push(i);
+        cols.push(i);
+        data.push(T::from_real(T::real(4.0)));
+        if i + 1 < n {
+            let lower = T::rand(&mut rng);
+            let upper = if hermitian {
+                lower.conj()
+            } else {
+                T::rand(&mut rng)
+            };
+            rows.extend([i + 1, i]);
+            cols.extend([i, i + 1]);
+            data.extend([lower, upper]);
+        }
+    }
+    CsrMatrix::from_aij([n, n], &rows, &cols, &data).unwrap()
+}
+
+macro_rules! impl_incomplete_factorisation_tests {
+    ($scalar:ty, $tol:expr) => {
+        paste::paste! {
+            #[test]
+            fn [<test_incomplete_factorisations_ $scalar>]() {
+                let n = 30;
+                let x_exact = (0..n)
+                    .map(|i| <$scalar as RlstScalar>::from_real(1.0 + i as <$scalar as RlstScalar>::Real))
+                    .collect::<Vec<_>>();
+
+                // There is no fill-in for tridiagonal matrices, so ILU(0) and IC(0) are exact.
+                let general = random_tridiagonal::<$scalar>(n, false, 1);
+                let hermitian = random_tridiagonal::<$scalar>(n, true, 2);
+
+                let ilu = general.ilu0().unwrap();
+                let ic = hermitian.ic0().unwrap();
+                assert_eq!(ilu.nelems(), general.nelems());
+                assert_eq!(ic.nelems(), (general.nelems() + n) / 2);
+
+                let check = |mat: &CsrMatrix<$scalar>, solve: &dyn Fn(&[$scalar], &mut [$scalar])| {
+                    let mut rhs = vec![<$scalar as RlstScalar>::from_real(0.0); n];
+                    mat.matmul(<$scalar as RlstScalar>::from_real(1.0), &x_exact, <$scalar as RlstScalar>::from_real(0.0), &mut rhs);
+                    let mut x = vec![<$scalar as RlstScalar>::from_real(0.0); n];
+                    solve(&rhs, &mut x);
+                    for (actual, expected) in x.iter().zip(x_exact.iter()) {
+                        assert!((*actual - *expected).abs() < $tol * expected.abs());
+                    }
+                };
+                check(&general, &|rhs, x| ilu.solve(rhs, x));
+                check(&hermitian, &|rhs, x| ic.solve(rhs, x));
+
+                // The factors of IC(0) reproduce the matrix.
+                let l = ic.l_factor();
+                let product = l.matmul_sparse(&l.conj_transpose());
+                let mut expected = vec![<$scalar as RlstScalar>::from_real(0.0); n];
+                let mut actual = vec![<$scalar as RlstScalar>::from_real(0.0); n];
+                hermitian.matmul(<$scalar as RlstScalar>::from_real(1.0), &x_exact, <$scalar as RlstScalar>::from_real(0.0), &mut expected);
+                product.matmul(<$scalar as RlstScalar>::from_real(1.0), &x_exact, <$scalar as RlstScalar>::from_real(0.0), &mut actual);
+                for (actual, expected) in actual.iter().zip(expected.iter()) {
+                    assert!((*actual - *expected).abs() < $tol * expected.abs());
+                }
+            }
+        }
+    };
+}
+
+impl_incomplete_factorisation_tests!(f64, 1E-10);
+impl_incomplete_factorisation_tests!(c64, 1E-10);
+
+#[test]
+fn test_ilut() {
+    let n = 10;
+    let numbering = (0..n * n).collect::<Vec<_>>();
+    let mat = laplace_2d(n, &numbering);
+
+    let x_exact = (0..n * n).map(|i| 1.0 + i as f64).collect::<Vec<_>>();
+    let mut rhs = vec![0.0; n * n];
+    mat.matmul(1.0, &x_exact, 0.0, &mut rhs);
+
+    // Without dropping ILUT is the exact LU decomposition.
+    let lu = mat.ilut(0.0, n * n).unwrap();
+    let mut x = vec![0.0; n * n];
+    lu.solve(&rhs, &mut x);
+    for (actual, expected) in x.iter().zip(x_exact.iter()) {
+        approx::assert_relative_eq!(actual, expected, epsilon = 1E-10);
+    }
+
+    // With dropping the factors are sparser than the exact factors, but denser than ILU(0).
+    let ilu0 = mat.ilu0().unwrap();
+    let ilut = mat.ilut(1E-3, 10).unwrap();
+    assert!(ilut.nelems() < lu.nelems());
+    assert!(ilut.nelems() > ilu0.nelems());
+
+    // The rows of the factors have at most 10 entries besides the diagonal.
+    let l = ilut.l_factor();
+    let u = ilut.u_factor();
+    for row in 0..n * n {
+        assert!(l.indptr()[1 + row] - l.indptr()[row] <= 11);
+        assert!(u.indptr()[1 + row] - u.indptr()[row] <= 11);
+        assert_eq!(l.indices()[l.indptr()[1 + row] - 1], row);
+        assert_eq!(u.indices()[u.indptr()[row]], row);
+    }
+}
+
+#[test]
+fn test_incomplete_factorisation_breakdown() {
+    // Zero diagonal entry
+    let mat = CsrMatrix::from_aij([2, 2], &[0, 1, 1], &[1, 0, 1], &[1.0, 1.0, 1.0]).unwrap();
+    assert!(matches!(
+        mat.ilu0(),
+        Err(RlstError::FactorisationBreakdown { pivot: 0, .. })
+    ));
+
+    // Indefinite matrix
+    let mat =
+        CsrMatrix::from_aij([2, 2], &[0, 0, 1, 1], &[0, 1, 0, 1], &[1.0, 2.0, 2.0, 1.0]).unwrap();
+    assert!(matches!(
+        mat.ic0(),
+        Err(RlstError::FactorisationBreakdown { pivot: 1, .. })
+    ));
+    assert!(mat.ilu0().is_ok());
+}
+
+#[test]
+fn test_amg() {
+    let n = 32;
+    let numbering = (0..n * n).collect::<Vec<_>>();
+    let mat = laplace_2d(n, &numbering);
+
+    let x_exact = (0..n * n)
+        .map(|i| (i as f64 * 0.1).sin())
+        .collect::<Vec<_>>();
+    let mut rhs = vec![0.0; n * n];
+    mat.matmul(1.0, &x_exact, 0.0, &mut rhs);
+
+    for smoother in [AmgSmoother::GaussSeidel, AmgSmoother::Jacobi(2.0 / 3.0)] {
+        let amg = AmgBuilder::new(&mat)
+            .set_max_coarse_size(30)
+            .set_smoother(smoother)
+            .set_sweeps(2, 2)
+            .build()
+            .unwrap();
+
+        let dims = amg.level_dims();
+        assert_eq!(dims.len(), amg.nlevels());
+        assert_eq!(dims[0], n * n);
+        assert!(amg.nlevels() >= 3);
+        assert!(*dims.last().unwrap() <= 30);
+        assert!(dims.windows(2).all(|dims| dims[1] < dims[0] / 3));
+        assert!(amg.grid_complexity() > 1.0 && amg.grid_complexity() < 1.5);
+        assert!(amg.operator_complexity() > 1.0 && amg.operator_complexity() < 2.0);
+
+        let mut x = vec![0.0; n * n];
+        let res = amg.solve(&rhs, &mut x, 1E-10, 40).unwrap();
+        assert!(res < 1E-10);
+        for (actual, expected) in x.iter().zip(x_exact.iter()) {
+            approx::assert_abs_diff_eq!(actual, expected, epsilon = 1E-6);
+        }
+    }
+
+    // A small matrix is solved directly on a single level.
+    let small = laplace_2d(4, &(0..16).collect::<Vec<_>>());
+    let amg = small.amg().unwrap();
+    assert_eq!(amg.nlevels(), 1);
+    let mut rhs = vec![1.0; 16];
+    let mut x = vec![0.0; 16];
+    amg.vcycle(&rhs, &mut x).unwrap();
+    small.matmul(-1.0, &x, 1.0, &mut rhs);
+    assert!(rhs.iter().all(|r| r.abs() < 1E-12));
+}
+
+#[test]
+fn test_stationary_methods() {
+    let n = 10;
+    let numbering = (0..n * n).collect::<Vec<_>>();
+    let mat = laplace_2d(n, &numbering);
+    let tol = 1E-8;
+
+    let x_exact = (0..n * n)
+        .map(|i| (i as f64 * 0.1).sin())
+        .collect::<Vec<_>>();
+    let mut rhs = vec![0.0; n * n];
+    mat.matmul(1.0, &x_exact, 0.0, &mut rhs);
+
+    // The eigenvalues of D^{-1} A lie in [1 - cos(π / (n + 1)), 1 + cos(π / (n + 1))].
+    let (lower, upper) = jacobi_eigenvalue_bounds(&mat, n * n).unwrap();
+    let c = (std::f64::consts::PI / (n + 1) as f64).cos();
+    approx::assert_relative_eq!(lower, 1.0 - c, epsilon = 1E-8);
+    approx::assert_relative_eq!(upper, 1.0 + c, epsilon = 1E-8);
+
+    let sor_weight = 2.0 / (1.0 + (1.0 - c * c).sqrt());
+    let methods = [
+        StationaryMethod::jacobi(),
+        StationaryMethod::weighted_jacobi(0.8),
+        StationaryMethod::gauss_seidel(SweepDirection::Forward),
+        StationaryMethod::gauss_seidel(SweepDirection::Backward),
+        StationaryMethod::gauss_seidel(SweepDirection::Symmetric),
+        StationaryMethod::sor(sor_weight),
+        StationaryMethod::ssor(1.5),
+        StationaryMethod::chebyshev(4, lower, upper),
+    ];
+
+    let mut iterations = Vec::new();
+    for method in methods {
+        let iteration = StationaryIteration::new(&mat, method).unwrap();
+        let mut x = vec![0.0; n * n];
+        let (iters, res) = iteration.solve(&rhs, &mut x, tol, 2000);
+        assert!(res <= tol, "{method:?} did not converge");
+        for (actual, expected) in x.iter().zip(x_exact.iter()) {
+            approx::assert_abs_diff_eq!(actual, expected, epsilon = 1E-6);
+        }
+        iterations.push(iters);
+    }
+
+    // Gauss-Seidel needs about half as many iterations as Jacobi and SOR with the optimal
+    // weight is much faster than both. Chebyshev needs fewer matrix-vector products than
+    // Jacobi.
+    assert!(iterations[2] < iterations[0] * 2 / 3);
+    assert!(iterations[5] < iterations[2] / 4);
+    assert!(iterations[7] * 4 < iterations[0] / 2);
+
+    // The Chebyshev smoother targets the upper part of the spectrum and damps oscillating
+    // errors.
+    let smoother = StationaryIteration::chebyshev_smoother(&mat, 3, 10).unwrap();
+    match smoother.method() {
+        StationaryMethod::Chebyshev { lower, upper, .. } => {
+            assert!(upper > 1.0 + c && upper < 1.2 * (1.0 + c));
+            approx::assert_relative_eq!(lower, upper / 30.0);
+        }
+        _ => panic!("Expected a Chebyshev smoother."),
+    }
+    let mut error = (0..n * n)
+        .map(|i| if (i / n + i % n) % 2 == 0 { 1.0 } else { -1.0 })
+        .collect::<Vec<_>>();
+    smoother.smooth(&vec![0.0; n * n], &mut error, 1);
+    assert!(error.iter().map(|e| e * e).sum::<f64>().sqrt() < 0.3 * n as f64);
+
+    // A zero diagonal entry is rejected.
+    let mat = CsrMatrix::from_aij([2, 2], &[0, 1, 1], &[1, 0, 1], &[1.0, 1.0, 1.0]).unwrap();
+    assert!(matches!(
+        StationaryIteration::new(&mat, StationaryMethod::jacobi()),
+        Err(RlstError::FactorisationBreakdown { pivot: 0, .. })
+    ));
+}
+
+#[test]
+fn test_sparse_validate() {
+    // The matrix [[1, 2, 0], [0, 0, 0], [0, 3, 4]]
+    let csr = CsrMatrix::new(
+        [3, 3],
+        vec![0, 1, 1, 2],
+        vec![0, 2, 2, 4],
+        vec![1.0, 2.0, 3.0, 4.0],
+    );
+    assert!(csr.validate().is_ok());
+    assert!(csr.clone().into_csc().validate().is_ok());
+
+    let check = |result: RlstResult<()>, message: &str| match result {
+        Err(RlstError::InvalidSparseMatrix(actual)) => assert_eq!(actual, message),
+        _ => panic!("Expected an invalid sparse matrix error."),
+    };
+
+    let invalid = |indices: Vec<usize>, indptr: Vec<usize>, ndata: usize| {
+        CsrMatrix::new([3, 3], indices, indptr, vec![1.0; ndata]).validate()
+    };
+    check(
+        invalid(vec![0, 1, 1, 2], vec![0, 2, 4], 4),
+        "indptr has length 3 but the matrix has 3 rows.",
+    );
+    check(
+        invalid(vec![0, 1, 1, 2], vec![1, 2, 2, 4], 4),
+        "indptr starts at 1 instead of 0.",
+    );
+    check(
+        invalid(vec![0, 1, 1, 2], vec![0, 3, 2, 4], 4),
+        "indptr is not monotone. indptr[1] = 3 > indptr[2] = 2.",
+    );
+    check(
+        invalid(vec![0, 1, 1, 2], vec![0, 2, 2, 3], 4),
+        "indptr ends at 3 but there are 4 indices.",
+    );
+    check(
+        invalid(vec![0, 1, 1, 2], vec![0, 2, 2, 4], 3),
+        "There are 4 indices but 3 data entries.",
+    );
+    check(
+        invalid(vec![0, 1, 1, 3], vec![0, 2, 2, 4], 4),
+        "Column index 3 in row 2 is out of bounds for 3 columns.",
+    );
+    check(
+        invalid(vec![1, 0, 1, 2], vec![0, 2, 2, 4], 4),
+        "Column indices in row 0 are not sorted. 0 follows 1.",
+    );
+    check(
+        invalid(vec![0, 1, 2, 2], vec![0, 2, 2, 4], 4),
+        "Duplicate entry (2, 2).",
+    );
+
+    // CSC matrices report rows and columns the other way round.
+    let csc = CscMatrix::new([3, 2], vec![0, 2, 2], vec![0, 1, 3], vec![1.0; 3]);
+    check(csc.validate(), "Duplicate entry (2, 1).");
+    let csc = CscMatrix::new([2, 3], vec![0, 2], vec![0, 1, 2, 2], vec![1.0; 2]);
+    check(
+        csc.validate(),
+        "Row index 2 in column 1 is out of bounds for 2 rows.",
+    );
+}
+
+#[test]
+fn test_sparse_stats() {
+    // The matrix [[4, -1, 0, 0], [-1, 4, 0, 2], [0, 0, 0, 0], [0, 0, 1, 0.5]]
+    let rows = vec![0, 0, 1, 1, 1, 3, 3];
+    let cols = vec![0, 1, 0, 1, 3, 2, 3];
+    let data = vec![4.0, -1.0, -1.0, 4.0, 2.0, 1.0, 0.5];
+    let csr = CsrMatrix::from_aij([4, 4], &rows, &cols, &data).unwrap();
+    let csc = CscMatrix::from_aij([4, 4], &rows, &cols, &data).unwrap();
+
+    let expected = SparseStats {
+        shape: [4, 4],
+        nnz: 7,
+        min_row_nnz: 0,
+        max_row_nnz: 3,
+        mean_row_nnz: 1.75,
+        empty_rows: 1,
+        lower_bandwidth: 1,
+        upper_bandwidth: 2,
+        structurally_symmetric: false,
+        diagonally_dominant: false,
+        strictly_diagonally_dominant: false,
+    };
+    assert_eq!(csr.stats(), expected);
+    assert_eq!(csc.stats(), expected);
+
+    let mat = laplace_2d(5, &(0..25).collect::<Vec<_>>());
+    let stats = mat.stats();
+    assert_eq!(stats.min_row_nnz, 3);
+    assert_eq!(stats.max_row_nnz, 5);
+    assert_eq!(stats.empty_rows, 0);
+    assert_eq!(stats.lower_bandwidth, 5);
+    assert_eq!(stats.upper_bandwidth, 5);
+    assert!(stats.structurally_symmetric);
+    assert!(stats.diagonally_dominant);
+    assert!(!stats.strictly_diagonally_dominant);
+
+    let stats = random_tridiagonal::<f64>(10, false, 0).stats();
+    assert!(stats.structurally_symmetric);
+    assert!(stats.strictly_diagonally_dominant);
+
+    let rectangular = CsrMatrix::from_aij([2, 3], &[0, 1], &[0, 1], &[1.0, 1.0]).unwrap();
+    let stats = rectangular.stats();
+    assert!(!stats.structurally_symmetric);
+    assert!(!stats.diagonally_dominant);
+}
+
+#[test]
+fn test_sparse_symmetry() {
+    let n = 10;
+    let symmetric = laplace_2d(4, &(0..16).collect::<Vec<_>>());
+    assert!(symmetric.is_symmetric(0.0));
+    assert!(symmetric.is_hermitian(0.0));
+    assert!(symmetric.clone().into_csc().is_symmetric(0.0));
+
+    let general = random_tridiagonal::<f64>(n, false, 0);
+    assert!(!general.is_symmetric(0.1));
+    assert!(!general.clone().into_csc().is_symmetric(0.1));
+
+    // Hermitian but not symmetric complex matrix
+    let hermitian = random_tridiagonal::<c64>(n, true, 0);
+    assert!(hermitian.is_hermitian(0.0));
+    assert!(!hermitian.is_symmetric(0.1));
+    assert!(hermitian.clone().into_csc().is_hermitian(0.0));
+    let symmetric = CsrMatrix::from_aij(
+        [2, 2],
+        &[0, 0, 1],
+        &[0, 1, 0],
+        &[c64::new(1.0, 0.0), c64::new(0.0, 1.0), c64::new(0.0, 1.0)],
+    )
+    .unwrap();
+    assert!(symmetric.is_symmetric(0.0));
+    assert!(!symmetric.is_hermitian(0.1));
+
+    // Small perturbations are accepted with a tolerance.
+    let perturbed = CsrMatrix::from_aij(
+        [2, 2],
+        &[0, 0, 1, 1],
+        &[0, 1, 0, 1],
+        &[2.0, 1.0, 1.0 + 1E-10, 2.0],
+    )
+    .unwrap();
+    assert!(!perturbed.is_symmetric(0.0));
+    assert!(perturbed.is_symmetric(1E-8));
+
+    // A missing transposed entry is only accepted with a tolerance of at least one.
+    let triangular = CsrMatrix::from_aij([2, 2], &[0, 1, 1], &[0, 0, 1], &[1.0, 1.0, 1.0]).unwrap();
+    assert!(!triangular.is_symmetric(0.5));
+    assert!(!CsrMatrix::from_aij([2, 3], &[0], &[0], &[1.0])
+        .unwrap()
+        .is_symmetric(1.0));
+}
+
+macro_rules! impl_dense_conversion_tests {
+    ($scalar:ty) => {
+        paste::paste! {
+            #[test]
+            fn [<test_dense_conversion_ $scalar>]() {
+                let shape = [5, 7];
+                let mut dense = rlst_dynamic_array2!($scalar, shape);
+                dense.fill_from_seed_equally_distributed(0);
+                // Zero out a checkerboard and scale down every third remaining entry.
+                for row in 0..shape[0] {
+                    for col in 0..shape[1] {
+                        if (row + col) % 2 == 0 {
+                            dense[[row, col]] = <$scalar as RlstScalar>::from_real(0.0);
+                        } else if (row * shape[1] + col) % 3 == 0 {
+                            dense[[row, col]] *= <$scalar as RlstScalar>::from_real(1E-10);
+                        }
+                    }
+                }
+                let nonzeros = (0..shape[0])
+                    .flat_map(|row| (0..shape[1]).map(move |col| (row, col)))
+                    .filter(|&(row, col)| (row + col) % 2 == 1)
+                    .collect::<Vec<_>>();
+                let large = nonzeros
+                    .iter()
+                    .filter(|&&(row, col)| (row * shape[1] + col) % 3 != 0)
+                    .count();
+
+                let csr = CsrMatrix::from_dense(&dense, 0.0);
+                let csc = CscMatrix::from_dense(&dense, 0.0);
+                assert_eq!(csr.nelems(), nonzeros.len());
+                assert_eq!(csc.nelems(), nonzeros.len());
+                assert!(csr.validate().is_ok());
+                assert!(csc.validate().is_ok());
+
+                for sparse in [csr.to_dense(), csc.to_dense()] {
+                    assert_eq!(sparse.shape(), shape);
+                    for row in 0..shape[0] {
+                        for col in 0..shape[1] {
+                            assert_eq!(sparse[[row, col]], dense[[row, col]]);
+                        }
+                    }
+                }
+
+                // The sparse and dense matrix-vector products agree.
+                let x = (0..shape[1])
+                    .map(|i| <$scalar as RlstScalar>::from_real(1.0 + i as <$scalar as RlstScalar>::Real))
+                    .collect::<Vec<_>>();
+                let mut expected = vec![<$scalar as RlstScalar>::from_real(0.0); shape[0]];
+                for (row, value) in expected.iter_mut().enumerate() {
+                    for (col, &x_elem) in x.iter().enumerate() {
+                        *value += dense[[row, col]] * x_elem;
+                    }
+                }
+                let mut actual = vec![<$scalar as RlstScalar>::from_real(0.0); shape[0]];
+                csr.matmul(<$scalar as RlstScalar>::from_real(1.0), &x, <$scalar as RlstScalar>::from_real(0.0), &mut actual);
+                for (actual, expected) in actual.iter().zip(expected.iter()) {
+                    assert!((*actual - *expected).abs() < 1E-12);
+                }
+
+                // Small entries are dropped.
+                let csr = CsrMatrix::from_dense(&dense, 1E-5);
+                let csc = CscMatrix::from_dense(&dense, 1E-5);
+                assert_eq!(csr.nelems(), large);
+                assert_eq!(csc.nelems(), large);
+                assert!(csr.to_dense().data().iter().all(|elem| *elem == <$scalar as RlstScalar>::from_real(0.0) || elem.abs() > 1E-5));
+            }
+        }
+    };
+}
+
+impl_dense_conversion_tests!(f64);
+impl_dense_conversion_tests!(c64);
+
+#[test]
+fn test_dense_conversion_duplicates() {
+    // Duplicate entries are summed when converting to a dense array.
+    let csr = CsrMatrix::new([2, 2], vec![1, 1], vec![0, 2, 2], vec![1.0, 2.0]);
+    let dense = csr.to_dense();
+    assert_eq!(dense[[0, 1]], 3.0);
+    assert_eq!(dense[[0, 0]], 0.0);
+    assert_eq!(dense[[1, 1]], 0.0);
+
+    // Empty matrices
+    let empty = CscMatrix::<f64>::from_dense(&rlst_dynamic_array2!(f64, [0, 3]), 0.0);
+    assert_eq!(empty.shape(), [0, 3]);
+    assert_eq!(empty.indptr(), &[0, 0, 0, 0]);
+    assert_eq!(empty.to_dense().shape(), [0, 3]);
+}
+
+#[test]
+fn test_from_dense_keeps_nan() {
+    let mut dense = rlst_dynamic_array2!(f64, [2, 2]);
+    dense[[0, 1]] = f64::NAN;
+    dense[[1, 0]] = 1E-8;
+
+    let csr = CsrMatrix::from_dense(&dense, 1E-5);
+    let csc = CscMatrix::from_dense(&dense, 1E-5);
+    assert_eq!(csr.indices(), &[1]);
+    assert_eq!(csr.indptr(), &[0, 1, 1]);
+    assert!(csr.data()[0].is_nan());
+    assert_eq!(csc.indices(), &[0]);
+    assert_eq!(csc.indptr(), &[0, 0, 1]);
+    assert!(csc.data()[0].is_nan());
+}
+
+#[test]
+#[should_panic]
+fn test_to_dense_invalid_index() {
+    let csr = CsrMatrix::new([2, 2], vec![3], vec![0, 1, 1], vec![1.0]);
+    csr.to_dense();
+}
+
+#[test]
+fn test_sparse_index_types() {
+    let mat = laplace_2d(6, &(0..36).collect::<Vec<_>>());
+    let x = (0..36).map(|i| (i as f64).cos()).collect::<Vec<_>>();
+    let mut expected = vec![0.0; 36];
+    mat.matmul(1.0, &x, 0.0, &mut expected);
+
+    let check = |actual: &[f64]| {
+        for (actual, expected) in actual.iter().zip(expected.iter()) {
+            approx::assert_relative_eq!(actual, expected, epsilon = 1E-14);
+        }
+    };
+
+    // Owned matrices with 32-bit indices
+    let csr = mat.to_index_type::<u32>().unwrap();
+    let csc = mat.clone().into_csc().to_index_type::<i32>().unwrap();
+    assert_eq!(std::mem::size_of_val(csr.indices()), 4 * mat.nelems());
+    assert!(csr.validate().is_ok());
+    assert!(csc.validate().is_ok());
+    assert_eq!(csr.stats(), mat.stats());
+    assert!(csr.is_symmetric(0.0));
+    assert!(csc.is_hermitian(0.0));
+    assert_eq!(csr.diagonal(), mat.diagonal());
+    assert_eq!(
+        csr.iter_aij().collect::<Vec<_>>(),
+        mat.iter_aij().collect::<Vec<_>>()
+    );
+
+    let mut actual = vec![0.0; 36];
+    csr.matmul(1.0, &x, 0.0, &mut actual);
+    check(&actual);
+    csc.matmul(1.0, &x, 0.0, &mut actual);
+    check(&actual);
+    csc.matmul_trans(1.0, &x, 0.0, &mut actual);
+    check(&actual);
+
+    // Views of external arrays
+    let indptr = csr.indptr().to_vec();
+    let indices = csr.indices().to_vec();
+    let data = csr.data().to_vec();
+    let view = CsrMatrixView::new([36, 36], &indices, &indptr, &data);
+    assert!(view.validate().is_ok());
+    view.matmul(1.0, &x, 0.0, &mut actual);
+    check(&actual);
+    view.matmul_trans(1.0, &x, 0.0, &mut actual);
+    check(&actual);
+    let owned = view.into_owned();
+    assert_eq!(owned.indices(), csr.indices());
+    assert_eq!(owned.to_dense().data(), mat.to_dense().data());
+
+    let view = csc.view();
+    assert_eq!(view.shape(), [36, 36]);
+    assert_eq!(view.to_dense().data(), mat.to_dense().data());
+    assert_eq!(view.iter_aij().count(), mat.nelems());
+
+    // Negative indices are out of bounds.
+    let view = CscMatrixView::new([2, 2], &[0, -1], &[0, 1, 2], &[1.0, 1.0]);
+    assert!(matches!(
+        view.validate(),
+        Err(RlstError::InvalidSparseMatrix(message)) if message == "Row index -1 in column 1 is out of bounds for 2 rows."
+    ));
+
+    // Indices that do not fit into the target type are rejected.
+    let large = CsrMatrix::new([1, 1 << 33], vec![1usize << 32], vec![0, 1], vec![1.0]);
+    assert!(large.to_index_type::<u32>().is_err());
+    assert!(large.to_index_type::<i64>().is_ok());
+}
+
+#[test]
+fn test_sparse_index_type_operations() {
+    let mat = laplace_2d(5, &(0..25).collect::<Vec<_>>());
+    let other = mat.transpose().matmul_sparse(&mat);
+    let csr = mat.to_index_type::<u32>().unwrap();
+    let csr_other = other.to_index_type::<u32>().unwrap();
+
+    let same = |actual: &CsrMatrix<f64, u32>, expected: &CsrMatrix<f64>| {
+        assert!(actual.validate().is_ok());
+        assert_eq!(actual.shape(), expected.shape());
+        assert_eq!(
+            actual.iter_aij().collect::<Vec<_>>(),
+            expected.iter_aij().collect::<Vec<_>>()
+        );
+    };
+
+    same(&csr.add(&csr_other, 2.0, -1.0), &mat.add(&other, 2.0, -1.0));
+    same(&csr.transpose(), &mat.transpose());
+    same(&csr.matmul_sparse(&csr_other), &mat.matmul_sparse(&other));
+    same(&csr.to_bsr([5, 5]).to_csr().to_index_type().unwrap(), &mat);
+
+    let rows = [3, 1, 1, 24];
+    let cols = [0, 2, 4, 1, 3];
+    same(&csr.submatrix(&rows, &cols), &mat.submatrix(&rows, &cols));
+    let permutation = (0..25).rev().collect::<Vec<_>>();
+    same(&csr.permute(&permutation), &mat.permute(&permutation));
+
+    let mut diag = csr.clone();
+    let mut expected = mat.clone();
+    diag.set_diagonal(&[2.0; 25]);
+    expected.set_diagonal(&[2.0; 25]);
+    same(&diag, &expected);
+
+    assert_eq!(
+        csr.clone().into_csc().iter_aij().collect::<Vec<_>>(),
+        mat.clone().into_csc().iter_aij().collect::<Vec<_>>()
+    );
+
+    // Factorisations and preconditioners
+    let rhs = (0..25).map(|i| (i as f64).sin()).collect::<Vec<_>>();
+    let mut actual = vec![0.0; 25];
+    let mut expected = vec![0.0; 25];
+
+    csr.ilu0().unwrap().solve(&rhs, &mut actual);
+    mat.ilu0().unwrap().solve(&rhs, &mut expected);
+    assert_eq!(actual, expected);
+
+    csr.ic0().unwrap().solve(&rhs, &mut actual);
+    mat.ic0().unwrap().solve(&rhs, &mut expected);
+    assert_eq!(actual, expected);
+
+    csr.clone()
+        .into_csc()
+        .cholesky()
+        .unwrap()
+        .solve(&rhs, &mut actual);
+    mat.clone()
+        .into_csc()
+        .cholesky()
+        .unwrap()
+        .solve(&rhs, &mut expected);
+    assert_eq!(actual, expected);
+
+    let method = StationaryMethod::gauss_seidel(SweepDirection::Symmetric);
+    StationaryIteration::new(&csr, method)
+        .unwrap()
+        .smooth(&rhs, &mut actual, 2);
+    StationaryIteration::new(&mat, method)
+        .unwrap()
+        .smooth(&rhs, &mut expected, 2);
+    assert_eq!(actual, expected);
+}